@@ -0,0 +1,113 @@
+// Compares the old all-pairs detection (with a linear scan for duplicates)
+// against the uniform grid + pair set, for a growing number of projectiles
+//
+// cargo bench --bench broadphase
+//
+// the grid only depends on std, so it's pulled in directly rather than through the whole lib
+#[path = "../src/collision/broadphase.rs"]
+#[allow(dead_code)]
+mod broadphase;
+
+use std::time::{Duration, Instant};
+
+use broadphase::{Aabb, PairSet, UniformGrid};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// roughly a 1080p viewport, with rocket-sized boxes
+const WORLD_WIDTH: f32 = 1920.0;
+const WORLD_HEIGHT: f32 = 1080.0;
+const BOX_WIDTH: f32 = 20.0;
+const BOX_HEIGHT: f32 = 60.0;
+const FRAMES: u32 = 20;
+
+fn random_boxes(rng: &mut StdRng, count: usize) -> Vec<(u32, Aabb)> {
+    (0..count)
+        .map(|index| {
+            let x = rng.gen_range(0.0..WORLD_WIDTH);
+            let y = rng.gen_range(0.0..WORLD_HEIGHT);
+            (
+                index as u32,
+                Aabb {
+                    min_x: x,
+                    min_y: y,
+                    max_x: x + BOX_WIDTH,
+                    max_y: y + BOX_HEIGHT,
+                },
+            )
+        })
+        .collect()
+}
+
+// what detect_geometric_collision_sys used to do
+fn all_pairs(bombs: &[(u32, Aabb)], others: &[(u32, Aabb)]) -> usize {
+    let mut queue: Vec<(u32, u32)> = Vec::new();
+
+    for (bomb, bomb_aabb) in bombs {
+        for (other, other_aabb) in others {
+            let already = queue
+                .iter()
+                .any(|(a, b)| (a == bomb && b == other) || (a == other && b == bomb));
+            if !already && bomb_aabb.intersects(other_aabb) {
+                queue.push((*bomb, *other));
+            }
+        }
+    }
+
+    queue.len()
+}
+
+fn grid(bombs: &[(u32, Aabb)], others: &[(u32, Aabb)]) -> usize {
+    let mut grid = UniformGrid::new(UniformGrid::<u32>::DEFAULT_CELL_SIZE);
+    let mut pairs = PairSet::new();
+    let mut candidates = Vec::new();
+
+    for (other, aabb) in others {
+        grid.insert(*other, *aabb);
+    }
+
+    for (bomb, aabb) in bombs {
+        grid.query(aabb, &mut candidates);
+        for other in &candidates {
+            pairs.insert(*bomb, *other);
+        }
+    }
+
+    pairs.len()
+}
+
+fn time(f: impl Fn() -> usize) -> (Duration, usize) {
+    let start = Instant::now();
+    let mut found = 0;
+    for _ in 0..FRAMES {
+        found = f();
+    }
+    (start.elapsed() / FRAMES, found)
+}
+
+fn main() {
+    println!(
+        "{:>8} {:>8} {:>14} {:>14} {:>8}",
+        "bombs", "others", "all pairs", "grid", "pairs"
+    );
+
+    for count in [10, 50, 100, 250, 500, 1000] {
+        // others ids start after the bombs, so they never clash
+        let mut rng = StdRng::seed_from_u64(count as u64);
+        let bombs = random_boxes(&mut rng, count);
+        let others: Vec<_> = random_boxes(&mut rng, count)
+            .into_iter()
+            .map(|(index, aabb)| (index + count as u32, aabb))
+            .collect();
+
+        let (all_pairs_time, all_pairs_found) = time(|| all_pairs(&bombs, &others));
+        let (grid_time, grid_found) = time(|| grid(&bombs, &others));
+
+        // the grid only narrows things down, it must never miss a pair
+        assert_eq!(all_pairs_found, grid_found);
+
+        println!(
+            "{:>8} {:>8} {:>14?} {:>14?} {:>8}",
+            count, count, all_pairs_time, grid_time, grid_found
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use awsm_web::webgl::{
+    PixelFormat, SimpleTextureOptions, TextureTarget, TextureWrapMode, WebGlTextureSource,
+};
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement, WebGl2RenderingContext,
+};
+
+use super::packer::pack;
+use crate::{
+    media::Media,
+    prelude::*,
+    renderer::{uvs::Uvs, Renderer},
+};
+
+// One sprite sheet cell (or a whole single image), wherever it ended up
+// cells of the same sheet can land on different pages, so each one carries its own page
+// it's the index rather than the texture, so the sim can hold onto regions without a gl context
+#[derive(Debug, Clone)]
+pub struct AtlasRegion {
+    pub page: usize,
+    // y from the top, like the sprite sheet info
+    pub bounds: Bounds,
+    pub atlas_width: f32,
+    pub atlas_height: f32,
+}
+
+impl AtlasRegion {
+    pub fn width(&self) -> f32 {
+        self.bounds.width as f32
+    }
+
+    pub fn height(&self) -> f32 {
+        self.bounds.height as f32
+    }
+
+    pub fn uvs(&self) -> Uvs {
+        Uvs::new(self.atlas_width, self.atlas_height, &self.bounds)
+    }
+}
+
+pub type TextureAtlasViewMut<'a> = UniqueViewMut<'a, TextureAtlas>;
+pub type TextureAtlasView<'a> = UniqueView<'a, TextureAtlas>;
+
+// kept around after startup, so it can be rebuilt if the gl context is lost
+#[derive(Component, Unique)]
+pub struct TextureAtlas {
+    // keyed by the image src, one region per cell (single images have just the one)
+    regions: HashMap<String, Vec<AtlasRegion>>,
+    // one per page
+    pub texture_ids: Vec<Id>,
+}
+
+impl TextureAtlas {
+    // a gutter around every cell, so linear filtering doesn't pick up the neighbours
+    pub const PADDING: u32 = 2;
+    // plenty for everything we have, and what pretty much every device supports
+    pub const MAX_SIZE: u32 = 4096;
+
+    pub fn new(renderer: &mut Renderer, media: &Media) -> Result<Self> {
+        let sources = atlas_sources(media)?;
+
+        let rects: Vec<(&HtmlImageElement, &Bounds)> = sources
+            .iter()
+            .flat_map(|(img, cells)| cells.iter().map(move |cell| (*img, cell)))
+            .collect();
+
+        let sizes: Vec<(u32, u32)> = rects
+            .iter()
+            .map(|(_, cell)| (cell.width as u32, cell.height as u32))
+            .collect();
+
+        let max_size = renderer
+            .gl
+            .gl
+            .get_parameter(WebGl2RenderingContext::MAX_TEXTURE_SIZE)
+            .ok()
+            .and_then(|value| value.as_f64())
+            .map_or(Self::MAX_SIZE, |value| (value as u32).min(Self::MAX_SIZE));
+
+        let packing = pack(&sizes, max_size, Self::PADDING)?;
+
+        let pages = packing
+            .pages
+            .iter()
+            .map(|&(width, height)| {
+                let canvas = create_canvas(width, height)?;
+                let ctx = canvas_context(&canvas)?;
+                Ok((canvas, ctx))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for ((img, cell), placement) in rects.iter().zip(packing.placements.iter()) {
+            let (_, ctx) = &pages[placement.page];
+            ctx.draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+                img,
+                cell.x,
+                cell.y,
+                cell.width,
+                cell.height,
+                placement.x as f64,
+                placement.y as f64,
+                cell.width,
+                cell.height,
+            )
+            .map_err(|_| anyhow!("could not draw {} into the atlas", img.src()))?;
+        }
+
+        let texture_ids = pages
+            .iter()
+            .map(|(canvas, _)| {
+                let texture_id = renderer.create_texture()?;
+                renderer.assign_simple_texture(
+                    texture_id,
+                    TextureTarget::Texture2d,
+                    &SimpleTextureOptions {
+                        pixel_format: PixelFormat::Rgba,
+                        wrap_s: Some(TextureWrapMode::ClampToEdge),
+                        wrap_t: Some(TextureWrapMode::ClampToEdge),
+                        ..SimpleTextureOptions::default()
+                    },
+                    &WebGlTextureSource::CanvasElement(canvas),
+                )?;
+                Ok(texture_id)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut regions: HashMap<String, Vec<AtlasRegion>> = HashMap::new();
+        for ((img, cell), placement) in rects.iter().zip(packing.placements.iter()) {
+            let (page_width, page_height) = packing.pages[placement.page];
+            regions.entry(img.src()).or_default().push(AtlasRegion {
+                page: placement.page,
+                bounds: Bounds {
+                    x: placement.x as f64,
+                    y: placement.y as f64,
+                    width: cell.width,
+                    height: cell.height,
+                },
+                atlas_width: page_width as f32,
+                atlas_height: page_height as f32,
+            });
+        }
+
+        log::info!(
+            "packed {} sprites into {} atlas page(s): {:?}",
+            rects.len(),
+            packing.pages.len(),
+            packing.pages
+        );
+
+        Ok(Self {
+            regions,
+            texture_ids,
+        })
+    }
+
+    // all the cells of a sprite sheet, in the same order as its info
+    pub fn regions(&self, img: &HtmlImageElement) -> Result<&[AtlasRegion]> {
+        self.regions
+            .get(&img.src())
+            .map(|regions| regions.as_slice())
+            .ok_or_else(|| anyhow!("{} is not in the atlas", img.src()))
+    }
+
+    // a single image, e.g. a projectile
+    pub fn image(&self, img: &HtmlImageElement) -> Result<&AtlasRegion> {
+        self.regions(img)?
+            .first()
+            .ok_or_else(|| anyhow!("{} has no cells in the atlas", img.src()))
+    }
+
+    // the packing only depends on the media, so a rebuilt atlas has the same pages
+    pub fn texture_id(&self, page: usize) -> Id {
+        self.texture_ids[page]
+    }
+}
+
+// every image that gets packed, along with the cells to take from it
+fn atlas_sources(media: &Media) -> Result<Vec<(&HtmlImageElement, Vec<Bounds>)>> {
+    let mut sources = Vec::new();
+
+    let objects = &media.objects;
+    sources.push((&objects.launcher_img, objects.launcher_info.cells()?));
+    sources.push((&objects.explosion_img, objects.explosion_info.cells()?));
+    for img in [
+        &objects.rocket_bad_img,
+        &objects.bullet_img,
+        &objects.rocket_good_img,
+    ] {
+        sources.push((img, vec![whole_image(img)]));
+    }
+
+    let terrorists = &media.terrorists;
+    if let Some(t) = &terrorists.one {
+        sources.push((&t.blast_img, t.blast_info.cells()?));
+        sources.push((&t.hurt_img, t.hurt_info.cells()?));
+        sources.push((&t.idle_img, t.idle_info.cells()?));
+        sources.push((&t.walk_img, t.walk_info.cells()?));
+    }
+    if let Some(t) = &terrorists.two {
+        sources.push((&t.hurt_img, t.hurt_info.cells()?));
+        sources.push((&t.idle_img, t.idle_info.cells()?));
+        sources.push((&t.shooting_img, t.shooting_info.cells()?));
+        sources.push((&t.walk_img, t.walk_info.cells()?));
+    }
+    if let Some(t) = &terrorists.three {
+        sources.push((&t.hurt_img, t.hurt_info.cells()?));
+        sources.push((&t.idle_img, t.idle_info.cells()?));
+        sources.push((&t.shoot_img, t.shoot_info.cells()?));
+        sources.push((&t.walk_img, t.walk_info.cells()?));
+    }
+    if let Some(t) = &terrorists.four {
+        sources.push((&t.hurt_img, t.hurt_info.cells()?));
+        sources.push((&t.idle_img, t.idle_info.cells()?));
+        sources.push((&t.shoot_img, t.shoot_info.cells()?));
+    }
+
+    Ok(sources)
+}
+
+fn whole_image(img: &HtmlImageElement) -> Bounds {
+    Bounds {
+        x: 0.0,
+        y: 0.0,
+        width: img.width() as f64,
+        height: img.height() as f64,
+    }
+}
+
+pub(crate) fn create_canvas(width: u32, height: u32) -> Result<HtmlCanvasElement> {
+    let canvas: HtmlCanvasElement = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| anyhow!("no document"))?
+        .create_element("canvas")
+        .map_err(|_| anyhow!("could not create canvas"))?
+        .unchecked_into();
+
+    canvas.set_width(width);
+    canvas.set_height(height);
+
+    Ok(canvas)
+}
+
+pub(crate) fn canvas_context(canvas: &HtmlCanvasElement) -> Result<CanvasRenderingContext2d> {
+    Ok(canvas
+        .get_context("2d")
+        .map_err(|_| anyhow!("could not get 2d context"))?
+        .ok_or_else(|| anyhow!("no 2d context"))?
+        .unchecked_into())
+}
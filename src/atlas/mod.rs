@@ -0,0 +1,4 @@
+// All the sprite sheet cells and single images, packed into one (or a few) big textures after loading
+// fewer textures means fewer switches, and more sprites that can share a batch (see renderer/batch.rs)
+pub mod data;
+pub mod packer;
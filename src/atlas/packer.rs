@@ -0,0 +1,158 @@
+// Shelf packing, first fit by decreasing height
+// rects are sorted tallest first, and each one goes onto the first shelf (of any page) that it fits on
+// if none fit, a new shelf is opened on the first page with room left, and failing that, a new page
+//
+// std only, so it can be tested natively (see tests/atlas_packer.rs)
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub page: usize,
+    // top-left corner, inside the gutter
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packing {
+    // same order as the sizes that were passed in
+    pub placements: Vec<Placement>,
+    // (width, height) of each page, just big enough for what's on it
+    pub pages: Vec<(u32, u32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackError {
+    // the rect at this index doesn't fit on a page even by itself
+    TooLarge {
+        index: usize,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge {
+                index,
+                width,
+                height,
+            } => write!(
+                f,
+                "rect {index} ({width}x{height}) is too large for the atlas"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    // where the next rect goes
+    x: u32,
+}
+
+struct Page {
+    shelves: Vec<Shelf>,
+    width: u32,
+    height: u32,
+}
+
+// `padding` is a transparent gutter on every side of each rect, so that filtering doesn't bleed between neighbours
+pub fn pack(sizes: &[(u32, u32)], max_size: u32, padding: u32) -> Result<Packing, PackError> {
+    let slot = |index: usize| {
+        let (width, height) = sizes[index];
+        (width + padding * 2, height + padding * 2)
+    };
+
+    for (index, &(width, height)) in sizes.iter().enumerate() {
+        if width + padding * 2 > max_size || height + padding * 2 > max_size {
+            return Err(PackError::TooLarge {
+                index,
+                width,
+                height,
+            });
+        }
+    }
+
+    // tallest first, then widest, then the original order so the result is always the same
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a_width, a_height) = sizes[a];
+        let (b_width, b_height) = sizes[b];
+        b_height
+            .cmp(&a_height)
+            .then(b_width.cmp(&a_width))
+            .then(a.cmp(&b))
+    });
+
+    let mut pages: Vec<Page> = Vec::new();
+    let mut placements = vec![
+        Placement {
+            page: 0,
+            x: 0,
+            y: 0
+        };
+        sizes.len()
+    ];
+
+    for index in order {
+        let (width, height) = slot(index);
+
+        let existing = pages.iter().enumerate().find_map(|(page_index, page)| {
+            page.shelves
+                .iter()
+                .position(|shelf| height <= shelf.height && shelf.x + width <= max_size)
+                .map(|shelf_index| (page_index, shelf_index))
+        });
+
+        let (page_index, shelf_index) = match existing {
+            Some(found) => found,
+            None => {
+                let page_index = match pages
+                    .iter()
+                    .position(|page| page.height + height <= max_size)
+                {
+                    Some(page_index) => page_index,
+                    None => {
+                        pages.push(Page {
+                            shelves: Vec::new(),
+                            width: 0,
+                            height: 0,
+                        });
+                        pages.len() - 1
+                    }
+                };
+
+                let page = &mut pages[page_index];
+                page.shelves.push(Shelf {
+                    y: page.height,
+                    height,
+                    x: 0,
+                });
+                page.height += height;
+                (page_index, page.shelves.len() - 1)
+            }
+        };
+
+        let page = &mut pages[page_index];
+        let shelf = &mut page.shelves[shelf_index];
+
+        placements[index] = Placement {
+            page: page_index,
+            x: shelf.x + padding,
+            y: shelf.y + padding,
+        };
+
+        shelf.x += width;
+        page.width = page.width.max(shelf.x);
+    }
+
+    Ok(Packing {
+        placements,
+        pages: pages.iter().map(|page| (page.width, page.height)).collect(),
+    })
+}
@@ -1,7 +1,7 @@
 use awsm_web::{audio::AudioSource, loaders};
 use wasm_bindgen_futures::spawn_local;
 
-use crate::{media::MediaView, prelude::*};
+use crate::{hit::data::HitEventQueueView, media::MediaView, prelude::*};
 
 use super::{AudioEvent, AudioEventQueue, AudioPlayer, AudioPlayerViewMut};
 
@@ -39,3 +39,13 @@ pub fn audio_event_process_sys(
         };
     }
 }
+
+// one impact sound per update, no matter how many things hit at once
+pub fn audio_hit_sys(
+    hit_events: HitEventQueueView,
+    mut audio_events: UniqueViewMut<AudioEventQueue>,
+) {
+    if !hit_events.is_empty() {
+        audio_events.push(AudioEvent::CollisionImpact);
+    }
+}
@@ -2,13 +2,24 @@ use awsm_web::webgl::{BeginMode, BlendFactor, BufferData, BufferTarget, BufferUs
 
 use super::data::Background;
 use crate::{
+    level::data::Level,
     prelude::*,
     renderer::{buffers::Buffers, uvs::Uvs, Renderer},
 };
 
 impl Background {
-    pub fn resize(&mut self, width: f64, height: f64) {
-        self.width = width as f32;
-        self.height = height as f32;
+    // picks the pane for a new run, the level's own if it has one, otherwise the next in the rotation
+    pub fn start_run(&mut self, level: Option<&Level>) {
+        let pane_count = self.texture_ids.len().max(1);
+        self.pane = match level.and_then(|level| level.background) {
+            Some(pane) => {
+                if pane > pane_count {
+                    log::warn!("background pane {pane} isn't loaded (see max_bg_panes)");
+                }
+                (pane - 1) % pane_count
+            }
+            None => self.run % pane_count,
+        };
+        self.run += 1;
     }
 }
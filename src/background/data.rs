@@ -3,7 +3,10 @@ use awsm_web::webgl::{
     TextureWrapMode, VertexArray, WebGl2Renderer, WebGlTextureSource,
 };
 
+use serde::Deserialize;
+
 use crate::{
+    layout::scaling::{DESIGN_HEIGHT, DESIGN_WIDTH},
     media::Media,
     prelude::*,
     renderer::{shaders::ShaderProgram, Renderer},
@@ -15,41 +18,111 @@ pub type BackgroundView<'a> = UniqueView<'a, Background>;
 #[derive(Component, Unique)]
 pub struct Background {
     pub shader: ShaderProgram,
+    // pane -> layers, back to front
     pub texture_ids: Vec<Vec<Id>>,
     pub width: f32,
     pub height: f32,
-    pub cloud_offset: f64,
+    // same order as the layers
+    pub layers: Vec<BackgroundLayer>,
+    // horizontal uv offset of each layer, from autoscrolling
+    pub scroll_offsets: Vec<f64>,
+    // index into texture_ids
+    pub pane: usize,
+    // bumped on every restart, for rotating the panes
+    pub run: usize,
 }
 
 impl Background {
-    pub fn new(renderer: &mut Renderer, media: &Media) -> Result<Self> {
-        let mut texture_ids = Vec::new();
-        for bgs in &media.bg {
-            let mut ids = Vec::new();
-            for bg in bgs {
-                let texture_id = renderer.create_texture()?;
-                renderer.assign_simple_texture(
-                    texture_id,
-                    TextureTarget::Texture2d,
-                    &SimpleTextureOptions {
-                        pixel_format: PixelFormat::Rgba,
-                        wrap_s: Some(TextureWrapMode::Repeat),
-                        wrap_t: Some(TextureWrapMode::Repeat),
-                        ..SimpleTextureOptions::default()
-                    },
-                    &WebGlTextureSource::ImageElement(bg),
-                )?;
-                ids.push(texture_id);
-            }
-            texture_ids.push(ids);
-        }
-
+    // the seed picks where the rotation starts, so it's not always the first pane
+    pub fn new(renderer: &mut Renderer, media: &Media, seed: u64) -> Result<Self> {
         Ok(Self {
             shader: renderer.shaders.programs.sprite.clone(),
-            texture_ids,
-            width: 0.0,
-            height: 0.0,
-            cloud_offset: 0.0,
+            scroll_offsets: vec![0.0; media.data.background.layers.len()],
+            layers: media.data.background.layers.clone(),
+            texture_ids: create_textures(renderer, media)?,
+            // covers the design area exactly, the images are made at that size anyway
+            width: DESIGN_WIDTH as f32,
+            height: DESIGN_HEIGHT as f32,
+            pane: 0,
+            run: (seed % media.bg.len().max(1) as u64) as usize,
         })
     }
+
+    // the gl context was lost, the pane and scrolling carry on as they were
+    pub fn rebuild(&mut self, renderer: &mut Renderer, media: &Media) -> Result<()> {
+        self.shader = renderer.shaders.programs.sprite.clone();
+        self.texture_ids = create_textures(renderer, media)?;
+        Ok(())
+    }
+
+    // the per-layer settings, missing ones just sit still in the foreground
+    pub fn layer(&self, idx: usize) -> BackgroundLayer {
+        self.layers.get(idx).cloned().unwrap_or_default()
+    }
+}
+
+// pane -> layers
+fn create_textures(renderer: &mut Renderer, media: &Media) -> Result<Vec<Vec<Id>>> {
+    let mut texture_ids = Vec::new();
+    for bgs in &media.bg {
+        let mut ids = Vec::new();
+        for bg in bgs {
+            let texture_id = renderer.create_texture()?;
+            renderer.assign_simple_texture(
+                texture_id,
+                TextureTarget::Texture2d,
+                &SimpleTextureOptions {
+                    pixel_format: PixelFormat::Rgba,
+                    wrap_s: Some(TextureWrapMode::Repeat),
+                    wrap_t: Some(TextureWrapMode::Repeat),
+                    ..SimpleTextureOptions::default()
+                },
+                &WebGlTextureSource::ImageElement(bg),
+            )?;
+            ids.push(texture_id);
+        }
+        texture_ids.push(ids);
+    }
+
+    Ok(texture_ids)
+}
+
+// Per-layer settings (see media/data/background.json)
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackgroundLayer {
+    // how much it moves along with the camera, 0 is stuck to the screen (far away) and 1 moves with the world
+    pub parallax: f32,
+    // autoscroll, in layer widths per second
+    #[serde(default)]
+    pub scroll: f64,
+}
+
+impl Default for BackgroundLayer {
+    fn default() -> Self {
+        Self {
+            parallax: 1.0,
+            scroll: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackgroundLayers {
+    // back to front, like the l1..l7 images
+    pub layers: Vec<BackgroundLayer>,
+}
+
+impl BackgroundLayers {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let data: Self = serde_json::from_str(json)?;
+        for (idx, layer) in data.layers.iter().enumerate() {
+            if !(0.0..=1.0).contains(&layer.parallax) {
+                return Err(anyhow!(
+                    "background layer {} parallax must be within 0..1",
+                    idx + 1
+                ));
+            }
+        }
+        Ok(data)
+    }
 }
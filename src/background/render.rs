@@ -2,12 +2,13 @@ use awsm_web::webgl::{BeginMode, BlendFactor, BufferData, BufferTarget, BufferUs
 
 use super::data::Background;
 use crate::{
+    camera::Camera,
     prelude::*,
     renderer::{buffers::Buffers, uvs::Uvs, Renderer},
 };
 
 impl Background {
-    pub fn render(&self, renderer: &mut Renderer) -> Result<()> {
+    pub fn render(&self, renderer: &mut Renderer, camera: &Camera) -> Result<()> {
         renderer.toggle(GlToggle::Blend, true);
         renderer.set_blend_func(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha);
         renderer.set_depth_func(awsm_web::webgl::CmpFunction::Less);
@@ -27,26 +28,29 @@ impl Background {
 
         let mut model_matrix_data: [f32; 16] = [0.0; 16];
 
-        for (idx, texture_id) in self.texture_ids[0].iter().enumerate() {
+        let (camera_x, camera_y) = camera.center();
+
+        for (idx, texture_id) in self.texture_ids[self.pane].iter().enumerate() {
+            let layer = self.layer(idx);
+            // goes part of the way with the camera, so the farther back it is the less it shifts on screen
+            let lag = 1.0 - layer.parallax;
             let mat = nalgebra_glm::translate(
                 &Mat4::identity(),
-                &Vec3::new(-self.width / 2.0, -self.height / 2.0, (idx as f32)),
+                &Vec3::new(
+                    (-self.width / 2.0) + (camera_x as f32 * lag),
+                    (-self.height / 2.0) + (camera_y as f32 * lag),
+                    idx as f32,
+                ),
             );
 
             mat.write_to_vf32(&mut model_matrix_data);
             renderer.upload_uniform_mat_4_name("u_model", &model_matrix_data)?;
-            if idx == 2 {
-                renderer
-                    .upload_uniform_fvals_2_name("u_uv_offset", (self.cloud_offset as f32, 0.0));
-            } else {
-                renderer.upload_uniform_fvals_2_name("u_uv_offset", (0.0, 0.0));
-            }
-
-            renderer.upload_uniform_mat_4_name("u_model", &model_matrix_data)?;
+            let scroll_offset = self.scroll_offsets.get(idx).copied().unwrap_or_default();
+            renderer.upload_uniform_fvals_2_name("u_uv_offset", (scroll_offset as f32, 0.0));
 
             renderer.upload_uniform_fvals_4_name("u_tint", (1.0, 1.0, 1.0, 1.0));
             renderer.activate_texture_sampler_name(*texture_id, "u_sampler")?;
-            renderer.draw_arrays(BeginMode::TriangleStrip, 0, 4);
+            renderer.draw_arrays_counted(BeginMode::TriangleStrip, 0, 4);
         }
 
         Ok(())
@@ -2,7 +2,13 @@ use super::data::BackgroundViewMut;
 use crate::{prelude::*, tick::BeginTickView};
 
 pub fn background_move_sys(mut background: BackgroundViewMut, tick: BeginTickView) {
-    background.cloud_offset += tick.delta * 0.00001;
-
-    // TODO - move background layers
+    let background = &mut *background;
+    for (offset, layer) in background
+        .scroll_offsets
+        .iter_mut()
+        .zip(background.layers.iter())
+    {
+        // wrapped, so it doesn't lose precision over a long session
+        *offset = (*offset + layer.scroll * tick.delta / 1000.0).fract();
+    }
 }
@@ -6,6 +6,7 @@ pub type BomberView<'a> = UniqueView<'a, Bomber>;
 #[derive(Component, Unique)]
 pub struct Bomber {
     pub drop_countdown: Option<f64>,
+    pub bombs_dropped: u32,
 }
 
 impl Bomber {
@@ -13,6 +14,7 @@ impl Bomber {
         Self {
             //drop_countdown: None
             drop_countdown: Some(CONFIG.initial_drop_countdown),
+            bombs_dropped: 0,
         }
     }
 }
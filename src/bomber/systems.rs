@@ -1,7 +1,10 @@
 use crate::{
     config::CONFIG,
+    difficulty::data::DifficultyView,
+    level::data::{BombKind, LevelRunnerView},
     prelude::*,
     projectiles::data::{ProjectileSpawnerViewMut, ProjectileToSpawn},
+    stats::data::RunStatsView,
     tick::UpdateTickView,
 };
 
@@ -12,14 +15,61 @@ pub fn bomber_drop_sys(
     mut projectile_spawner: ProjectileSpawnerViewMut,
     mut rand: RandViewMut,
     tick: UpdateTickView,
+    difficulty: DifficultyView,
+    stats: RunStatsView,
+    level_runner: LevelRunnerView,
 ) {
+    // a level brings its own waves, see level_wave_sys
+    if level_runner.is_active() {
+        return;
+    }
+
+    let bomber = &mut *bomber;
+
     if let Some(drop_countdown) = &mut bomber.drop_countdown {
         *drop_countdown -= tick.delta;
 
         if *drop_countdown <= 0.0 {
-            *drop_countdown = rand.gen_range(CONFIG.live_drop_countdown_range.clone());
+            let stage = difficulty.current(&stats);
+
+            // never drop more than are left to survive
+            let remaining = CONFIG.bombs_to_win - bomber.bombs_dropped;
+            let count = stage.bombs_per_drop.max(1).min(remaining);
+
+            for _ in 0..count {
+                let half_spread = (stage.spread / 2.0) as f32;
+                let x = if half_spread > 0.0 {
+                    0.5 + rand.gen_range(-half_spread..half_spread)
+                } else {
+                    0.5
+                };
+                let (min_acceleration, max_acceleration) = stage.acceleration;
+                let acceleration = if min_acceleration < max_acceleration {
+                    rand.gen_range(min_acceleration..max_acceleration)
+                } else {
+                    min_acceleration
+                };
+
+                projectile_spawner.to_spawn.push(ProjectileToSpawn::Bomb {
+                    x,
+                    acceleration: acceleration as f32,
+                    kind: BombKind::Standard,
+                });
+            }
+            bomber.bombs_dropped += count;
 
-            projectile_spawner.to_spawn.push(ProjectileToSpawn::Bomb {});
+            // that was the last one, survive it and you win
+            bomber.drop_countdown = if bomber.bombs_dropped >= CONFIG.bombs_to_win {
+                None
+            } else {
+                let (min_interval, max_interval) =
+                    CONFIG.drop_interval.unwrap_or(stage.drop_interval);
+                Some(if min_interval < max_interval {
+                    rand.gen_range(min_interval..max_interval)
+                } else {
+                    min_interval
+                })
+            };
         }
     }
 }
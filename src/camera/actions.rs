@@ -1,16 +1,34 @@
-use super::data::Camera;
+use super::data::{Camera, CameraOffset};
 use crate::prelude::*;
 
 impl Camera {
-    pub fn resize(&mut self, viewport_width: f64, viewport_height: f64) {
-        let left = ((-viewport_width / (2.0 * self.zoom)) + self.x) as f32;
-        let right = ((viewport_width / (2.0 * self.zoom)) + self.x) as f32;
-        let bottom = ((-viewport_height / (2.0 * self.zoom)) + self.y) as f32;
-        let top = ((viewport_height / (2.0 * self.zoom)) + self.y) as f32;
+    pub fn resize(&mut self, view_width: f64, view_height: f64) {
+        self.view_width = view_width;
+        self.view_height = view_height;
+        self.update_matrices(&CameraOffset::default());
+    }
+
+    // the resting position with the effects on top, called every frame by camera_effects_sys
+    pub fn update_matrices(&mut self, offset: &CameraOffset) {
+        self.offset = *offset;
+        let zoom = self.zoom * offset.zoom;
+        let (x, y) = self.center();
+        let (view_width, view_height) = (self.view_width, self.view_height);
+
+        let left = ((-view_width / (2.0 * zoom)) + x) as f32;
+        let right = ((view_width / (2.0 * zoom)) + x) as f32;
+        let bottom = ((-view_height / (2.0 * zoom)) + y) as f32;
+        let top = ((view_height / (2.0 * zoom)) + y) as f32;
 
         self._proj_matrix =
             Mat4::new_orthographic(left, right, bottom, top, -Self::Z_DEPTH, Self::Z_DEPTH);
 
+        // rolls around the middle of what's on screen, not the world origin
+        let center = Vec3::new(x as f32, y as f32, 0.0);
+        self._view_matrix = nalgebra_glm::translate(&Mat4::identity(), &center)
+            * nalgebra_glm::rotate_z(&Mat4::identity(), offset.roll)
+            * nalgebra_glm::translate(&Mat4::identity(), &-center);
+
         self._view_matrix
             .write_to_vf32(&mut self._buffer_data[0..16]);
         self._proj_matrix
@@ -5,9 +5,15 @@ pub type CameraView<'a> = UniqueView<'a, Camera>;
 
 #[derive(Component, Unique)]
 pub struct Camera {
+    // the resting position, effects are added on top of this (see CameraOffset)
     pub zoom: f64,
     pub x: f64,
     pub y: f64,
+    // how much of the world is on screen at zoom 1, in design units (see layout/scaling.rs)
+    pub view_width: f64,
+    pub view_height: f64,
+    // what the effects added on top, as of the last update_matrices
+    pub offset: CameraOffset,
     pub buffer_id: Id,
     pub(super) _view_matrix: Mat4,
     pub(super) _proj_matrix: Mat4,
@@ -23,6 +29,9 @@ impl Camera {
             zoom: 1.0,
             x: 0.0,
             y: 0.0,
+            view_width: 0.0,
+            view_height: 0.0,
+            offset: CameraOffset::default(),
             buffer_id,
             _view_matrix: Mat4::identity(),
             _proj_matrix: Mat4::identity(),
@@ -43,4 +52,77 @@ impl Camera {
     pub fn view_proj_matrix(&self) -> Mat4 {
         self._proj_matrix * self._view_matrix
     }
+
+    // the middle of the screen in world space, effects included
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.offset.x, self.y + self.offset.y)
+    }
+}
+
+// Where the effects have moved the camera this frame, relative to its resting position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraOffset {
+    pub x: f64,
+    pub y: f64,
+    // multiplies Camera::zoom
+    pub zoom: f64,
+    // radians, around the middle of the screen
+    pub roll: f32,
+}
+
+impl Default for CameraOffset {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            zoom: 1.0,
+            roll: 0.0,
+        }
+    }
+}
+
+pub type CameraEffectsViewMut<'a> = UniqueViewMut<'a, CameraEffects>;
+pub type CameraEffectsView<'a> = UniqueView<'a, CameraEffects>;
+
+// Shake, zoom punch and follow, hits push these up (see camera_hit_sys)
+// and camera_effects_sys settles them back down every frame
+#[derive(Component, Unique, Debug, Default)]
+pub struct CameraEffects {
+    // 0..1, the shake goes with trauma squared so small knocks stay subtle
+    pub trauma: f32,
+    // extra zoom, e.g. 0.05 is 5% closer
+    pub punch: f32,
+    // the smoothed follow position, in world space
+    pub follow_x: f64,
+    pub follow_y: f64,
+    // seconds, only drives the shake pattern
+    pub time: f64,
+    // performance.now() of the last drawn frame
+    pub last_timestamp: Option<f64>,
+}
+
+impl CameraEffects {
+    // at full trauma
+    pub const SHAKE_OFFSET: f64 = 24.0;
+    pub const SHAKE_ROLL: f32 = 0.03;
+    pub const SHAKE_FREQUENCY: f64 = 18.0;
+    // per second
+    pub const TRAUMA_DECAY: f32 = 1.5;
+    pub const PUNCH_DECAY: f32 = 0.4;
+    pub const PUNCH_MAX: f32 = 0.12;
+
+    // zoomed in a little while following, so there's room to move without showing past the background
+    pub const FOLLOW_ZOOM: f64 = 1.2;
+    // how far (in design units) the enemy can get from the middle before the camera moves
+    pub const FOLLOW_DEAD_ZONE: (f64, f64) = (80.0, 60.0);
+    // ms to catch up halfway
+    pub const FOLLOW_EASE: f64 = 180.0;
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    pub fn add_punch(&mut self, amount: f32) {
+        self.punch = (self.punch + amount).min(Self::PUNCH_MAX);
+    }
 }
@@ -1,7 +1,12 @@
 use awsm_web::webgl::BufferUsage;
 
-use super::data::CameraView;
+use super::data::{CameraEffects, CameraEffectsViewMut, CameraOffset, CameraView, CameraViewMut};
 use crate::{
+    collision::data::Collider,
+    config::CONFIG,
+    dom::DomView,
+    enemy::controller::data::ActiveEnemyController,
+    hit::data::{HitEventQueueView, HitKind},
     prelude::*,
     renderer::{Renderer, RendererViewMut},
 };
@@ -17,3 +22,115 @@ pub fn camera_update_ubo_sys(camera: CameraView, mut renderer: RendererViewMut)
 
     renderer.activate_uniform_buffer_loc(camera.buffer_id, Renderer::UBO_CAMERA);
 }
+
+// Every hit shakes things up a bit, bombs more so (they're what explodes)
+// and a bomb landing on an enemy gets a quick zoom punch on top
+pub fn camera_hit_sys(hit_events: HitEventQueueView, mut effects: CameraEffectsViewMut) {
+    for hit in hit_events.iter() {
+        let bomb = [hit.attacker_kind, hit.victim_kind]
+            .into_iter()
+            .find_map(|kind| match kind {
+                HitKind::Bomb(kind) => Some(kind),
+                _ => None,
+            });
+
+        match bomb {
+            Some(kind) => {
+                // relative to a standard bomb
+                let weight = kind.damage() / 40.0;
+                effects.add_trauma(0.3 * weight);
+                if hit.victim_kind.is_enemy() {
+                    effects.add_punch(0.04 * weight);
+                }
+            }
+            None => effects.add_trauma(0.1),
+        }
+    }
+}
+
+// Runs on every drawn frame, by the wall clock like post_effects_sys
+// it's purely visual, so none of this feeds back into the simulation
+pub fn camera_effects_sys(
+    dom: DomView,
+    mut camera: CameraViewMut,
+    mut effects: CameraEffectsViewMut,
+    colliders: View<Collider>,
+    active_controllers: View<ActiveEnemyController>,
+) {
+    let now = dom.window.performance().unwrap_ext().now();
+    // capped, so that coming back from a pause doesn't skip the effects straight to the end
+    let elapsed_ms = effects
+        .last_timestamp
+        .map_or(0.0, |last| now - last)
+        .clamp(0.0, 100.0);
+    effects.last_timestamp = Some(now);
+    let elapsed = (elapsed_ms / 1000.0) as f32;
+    effects.time += elapsed_ms / 1000.0;
+
+    effects.trauma = (effects.trauma - CameraEffects::TRAUMA_DECAY * elapsed).max(0.0);
+    effects.punch = (effects.punch - CameraEffects::PUNCH_DECAY * elapsed).max(0.0);
+
+    let shake = (effects.trauma * effects.trauma) as f64;
+    let t = effects.time * CameraEffects::SHAKE_FREQUENCY;
+    let mut offset = CameraOffset {
+        x: CameraEffects::SHAKE_OFFSET * shake * shake_noise(t, 0.0),
+        y: CameraEffects::SHAKE_OFFSET * shake * shake_noise(t, 10.0),
+        zoom: 1.0 + effects.punch as f64,
+        roll: CameraEffects::SHAKE_ROLL * shake as f32 * shake_noise(t, 20.0) as f32,
+    };
+
+    if CONFIG.camera_follow {
+        // with nobody selected it drifts back to the middle
+        let target = (&colliders, &active_controllers)
+            .iter()
+            .next()
+            .map(|(collider, _)| collider.center());
+
+        let (dead_x, dead_y) = CameraEffects::FOLLOW_DEAD_ZONE;
+        let (mut goal_x, mut goal_y) = (effects.follow_x, effects.follow_y);
+        match target {
+            Some(target) => {
+                goal_x = dead_zone(goal_x, target.x as f64, dead_x);
+                goal_y = dead_zone(goal_y, target.y as f64, dead_y);
+            }
+            None => {
+                goal_x = 0.0;
+                goal_y = 0.0;
+            }
+        }
+
+        // never past the edges of what's normally on screen
+        let margin = 1.0 - 1.0 / CameraEffects::FOLLOW_ZOOM;
+        let max_x = camera.view_width / 2.0 * margin;
+        let max_y = camera.view_height / 2.0 * margin;
+        goal_x = goal_x.clamp(-max_x, max_x);
+        goal_y = goal_y.clamp(-max_y, max_y);
+
+        let ease = 1.0 - 0.5f64.powf(elapsed_ms / CameraEffects::FOLLOW_EASE);
+        effects.follow_x += (goal_x - effects.follow_x) * ease;
+        effects.follow_y += (goal_y - effects.follow_y) * ease;
+
+        offset.x += effects.follow_x;
+        offset.y += effects.follow_y;
+        offset.zoom *= CameraEffects::FOLLOW_ZOOM;
+    }
+
+    camera.update_matrices(&offset);
+}
+
+// a few sines at odd frequencies, smooth and roughly -1..1
+// not the seeded Rand, since that has to stay in step for replays
+fn shake_noise(t: f64, phase: f64) -> f64 {
+    let t = t + phase;
+    (t.sin() * 0.5) + ((t * 2.3 + 1.7).sin() * 0.3) + ((t * 4.1 + 4.2).sin() * 0.2)
+}
+
+// only moves once the target is outside of the dead zone, and then just enough to keep it on the edge
+fn dead_zone(current: f64, target: f64, size: f64) -> f64 {
+    let diff = target - current;
+    if diff.abs() <= size {
+        current
+    } else {
+        target - size * diff.signum()
+    }
+}
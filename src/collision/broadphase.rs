@@ -0,0 +1,156 @@
+// Cheap "could these possibly touch" pass, before the precise (SAT, then pixel) checks
+// a uniform grid of axis-aligned bounding boxes, rebuilt every frame
+//
+// This file only depends on std so that it can be benchmarked natively, see benches/broadphase.rs
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+impl Aabb {
+    // same layout as Collider::vertices, i.e. 4 (x, y) corners of a possibly rotated rect
+    pub fn from_vertices(vertices: &[f32; 8]) -> Self {
+        let mut aabb = Self {
+            min_x: f32::INFINITY,
+            min_y: f32::INFINITY,
+            max_x: f32::NEG_INFINITY,
+            max_y: f32::NEG_INFINITY,
+        };
+
+        for corner in vertices.chunks_exact(2) {
+            aabb.min_x = aabb.min_x.min(corner[0]);
+            aabb.max_x = aabb.max_x.max(corner[0]);
+            aabb.min_y = aabb.min_y.min(corner[1]);
+            aabb.max_y = aabb.max_y.max(corner[1]);
+        }
+
+        aabb
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min_x <= other.max_x
+            && self.max_x >= other.min_x
+            && self.min_y <= other.max_y
+            && self.max_y >= other.min_y
+    }
+}
+
+// Items go into every cell their box touches
+// so querying only looks at the handful of cells around the query box, rather than everything
+pub struct UniformGrid<T> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<(T, Aabb)>>,
+}
+
+impl<T: Copy + Ord> UniformGrid<T> {
+    // in world units, roughly the size of the bigger things that are inserted
+    pub const DEFAULT_CELL_SIZE: f32 = 256.0;
+
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn insert(&mut self, item: T, aabb: Aabb) {
+        let (min_x, min_y, max_x, max_y) = self.cell_range(&aabb);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.cells.entry((x, y)).or_default().push((item, aabb));
+            }
+        }
+    }
+
+    // fills `out` with every item whose box overlaps, sorted and without duplicates
+    // (sorted so that the results don't depend on hashing, which keeps replays deterministic)
+    pub fn query(&self, aabb: &Aabb, out: &mut Vec<T>) {
+        out.clear();
+
+        let (min_x, min_y, max_x, max_y) = self.cell_range(aabb);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                if let Some(cell) = self.cells.get(&(x, y)) {
+                    out.extend(
+                        cell.iter()
+                            .filter(|(_, other)| aabb.intersects(other))
+                            .map(|(item, _)| *item),
+                    );
+                }
+            }
+        }
+
+        out.sort_unstable();
+        out.dedup();
+    }
+
+    fn cell_range(&self, aabb: &Aabb) -> (i32, i32, i32, i32) {
+        let cell = |value: f32| (value / self.cell_size).floor() as i32;
+        (
+            cell(aabb.min_x),
+            cell(aabb.min_y),
+            cell(aabb.max_x),
+            cell(aabb.max_y),
+        )
+    }
+}
+
+// Unordered pairs, i.e. (a, b) and (b, a) are the same
+pub struct PairSet<T> {
+    pairs: HashSet<(T, T)>,
+}
+
+impl<T: Copy + Ord + Hash> PairSet<T> {
+    pub fn new() -> Self {
+        Self {
+            pairs: HashSet::new(),
+        }
+    }
+
+    // false if it was already there
+    pub fn insert(&mut self, a: T, b: T) -> bool {
+        self.pairs.insert(Self::key(a, b))
+    }
+
+    pub fn contains(&self, a: T, b: T) -> bool {
+        self.pairs.contains(&Self::key(a, b))
+    }
+
+    pub fn remove(&mut self, a: T, b: T) -> bool {
+        self.pairs.remove(&Self::key(a, b))
+    }
+
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    fn key(a: T, b: T) -> (T, T) {
+        if a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+}
+
+impl<T: Copy + Ord + Hash> Default for PairSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,15 @@
-use std::ops::{Deref, DerefMut};
+use std::ops::Deref;
 
 use nalgebra::{Point, Point3};
 use nalgebra_glm::{normalize, Vec2, Vec4};
 use web_sys::WebGlQuery;
 
-use crate::{camera::Camera, prelude::*, renderer::uvs::Uvs, spritesheet::SpriteSheet};
+use crate::{
+    camera::Camera, prelude::*, projectiles::data::Projectile, renderer::uvs::Uvs,
+    spritesheet::SpriteSheet,
+};
+
+use super::{broadphase::PairSet, mask::AlphaMask};
 
 #[derive(Component, Debug, Default)]
 pub struct Collider {
@@ -64,29 +69,67 @@ impl Collider {
         let x = left + ((right - left).abs() / 2.0);
         let y = bottom + ((top - bottom).abs() / 2.0);
 
-        Vec2::new(left, bottom)
+        Vec2::new(x, y)
     }
 }
 
+// how the pixel-perfect part of collision is checked, see Config::collision_backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionBackend {
+    // alpha masks, see mask.rs
+    Cpu,
+    // occlusion queries, see intersection.rs
+    Gpu,
+}
+
 pub type CollisionEventQueueViewMut<'a> = NonSendSync<UniqueViewMut<'a, CollisionEventQueue>>;
 pub type CollisionEventQueueView<'a> = NonSendSync<UniqueView<'a, CollisionEventQueue>>;
+// Only derefs immutably, all changes go through the methods below
+// so that the pair set always matches what's in the queue
 #[derive(Unique, Component, Default)]
 pub struct CollisionEventQueue {
     queue: Vec<CollisionEvent>,
+    pairs: PairSet<EntityId>,
 }
 
 impl CollisionEventQueue {
     pub fn has_any_collision(&self, entity_a: EntityId, entity_b: EntityId) -> bool {
-        self.queue.iter().any(|e| {
-            (e.a.entity == entity_a && e.b.entity == entity_b)
-                || (e.b.entity == entity_a && e.a.entity == entity_b)
-        })
+        self.pairs.contains(entity_a, entity_b)
     }
 }
 
 impl CollisionEventQueue {
     pub fn new() -> Self {
-        Self { queue: Vec::new() }
+        Self {
+            queue: Vec::new(),
+            pairs: PairSet::new(),
+        }
+    }
+
+    pub fn push(&mut self, event: CollisionEvent) {
+        if self.pairs.insert(event.a.entity, event.b.entity) {
+            self.queue.push(event);
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut CollisionEvent> {
+        self.queue.iter_mut()
+    }
+
+    pub fn retain_mut(&mut self, mut f: impl FnMut(&mut CollisionEvent) -> bool) {
+        let pairs = &mut self.pairs;
+        self.queue.retain_mut(|event| {
+            let keep = f(event);
+            if !keep {
+                pairs.remove(event.a.entity, event.b.entity);
+            }
+            keep
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.pairs.clear();
     }
 }
 
@@ -98,22 +141,50 @@ impl Deref for CollisionEventQueue {
     }
 }
 
-impl DerefMut for CollisionEventQueue {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.queue
-    }
-}
-
 pub struct CollisionEvent {
     pub a: CollisionEventTarget,
     pub b: CollisionEventTarget,
     pub occlusion_query: Option<WebGlQuery>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CollisionEventTarget {
     pub entity: EntityId,
     pub vertices: [f32; 8],
     pub uvs: [f32; 8],
-    pub texture_id: Id,
+    // the atlas page the uvs are on
+    pub page: usize,
+    // for the cpu backend, covers the same part of the texture as the uvs
+    pub mask: Arc<AlphaMask>,
+}
+
+impl CollisionEventTarget {
+    // what the enemy looks like right now, i.e. what the player sees
+    // the collider is from the same cell (see update_collider_sys), so the uvs and mask need to be too
+    // the facing is already baked into the vertices (via Scale.x), so they're never flipped
+    pub fn from_cell(
+        entity: EntityId,
+        collider: &Collider,
+        spritesheet: &SpriteSheet,
+        index: usize,
+    ) -> Self {
+        let cell = &spritesheet.cells[index];
+        Self {
+            entity,
+            page: cell.page,
+            vertices: collider.vertices,
+            uvs: cell.uvs().data,
+            mask: spritesheet.masks[index].clone(),
+        }
+    }
+
+    pub fn from_projectile(entity: EntityId, collider: &Collider, projectile: &Projectile) -> Self {
+        Self {
+            entity,
+            page: projectile.page,
+            vertices: collider.vertices,
+            uvs: projectile.uvs,
+            mask: projectile.mask.clone(),
+        }
+    }
 }
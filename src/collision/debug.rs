@@ -19,6 +19,9 @@ use super::data::Collider;
 pub struct CollisionDebugger {
     pub draw: bool,
     pub box_texture_id: Id,
+    // the renderer's DrawStats get logged alongside, see draw_stats_log_sys
+    // performance.now() of the last time they were
+    pub stats_logged_at: Option<f64>,
 }
 
 impl CollisionDebugger {
@@ -83,6 +86,7 @@ impl CollisionDebugger {
         Ok(Self {
             draw: false,
             box_texture_id,
+            stats_logged_at: None,
         })
     }
 }
@@ -130,7 +134,7 @@ impl Collider {
 
         renderer.upload_uniform_fvals_4_name("u_tint", color);
 
-        renderer.draw_arrays(BeginMode::TriangleStrip, 0, 4);
+        renderer.draw_arrays_counted(BeginMode::TriangleStrip, 0, 4);
         Ok(())
     }
 }
@@ -6,9 +6,13 @@ use awsm_web::webgl::{
 };
 use nalgebra_glm::Vec2;
 
-use crate::renderer::Renderer;
+use crate::{atlas::data::TextureAtlas, renderer::Renderer};
 
-use super::data::{Collider, CollisionEvent, CollisionEventQueue, CollisionEventTarget};
+use super::{
+    broadphase::Aabb,
+    data::{Collider, CollisionEvent, CollisionEventQueue, CollisionEventTarget},
+    quad::QuadBasis,
+};
 
 impl Collider {
     pub fn intersects_rect(&self, other: &Collider) -> bool {
@@ -71,7 +75,11 @@ impl CollisionEvent {
     // but occlusion queries aren't guaranteed to be available immediately
     // so we need to check if they're available "later"
     // we could do that at the end of this render, but, meh, we instead defer that to next physics step
-    pub fn render_pixel_intersection(&mut self, renderer: &mut Renderer) -> Result<()> {
+    pub fn render_pixel_intersection(
+        &mut self,
+        renderer: &mut Renderer,
+        atlas: &TextureAtlas,
+    ) -> Result<()> {
         // TODO: move these into awsm_web
         const ZERO: u32 = 0x0;
         const KEEP: u32 = 0x1E00;
@@ -103,7 +111,7 @@ impl CollisionEvent {
             .gl
             .stencil_func(CmpFunction::Always as u32, 1, 0xFF);
         renderer.gl.gl.stencil_op(0, REPLACE, REPLACE);
-        self.a.render(renderer)?; // alpha of 0.0 will be discarded
+        self.a.render(renderer, atlas)?; // alpha of 0.0 will be discarded
 
         // use the stencil to draw object `b`, surrounded by an occlusion query
         renderer
@@ -111,7 +119,7 @@ impl CollisionEvent {
             .gl
             .stencil_func(CmpFunction::Equal as u32, 1, 0xFF);
         renderer.begin_query(GlQueryKind::AnySamplesPassed, &query);
-        self.b.render(renderer)?;
+        self.b.render(renderer, atlas)?;
         renderer.end_query(GlQueryKind::AnySamplesPassed);
 
         self.occlusion_query = Some(query);
@@ -119,6 +127,49 @@ impl CollisionEvent {
         Ok(())
     }
 
+    // The cpu alternative to the above, with the answer available immediately
+    // walks every (world space) pixel where the two rects overlap and checks both masks there
+    pub fn check_alpha_masks(&self) -> bool {
+        // a collapsed quad has nothing to hit
+        let (a_basis, b_basis) = match (self.a.basis(), self.b.basis()) {
+            (Some(a_basis), Some(b_basis)) => (a_basis, b_basis),
+            _ => return false,
+        };
+
+        let a = Aabb::from_vertices(&self.a.vertices);
+        let b = Aabb::from_vertices(&self.b.vertices);
+
+        let min_x = a.min_x.max(b.min_x).floor();
+        let max_x = a.max_x.min(b.max_x).ceil();
+        let min_y = a.min_y.max(b.min_y).floor();
+        let max_y = a.max_y.min(b.max_y).ceil();
+
+        let mut y = min_y + 0.5;
+        while y < max_y {
+            let mut x = min_x + 0.5;
+            while x < max_x {
+                if self.a.is_solid_at(&a_basis, x, y) && self.b.is_solid_at(&b_basis, x, y) {
+                    return true;
+                }
+                x += 1.0;
+            }
+            y += 1.0;
+        }
+
+        false
+    }
+
+    // roughly where the two touch, i.e. the middle of where their rects overlap
+    pub fn contact_point(&self) -> Vec2 {
+        let a = Aabb::from_vertices(&self.a.vertices);
+        let b = Aabb::from_vertices(&self.b.vertices);
+
+        Vec2::new(
+            (a.min_x.max(b.min_x) + a.max_x.min(b.max_x)) / 2.0,
+            (a.min_y.max(b.min_y) + a.max_y.min(b.max_y)) / 2.0,
+        )
+    }
+
     // will return Ok(None) if the query is not available yet
     pub fn check_occlusion_query(&mut self, renderer: &mut Renderer) -> Result<Option<bool>> {
         match &self.occlusion_query {
@@ -138,7 +189,20 @@ impl CollisionEvent {
 }
 
 impl CollisionEventTarget {
-    pub fn render(&self, renderer: &mut Renderer) -> Result<()> {
+    // the quad may be rotated and/or flipped, that's all handled by inverting the vertices' basis
+    // None if it's collapsed, e.g. scaled down to nothing
+    pub fn basis(&self) -> Option<QuadBasis> {
+        QuadBasis::from_vertices(&self.vertices)
+    }
+
+    // world space point -> position on the quad -> mask
+    // the basis is from Self::basis, it's the same for every point so callers hold onto it
+    pub fn is_solid_at(&self, basis: &QuadBasis, x: f32, y: f32) -> bool {
+        let (s, t) = basis.quad_coords(x, y);
+        self.mask.sample(s, t)
+    }
+
+    pub fn render(&self, renderer: &mut Renderer, atlas: &TextureAtlas) -> Result<()> {
         let program = renderer.shaders.programs.collision.clone();
         program.activate(renderer)?;
 
@@ -160,8 +224,8 @@ impl CollisionEventTarget {
             ),
         )?;
 
-        renderer.activate_texture_sampler_name(self.texture_id, "u_sampler")?;
-        renderer.draw_arrays(BeginMode::TriangleStrip, 0, 4);
+        renderer.activate_texture_sampler_name(atlas.texture_id(self.page), "u_sampler")?;
+        renderer.draw_arrays_counted(BeginMode::TriangleStrip, 0, 4);
 
         Ok(())
     }
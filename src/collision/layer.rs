@@ -0,0 +1,84 @@
+// Which things can hit which
+// every collider that takes part has a CollisionLayer: the layer it's on, and a mask of the layers it can hit
+// the masks come from the rule table (media/data/collision.json), so e.g. friendly fire is just another rule
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+pub type CollisionRulesView<'a> = UniqueView<'a, CollisionRules>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionLayerKind {
+    Enemy,
+    Bomb,
+    Bullet,
+    Rocket,
+}
+
+impl CollisionLayerKind {
+    pub const ALL: [Self; 4] = [Self::Enemy, Self::Bomb, Self::Bullet, Self::Rocket];
+
+    pub fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionLayer {
+    pub kind: CollisionLayerKind,
+    // bits of the layers this can hit, see CollisionLayerKind::bit
+    pub mask: u32,
+}
+
+impl CollisionLayer {
+    pub const NONE: u32 = 0;
+
+    // both sides have to agree, so clearing one mask (e.g. a hiding enemy) is enough to rule out a pair
+    pub fn can_collide(&self, other: &Self) -> bool {
+        self.mask & other.kind.bit() != 0 && other.mask & self.kind.bit() != 0
+    }
+}
+
+// the raw data, a list of pairs that can collide (in either order)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CollisionRulesData {
+    rules: Vec<(CollisionLayerKind, CollisionLayerKind)>,
+}
+
+#[derive(Component, Unique, Debug, Clone)]
+pub struct CollisionRules {
+    // indexed by CollisionLayerKind
+    masks: [u32; CollisionLayerKind::ALL.len()],
+}
+
+impl CollisionRules {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let data: CollisionRulesData = serde_json::from_str(json)?;
+
+        let mut masks = [CollisionLayer::NONE; CollisionLayerKind::ALL.len()];
+        for (a, b) in data.rules {
+            if a == CollisionLayerKind::Enemy && b == CollisionLayerKind::Enemy {
+                // there's nothing that could happen between two enemies
+                return Err(anyhow!("enemies can't collide with each other"));
+            }
+            masks[a as usize] |= b.bit();
+            masks[b as usize] |= a.bit();
+        }
+
+        Ok(Self { masks })
+    }
+
+    pub fn mask(&self, kind: CollisionLayerKind) -> u32 {
+        self.masks[kind as usize]
+    }
+
+    // what a collider of this kind starts out with
+    pub fn layer(&self, kind: CollisionLayerKind) -> CollisionLayer {
+        CollisionLayer {
+            kind,
+            mask: self.mask(kind),
+        }
+    }
+}
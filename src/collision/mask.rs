@@ -0,0 +1,122 @@
+// CPU side of pixel-perfect collision
+// one bit per pixel, set wherever the pixel would survive collision.frag (i.e. alpha >= 0.5)
+// built once at load time, from the same images that become the textures
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone)]
+pub struct AlphaMask {
+    pub width: u32,
+    pub height: u32,
+    bits: Vec<u64>,
+}
+
+impl AlphaMask {
+    // collision.frag discards `a < 0.5`, and 128/255 is the first byte that isn't
+    pub const ALPHA_THRESHOLD: u8 = 128;
+
+    // a region of an rgba image, e.g. one sprite sheet cell
+    pub fn from_rgba(rgba: &[u8], image_width: u32, bounds: &Bounds) -> Self {
+        let x = bounds.x as u32;
+        let y = bounds.y as u32;
+        let width = bounds.width as u32;
+        let height = bounds.height as u32;
+        let mut bits = vec![0u64; ((width * height) as usize + 63) / 64];
+
+        for row in 0..height {
+            for col in 0..width {
+                let alpha_index = (((y + row) * image_width + (x + col)) * 4 + 3) as usize;
+                if rgba.get(alpha_index).copied().unwrap_or_default() >= Self::ALPHA_THRESHOLD {
+                    let index = (row * width + col) as usize;
+                    bits[index / 64] |= 1 << (index % 64);
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    pub fn from_image(img: &HtmlImageElement) -> Result<Self> {
+        let rgba = image_rgba(img)?;
+        Ok(Self::from_rgba(
+            &rgba,
+            img.width(),
+            &Bounds {
+                x: 0.0,
+                y: 0.0,
+                width: img.width() as f64,
+                height: img.height() as f64,
+            },
+        ))
+    }
+
+    // every pixel set, for when there's no image to go by (e.g. headless)
+    pub fn solid(width: u32, height: u32) -> Self {
+        let len = (width * height) as usize;
+        let mut bits = vec![u64::MAX; (len + 63) / 64];
+        if len % 64 != 0 {
+            if let Some(last) = bits.last_mut() {
+                *last = (1 << (len % 64)) - 1;
+            }
+        }
+
+        Self {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    // row 0 is the top of the image
+    pub fn get(&self, col: u32, row: u32) -> bool {
+        if col >= self.width || row >= self.height {
+            return false;
+        }
+        let index = (row * self.width + col) as usize;
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    // s and t are 0..1 across the quad, with t going up (like the uvs)
+    pub fn sample(&self, s: f32, t: f32) -> bool {
+        if !(0.0..1.0).contains(&s) || !(0.0..1.0).contains(&t) {
+            return false;
+        }
+        let col = (s * self.width as f32) as u32;
+        let row = ((1.0 - t) * self.height as f32) as u32;
+        self.get(col, row.min(self.height.saturating_sub(1)))
+    }
+}
+
+// the raw pixels, by way of a throwaway 2d canvas
+pub fn image_rgba(img: &HtmlImageElement) -> Result<Vec<u8>> {
+    let canvas: HtmlCanvasElement = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| anyhow!("no document"))?
+        .create_element("canvas")
+        .map_err(|_| anyhow!("could not create canvas"))?
+        .unchecked_into();
+
+    canvas.set_width(img.width());
+    canvas.set_height(img.height());
+
+    let ctx: CanvasRenderingContext2d = canvas
+        .get_context("2d")
+        .map_err(|_| anyhow!("could not get 2d context"))?
+        .ok_or_else(|| anyhow!("no 2d context"))?
+        .unchecked_into();
+
+    ctx.draw_image_with_html_image_element(img, 0.0, 0.0)
+        .map_err(|_| anyhow!("could not draw image"))?;
+
+    let data = ctx
+        .get_image_data(0.0, 0.0, img.width() as f64, img.height() as f64)
+        .map_err(|_| anyhow!("could not read image data (cross-origin?)"))?
+        .data();
+
+    Ok(data.0)
+}
@@ -1,4 +1,8 @@
+pub mod broadphase;
 pub mod data;
 pub mod debug;
 pub mod intersection;
+pub mod layer;
+pub mod mask;
+pub mod quad;
 pub mod systems;
@@ -0,0 +1,49 @@
+// Maps world space points back onto a collider's quad
+// std only, so it can be tested natively (see tests/collision_quad.rs)
+//
+// the vertices are laid out like Collider::vertices, i.e. [lt, lb, rt, rb]
+// and are already in world space, so any rotation or flip (e.g. Scale.x = -1 for enemies facing left)
+// is baked in - the quad coordinates are always relative to the unflipped sprite
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadBasis {
+    origin: (f32, f32),
+    axis_s: (f32, f32),
+    axis_t: (f32, f32),
+    det: f32,
+}
+
+impl QuadBasis {
+    // left-bottom is the origin, the other two are the width and height directions
+    // None if the quad has collapsed to a line or a point
+    pub fn from_vertices(v: &[f32; 8]) -> Option<Self> {
+        let origin = (v[2], v[3]);
+        let axis_s = (v[6] - v[2], v[7] - v[3]);
+        let axis_t = (v[0] - v[2], v[1] - v[3]);
+
+        let det = axis_s.0 * axis_t.1 - axis_s.1 * axis_t.0;
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+
+        Some(Self {
+            origin,
+            axis_s,
+            axis_t,
+            det,
+        })
+    }
+
+    // world point -> (s, t), both 0..1 inside the quad, with t going up (like the uvs)
+    pub fn quad_coords(&self, x: f32, y: f32) -> (f32, f32) {
+        let d = (x - self.origin.0, y - self.origin.1);
+        let s = (d.0 * self.axis_t.1 - d.1 * self.axis_t.0) / self.det;
+        let t = (self.axis_s.0 * d.1 - self.axis_s.1 * d.0) / self.det;
+        (s, t)
+    }
+
+    // true when the quad has been flipped on one axis, e.g. an enemy facing left
+    pub fn is_mirrored(&self) -> bool {
+        self.det < 0.0
+    }
+}
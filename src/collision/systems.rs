@@ -3,16 +3,24 @@ use nalgebra_glm::Vec2;
 
 use crate::{
     animation::data::Animation,
-    enemy::data::Enemy,
-    explosion::data::{Explosion, ExplosionSpawner},
+    atlas::data::TextureAtlasView,
+    config::CONFIG,
+    enemy::{data::Enemy, health::data::EnemyHealth},
+    hit::data::{HitEvent, HitEventQueueViewMut, HitKind},
     layout::data::{LayoutAnchor, LayoutPosition},
     prelude::*,
     projectiles::data::{Projectile, ProjectileMovement},
-    renderer::{buffers::Buffers, uvs::Uvs, RendererViewMut},
+    renderer::RendererViewMut,
+    tick::FrameCountView,
 };
 
-use super::data::{
-    Collider, CollisionEvent, CollisionEventQueue, CollisionEventQueueViewMut, CollisionEventTarget,
+use super::{
+    broadphase::{Aabb, UniformGrid},
+    data::{
+        Collider, CollisionBackend, CollisionEvent, CollisionEventQueue,
+        CollisionEventQueueViewMut, CollisionEventTarget,
+    },
+    layer::{CollisionLayer, CollisionRulesView},
 };
 
 // This system is responsible for updating the collider component
@@ -30,7 +38,7 @@ pub fn update_collider_sys(
         (&mut collider, &enemies, &animations, &transform).iter()
     {
         let cell = &enemy.spritesheet().cells[animation.index];
-        collider.update(cell.width as f32, cell.height as f32, &transform);
+        collider.update(cell.width(), cell.height(), &transform);
     }
 
     for (collider, projectile, transform) in (&mut collider, &projectiles, &transform).iter() {
@@ -38,94 +46,146 @@ pub fn update_collider_sys(
     }
 }
 
+// Hiding enemies can't be hit, which is just an empty mask for as long as they're down
+pub fn enemy_collision_layer_sys(
+    enemies: View<Enemy>,
+    healths: View<EnemyHealth>,
+    mut layers: ViewMut<CollisionLayer>,
+    rules: CollisionRulesView,
+) {
+    for (enemy, health, mut layer) in (&enemies, &healths, &mut layers).iter() {
+        // dead ones are waiting on their explosion, and shouldn't soak up any more bombs
+        layer.mask = if enemy.controller().hiding().is_some() || health.is_dead() {
+            CollisionLayer::NONE
+        } else {
+            rules.mask(layer.kind)
+        };
+    }
+}
+
+// Anything against anything, as long as their layers agree (see media/data/collision.json)
+// the grid narrows it down to things that are nearby, then the rects are checked properly
 pub fn detect_geometric_collision_sys(
     colliders: View<Collider>,
+    layers: View<CollisionLayer>,
     enemies: View<Enemy>,
+    animations: View<Animation>,
     projectiles: View<Projectile>,
-    transforms: View<WorldTransform>,
     mut event_queue: CollisionEventQueueViewMut,
 ) {
-    let mut bomb_projectiles = Vec::new();
-    let mut other_projectiles = Vec::new();
+    let mut grid = UniformGrid::new(UniformGrid::<EntityId>::DEFAULT_CELL_SIZE);
+    let mut entries = Vec::new();
 
-    for (entity_p, (collider_p, transform_p, projectile)) in
-        (&colliders, &transforms, &projectiles).iter().with_id()
-    {
-        match projectile.movement {
-            ProjectileMovement::Bomb { .. } => {
-                bomb_projectiles.push((entity_p, collider_p, transform_p, projectile));
-                for (entity_e, (collider_e, transform_e, enemy)) in
-                    (&colliders, &transforms, &enemies).iter().with_id()
-                {
-                    if enemy.controller().hiding().is_some() {
-                        // enemy is hiding, skipping collision check
-                        continue;
-                    }
-                    if !event_queue.has_any_collision(entity_p, entity_e)
-                        && collider_p.intersects_rect(collider_e)
-                    {
-                        let spritesheet = enemy.spritesheet();
-                        event_queue.push(CollisionEvent {
-                            a: CollisionEventTarget {
-                                entity: entity_p,
-                                texture_id: projectile.texture_id,
-                                vertices: collider_p.vertices,
-                                uvs: Buffers::QUAD_GEOM_UNIT,
-                            },
-                            b: CollisionEventTarget {
-                                entity: entity_e,
-                                texture_id: spritesheet.texture_id,
-                                vertices: collider_e.vertices,
-                                uvs: Uvs::new(
-                                    spritesheet.atlas_width,
-                                    spritesheet.atlas_height,
-                                    &spritesheet.cells[0],
-                                )
-                                .data,
-                            },
-                            occlusion_query: None,
-                        });
-                    }
-                }
-            }
-            ProjectileMovement::Bullet { .. } | ProjectileMovement::Rocket { .. } => {
-                other_projectiles.push((entity_p, collider_p, transform_p, projectile));
-            }
+    for (entity, (collider, layer)) in (&colliders, &layers).iter().with_id() {
+        if layer.mask == CollisionLayer::NONE {
+            // can't hit anything right now, e.g. a hiding enemy
+            continue;
         }
+        let aabb = Aabb::from_vertices(&collider.vertices);
+        grid.insert(entity, aabb);
+        entries.push((entity, aabb));
     }
 
-    for (entity_b, collider_b, transform_b, projectile_b) in &bomb_projectiles {
-        for (entity_o, collider_o, transform_o, projectile_o) in &other_projectiles {
-            if !event_queue.has_any_collision(*entity_b, *entity_o)
-                && collider_b.intersects_rect(collider_o)
+    // walked in a stable order, so the events are too
+    entries.sort_unstable_by_key(|(entity, _)| *entity);
+
+    let mut candidates = Vec::new();
+
+    for (entity_1, aabb) in entries {
+        grid.query(&aabb, &mut candidates);
+
+        for &entity_2 in &candidates {
+            // each pair is only checked from one side
+            if entity_2 <= entity_1 || event_queue.has_any_collision(entity_1, entity_2) {
+                continue;
+            }
+
+            let (layer_1, layer_2) = (
+                layers.get(entity_1).unwrap_ext(),
+                layers.get(entity_2).unwrap_ext(),
+            );
+            if !layer_1.can_collide(layer_2) {
+                continue;
+            }
+
+            let (collider_1, collider_2) = (
+                colliders.get(entity_1).unwrap_ext(),
+                colliders.get(entity_2).unwrap_ext(),
+            );
+            if !collider_1.intersects_rect(collider_2) {
+                continue;
+            }
+
+            // a is always a projectile, and the bomb if there is one (see resolve_pixel_collision)
+            let swap =
+                collision_rank(entity_2, &projectiles) < collision_rank(entity_1, &projectiles);
+            let (entity_a, collider_a, entity_b, collider_b) = if swap {
+                (entity_2, collider_2, entity_1, collider_1)
+            } else {
+                (entity_1, collider_1, entity_2, collider_2)
+            };
+
+            // with friendly fire on, enemies still can't hit themselves as their shots leave
+            if let (Ok(projectile), Ok(enemy)) = (projectiles.get(entity_a), enemies.get(entity_b))
             {
-                event_queue.push(CollisionEvent {
-                    a: CollisionEventTarget {
-                        entity: *entity_b,
-                        texture_id: projectile_b.texture_id,
-                        vertices: collider_b.vertices,
-                        uvs: Buffers::QUAD_GEOM_UNIT,
-                    },
-                    b: CollisionEventTarget {
-                        entity: *entity_o,
-                        texture_id: projectile_o.texture_id,
-                        vertices: collider_o.vertices,
-                        uvs: Buffers::QUAD_GEOM_UNIT,
-                    },
-                    occlusion_query: None,
-                });
+                if projectile.shooter == Some(enemy.kind()) {
+                    continue;
+                }
             }
+
+            event_queue.push(CollisionEvent {
+                a: collision_target(entity_a, collider_a, &enemies, &animations, &projectiles),
+                b: collision_target(entity_b, collider_b, &enemies, &animations, &projectiles),
+                occlusion_query: None,
+            });
         }
     }
 }
 
+// bombs, then other projectiles, then enemies
+fn collision_rank(entity: EntityId, projectiles: &View<Projectile>) -> u8 {
+    match projectiles.get(entity) {
+        Ok(projectile) => match projectile.movement {
+            ProjectileMovement::Bomb { .. } => 0,
+            ProjectileMovement::Bullet { .. } | ProjectileMovement::Rocket { .. } => 1,
+        },
+        Err(_) => 2,
+    }
+}
+
+fn collision_target(
+    entity: EntityId,
+    collider: &Collider,
+    enemies: &View<Enemy>,
+    animations: &View<Animation>,
+    projectiles: &View<Projectile>,
+) -> CollisionEventTarget {
+    match (enemies, animations).get(entity) {
+        Ok((enemy, animation)) => {
+            CollisionEventTarget::from_cell(entity, collider, enemy.spritesheet(), animation.index)
+        }
+        Err(_) => CollisionEventTarget::from_projectile(
+            entity,
+            collider,
+            projectiles.get(entity).unwrap_ext(),
+        ),
+    }
+}
+
 pub fn pixel_collision_render_sys(
     mut renderer: RendererViewMut,
     mut event_queue: CollisionEventQueueViewMut,
+    atlas: TextureAtlasView,
 ) {
+    if CONFIG.collision_backend != CollisionBackend::Gpu {
+        return;
+    }
+
     for event in event_queue.iter_mut() {
         if event.occlusion_query.is_none() {
-            event.render_pixel_intersection(&mut renderer).unwrap_ext();
+            event
+                .render_pixel_intersection(&mut renderer, &atlas)
+                .unwrap_ext();
         }
     }
 }
@@ -133,8 +193,15 @@ pub fn pixel_collision_render_sys(
 pub fn pixel_collision_check_sys(
     mut renderer: RendererViewMut,
     mut event_queue: CollisionEventQueueViewMut,
-    mut explosion_spawner: UniqueViewMut<ExplosionSpawner>,
+    mut hit_events: HitEventQueueViewMut,
+    frame_count: FrameCountView,
+    projectiles: View<Projectile>,
+    enemies: View<Enemy>,
 ) {
+    if CONFIG.collision_backend != CollisionBackend::Gpu {
+        return;
+    }
+
     event_queue.retain_mut(|event| {
         // if we have a None, it doesn't mean there's no collision, just that the query isn't available yet
         // so we keep it in the queue
@@ -142,8 +209,7 @@ pub fn pixel_collision_check_sys(
             None => true,
             Some(result) => {
                 if result {
-                    explosion_spawner.to_spawn.insert(event.a.entity);
-                    explosion_spawner.to_spawn.insert(event.b.entity);
+                    hit_events.push(hit_event(event, frame_count.0, &projectiles, &enemies));
                 }
 
                 false
@@ -151,3 +217,56 @@ pub fn pixel_collision_check_sys(
         }
     })
 }
+
+// Same outcome as pixel_collision_check_sys, but checked on the cpu, right away
+// doesn't need the renderer, so it runs headless too
+pub fn mask_collision_check_sys(
+    mut event_queue: CollisionEventQueueViewMut,
+    mut hit_events: HitEventQueueViewMut,
+    frame_count: FrameCountView,
+    projectiles: View<Projectile>,
+    enemies: View<Enemy>,
+) {
+    if CONFIG.collision_backend != CollisionBackend::Cpu {
+        return;
+    }
+
+    event_queue.retain_mut(|event| {
+        if event.check_alpha_masks() {
+            hit_events.push(hit_event(event, frame_count.0, &projectiles, &enemies));
+        }
+
+        false
+    })
+}
+
+// a is always a projectile, and the bomb if there is one (see detect_geometric_collision_sys)
+// the victim is whoever takes the hit: an enemy if there is one, otherwise the bomb that got intercepted
+fn hit_event(
+    event: &CollisionEvent,
+    frame: u64,
+    projectiles: &View<Projectile>,
+    enemies: &View<Enemy>,
+) -> HitEvent {
+    let kind = |entity: EntityId| match enemies.get(entity) {
+        Ok(enemy) => HitKind::Enemy(enemy.kind()),
+        Err(_) => HitKind::from_projectile(projectiles.get(entity).unwrap_ext()),
+    };
+
+    let (a_kind, b_kind) = (kind(event.a.entity), kind(event.b.entity));
+
+    let (attacker, attacker_kind, victim, victim_kind) = if a_kind.is_bomb() && !b_kind.is_enemy() {
+        (event.b.entity, b_kind, event.a.entity, a_kind)
+    } else {
+        (event.a.entity, a_kind, event.b.entity, b_kind)
+    };
+
+    HitEvent {
+        attacker,
+        victim,
+        attacker_kind,
+        victim_kind,
+        contact: event.contact_point(),
+        frame,
+    }
+}
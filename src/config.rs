@@ -1,50 +1,104 @@
-use std::sync::{atomic::AtomicBool, LazyLock};
+use std::{
+    fmt::Display,
+    ops::Deref,
+    str::FromStr,
+    sync::{LazyLock, OnceLock},
+};
 
-use awsm_web::{env::env_var, prelude::UnwrapExt};
+use anyhow::{anyhow, Context, Result};
+use awsm_web::loaders;
 use serde::Deserialize;
 
-use crate::{dom::ui::game::GameUiPhase, enemy::data::EnemyKind};
+use crate::{
+    collision::data::CollisionBackend,
+    dom::ui::game::GameUiPhase,
+    enemy::data::EnemyKind,
+    layout::scaling::ScaleMode,
+    post::data::{PostPass, PostQuality},
+    rand_helpers::Rand,
+};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "dev")] {
         // for github pages etc. where website isn't at root
         pub const URI_ROOT:&'static str = "";
-        pub const CONFIG: LazyLock<Config> = LazyLock::new(|| {
+        fn default_config() -> Config {
             Config {
                 image_base: "http://127.0.0.1:9000/image".to_string(),
                 audio_base: "http://127.0.0.1:9000/audio".to_string(),
                 html_base: "http://127.0.0.1:9000/html".to_string(),
+                data_base: "http://127.0.0.1:9000/data".to_string(),
                 max_bg_panes: Some(1),
                 max_bg_layers: None,
                 initial_drop_countdown: 100.0,
-                live_drop_countdown_range: 100.0..200.0,
-                //live_drop_countdown_range: 1000.0..5000.0,
+                drop_interval: None,
+                bombs_to_win: 5,
                 selected_enemy: Some(EnemyKind::Two),
                 can_debug_colliders: true,
+                collision_backend: CollisionBackend::Cpu,
                 cell_duration: 50.0,
-                initial_game_phase: Some(GameUiPhase::Welcome)
+                initial_game_phase: Some(GameUiPhase::Welcome),
                 //initial_game_phase: None
+                seed: None,
+                post_passes: PostPass::ALL.to_vec(),
+                post_quality: PostQuality::High,
+                camera_follow: false,
+                scale_mode: ScaleMode::Letterbox,
+                max_pixel_ratio: 2.0,
             }
-        });
+        }
+        fn config_json_url() -> String {
+            "http://127.0.0.1:9000/config.json".to_string()
+        }
     } else {
             // for github pages etc. where website isn't at root
             pub const URI_ROOT:&'static str = "not-a-game";
-            pub const CONFIG: LazyLock<Config> = LazyLock::new(|| {
+            fn default_config() -> Config {
             Config {
                 image_base: format!("/{}/media/image", URI_ROOT),
                 audio_base: format!("/{}/media/audio", URI_ROOT),
                 html_base: format!("/{}/media/html", URI_ROOT),
+                data_base: format!("/{}/media/data", URI_ROOT),
                 initial_drop_countdown: 100.0,
-                live_drop_countdown_range: 1000.0..5000.0,
-                //live_drop_countdown_range: 100.0..200.0,
-                max_bg_panes: Some(1),
+                drop_interval: None,
+                bombs_to_win: 20,
+                max_bg_panes: None,
                 max_bg_layers: None,
                 selected_enemy: Some(EnemyKind::Two),
                 can_debug_colliders: false,
+                collision_backend: CollisionBackend::Cpu,
                 cell_duration: 50.0,
-                initial_game_phase: Some(GameUiPhase::Welcome)
+                initial_game_phase: Some(GameUiPhase::Welcome),
+                seed: None,
+                post_passes: PostPass::ALL.to_vec(),
+                post_quality: PostQuality::High,
+                camera_follow: false,
+                scale_mode: ScaleMode::Letterbox,
+                max_pixel_ratio: 2.0,
             }
-        });
+        }
+        fn config_json_url() -> String {
+            format!("/{}/media/config.json", URI_ROOT)
+        }
+    }
+}
+
+// The compiled-in defaults above, overridden at startup (see Config::init)
+// by an optional config.json next to the media, and then by the url
+// e.g. ?selected_enemy=3&cell_duration=100&can_debug_colliders=true
+pub static CONFIG: ConfigRef = ConfigRef;
+
+static DEFAULT_CONFIG: LazyLock<Config> = LazyLock::new(default_config);
+static RUNTIME_CONFIG: OnceLock<Config> = OnceLock::new();
+
+pub struct ConfigRef;
+
+impl Deref for ConfigRef {
+    type Target = Config;
+
+    // anything that reads the config before init gets the defaults
+    fn deref(&self) -> &Config {
+        RUNTIME_CONFIG.get().unwrap_or(&DEFAULT_CONFIG)
     }
 }
 
@@ -53,17 +107,249 @@ pub struct Config {
     image_base: String,
     audio_base: String,
     html_base: String,
+    data_base: String,
+    // after this, drops are paced by the selected difficulty (see media/data/difficulty.json)
     pub initial_drop_countdown: f64,
-    pub live_drop_countdown_range: std::ops::Range<f64>,
+    // if set, replaces the difficulty's drop interval (min, max), for testing
+    pub drop_interval: Option<(f64, f64)>,
+    // surviving this many bombs wins the game
+    pub bombs_to_win: u32,
+    // how many of media/image/bg/1..4 get loaded, None is all of them
+    // levels without their own background rotate through whatever's loaded
     pub max_bg_panes: Option<usize>,
     pub max_bg_layers: Option<usize>,
     pub selected_enemy: Option<EnemyKind>,
     pub can_debug_colliders: bool,
+    // cpu works headless and answers immediately, gpu is the original occlusion query approach
+    pub collision_backend: CollisionBackend,
     pub cell_duration: f64,
     pub initial_game_phase: Option<GameUiPhase>,
+    // if None, a random seed is picked for each session
+    pub seed: Option<u64>,
+    // which post-processing passes start out enabled, and how good they look
+    // both can be changed in-game from the pause screen
+    pub post_passes: Vec<PostPass>,
+    pub post_quality: PostQuality,
+    // the camera zooms in a little and follows the selected enemy around
+    pub camera_follow: bool,
+    // how the design resolution is fit to the window (see layout/scaling.rs)
+    pub scale_mode: ScaleMode,
+    // the canvas is sized for devicePixelRatio, up to this, since past 2x it's a lot of pixels for little gain
+    pub max_pixel_ratio: f64,
+}
+
+// Everything that can be changed without recompiling
+// the same names are used in config.json and the url
+// enemies are "1".."4" and phases are e.g. "welcome" or "none" (straight into the game)
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigOverrides {
+    pub image_base: Option<String>,
+    pub audio_base: Option<String>,
+    pub html_base: Option<String>,
+    pub data_base: Option<String>,
+    pub initial_drop_countdown: Option<f64>,
+    pub drop_interval: Option<(f64, f64)>,
+    pub bombs_to_win: Option<u32>,
+    pub max_bg_panes: Option<usize>,
+    pub max_bg_layers: Option<usize>,
+    pub selected_enemy: Option<String>,
+    pub can_debug_colliders: Option<bool>,
+    pub collision_backend: Option<String>,
+    pub cell_duration: Option<f64>,
+    pub initial_game_phase: Option<String>,
+    pub seed: Option<u64>,
+    pub post_fx: Option<String>,
+    pub post_quality: Option<String>,
+    pub camera_follow: Option<bool>,
+    pub scale_mode: Option<String>,
+    pub max_pixel_ratio: Option<f64>,
 }
 
 impl Config {
+    // must be called once, before anything that depends on overridden values
+    pub async fn init() -> Result<()> {
+        let mut config = default_config();
+
+        // it's fine for config.json to not exist, but not for it to be broken
+        // fetch only fails on network errors, a 404 (or any other error page) still comes back as a response
+        match loaders::fetch::fetch_url(&config_json_url()).await {
+            Ok(response) if response.ok() => {
+                let json = response.text().await?;
+                let overrides: ConfigOverrides =
+                    serde_json::from_str(&json).context("config.json is not valid")?;
+                config.apply(overrides).context("config.json")?;
+            }
+            Ok(response) => {
+                log::info!(
+                    "no config.json (status {}), using the built-in config",
+                    response.status()
+                );
+            }
+            Err(_) => {
+                log::info!("no config.json, using the built-in config");
+            }
+        }
+
+        config
+            .apply(ConfigOverrides::from_url()?)
+            .context("url parameters")?;
+
+        RUNTIME_CONFIG
+            .set(config)
+            .map_err(|_| anyhow!("config was already initialized"))
+    }
+
+    pub fn apply(&mut self, overrides: ConfigOverrides) -> Result<()> {
+        let ConfigOverrides {
+            image_base,
+            audio_base,
+            html_base,
+            data_base,
+            initial_drop_countdown,
+            drop_interval,
+            bombs_to_win,
+            max_bg_panes,
+            max_bg_layers,
+            selected_enemy,
+            can_debug_colliders,
+            collision_backend,
+            cell_duration,
+            initial_game_phase,
+            seed,
+            post_fx,
+            post_quality,
+            camera_follow,
+            scale_mode,
+            max_pixel_ratio,
+        } = overrides;
+
+        if let Some(value) = image_base {
+            self.image_base = value;
+        }
+        if let Some(value) = audio_base {
+            self.audio_base = value;
+        }
+        if let Some(value) = html_base {
+            self.html_base = value;
+        }
+        if let Some(value) = data_base {
+            self.data_base = value;
+        }
+        if let Some(value) = initial_drop_countdown {
+            self.initial_drop_countdown = value;
+        }
+        if let Some(value) = drop_interval {
+            self.drop_interval = Some(value);
+        }
+        if let Some(value) = bombs_to_win {
+            self.bombs_to_win = value;
+        }
+        if let Some(value) = max_bg_panes {
+            self.max_bg_panes = Some(value);
+        }
+        if let Some(value) = max_bg_layers {
+            self.max_bg_layers = Some(value);
+        }
+        if let Some(value) = selected_enemy {
+            self.selected_enemy = Some(parse_enemy_kind(&value)?);
+        }
+        if let Some(value) = can_debug_colliders {
+            self.can_debug_colliders = value;
+        }
+        if let Some(value) = collision_backend {
+            self.collision_backend = parse_collision_backend(&value)?;
+        }
+        if let Some(value) = cell_duration {
+            self.cell_duration = value;
+        }
+        if let Some(value) = initial_game_phase {
+            self.initial_game_phase = parse_game_phase(&value)?;
+        }
+        if let Some(value) = seed {
+            self.seed = Some(value);
+        }
+        if let Some(value) = post_fx {
+            self.post_passes = parse_post_passes(&value)?;
+        }
+        if let Some(value) = post_quality {
+            self.post_quality = parse_post_quality(&value)?;
+        }
+        if let Some(value) = camera_follow {
+            self.camera_follow = value;
+        }
+        if let Some(value) = scale_mode {
+            self.scale_mode = parse_scale_mode(&value)?;
+        }
+        if let Some(value) = max_pixel_ratio {
+            self.max_pixel_ratio = value;
+        }
+
+        self.validate()
+    }
+
+    // reports every problem at once, so QA doesn't have to fix them one reload at a time
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
+        for (name, base) in [
+            ("image_base", &self.image_base),
+            ("audio_base", &self.audio_base),
+            ("html_base", &self.html_base),
+            ("data_base", &self.data_base),
+        ] {
+            if base.is_empty() {
+                errors.push(format!("{name} can't be empty"));
+            } else if base.ends_with('/') {
+                errors.push(format!("{name} shouldn't end with a slash: {base}"));
+            }
+        }
+        if self.initial_drop_countdown < 0.0 {
+            errors.push(format!(
+                "initial_drop_countdown can't be negative: {}",
+                self.initial_drop_countdown
+            ));
+        }
+        if let Some((min, max)) = self.drop_interval {
+            if min <= 0.0 || min > max {
+                errors.push(format!(
+                    "drop_interval must be positive with min <= max: {min},{max}"
+                ));
+            }
+        }
+        if self.bombs_to_win == 0 {
+            errors.push("bombs_to_win must be at least 1".to_string());
+        }
+        if let Some(panes) = self.max_bg_panes {
+            if !(1..=4).contains(&panes) {
+                errors.push(format!("max_bg_panes must be within 1..4: {panes}"));
+            }
+        }
+        if let Some(layers) = self.max_bg_layers {
+            if !(1..=7).contains(&layers) {
+                errors.push(format!("max_bg_layers must be within 1..7: {layers}"));
+            }
+        }
+        if self.max_pixel_ratio < 1.0 {
+            errors.push(format!(
+                "max_pixel_ratio must be at least 1: {}",
+                self.max_pixel_ratio
+            ));
+        }
+        if self.cell_duration <= 0.0 {
+            errors.push(format!(
+                "cell_duration must be positive: {}",
+                self.cell_duration
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(errors.join(", ")))
+        }
+    }
+
     pub fn image_url(&self, path: &str) -> String {
         format!("{}/{}", self.image_base, path)
     }
@@ -74,17 +360,159 @@ impl Config {
     pub fn html_url(&self, path: &str) -> String {
         format!("{}/{}", self.html_base, path)
     }
+
+    pub fn data_url(&self, path: &str) -> String {
+        format!("{}/{}", self.data_base, path)
+    }
+
+    // the configured seed (e.g. ?seed=1234) if there is one, otherwise it's random
+    pub fn session_seed(&self) -> u64 {
+        self.seed.unwrap_or_else(Rand::random_seed)
+    }
+
+    // a handcrafted level instead of the endless bomber, e.g. ?level=tutorial
+    // see media/data/levels.json
+    pub fn level_name(&self) -> Option<String> {
+        url_param("level")
+    }
+
+    // a recording to replay instead of live input, e.g. ?replay=https://example.com/run.json
+    pub fn replay_url(&self) -> Option<String> {
+        url_param("replay")
+    }
 }
 
-fn check_env(name: &str) -> Option<String> {
-    match env_var(name) {
-        Ok(value) => {
-            if value.is_empty() {
-                None
-            } else {
-                Some(value)
-            }
-        }
-        Err(_) => None,
+impl ConfigOverrides {
+    pub fn from_url() -> Result<Self> {
+        Ok(Self {
+            image_base: url_param("image_base"),
+            audio_base: url_param("audio_base"),
+            html_base: url_param("html_base"),
+            data_base: url_param("data_base"),
+            initial_drop_countdown: parse_url_param("initial_drop_countdown")?,
+            drop_interval: match url_param("drop_interval") {
+                Some(value) => Some(parse_interval(&value)?),
+                None => None,
+            },
+            bombs_to_win: parse_url_param("bombs_to_win")?,
+            max_bg_panes: parse_url_param("max_bg_panes")?,
+            max_bg_layers: parse_url_param("max_bg_layers")?,
+            selected_enemy: url_param("selected_enemy"),
+            can_debug_colliders: parse_url_param("can_debug_colliders")?,
+            collision_backend: url_param("collision_backend"),
+            cell_duration: parse_url_param("cell_duration")?,
+            initial_game_phase: url_param("initial_game_phase"),
+            seed: parse_url_param("seed")?,
+            post_fx: url_param("post_fx"),
+            post_quality: url_param("post_quality"),
+            camera_follow: parse_url_param("camera_follow")?,
+            scale_mode: url_param("scale_mode"),
+            max_pixel_ratio: parse_url_param("max_pixel_ratio")?,
+        })
+    }
+}
+
+fn parse_enemy_kind(value: &str) -> Result<EnemyKind> {
+    match value.to_lowercase().as_str() {
+        "1" | "one" => Ok(EnemyKind::One),
+        "2" | "two" => Ok(EnemyKind::Two),
+        "3" | "three" => Ok(EnemyKind::Three),
+        "4" | "four" => Ok(EnemyKind::Four),
+        _ => Err(anyhow!("selected_enemy must be 1, 2, 3 or 4: {value}")),
+    }
+}
+
+fn parse_collision_backend(value: &str) -> Result<CollisionBackend> {
+    match value.to_lowercase().as_str() {
+        "cpu" => Ok(CollisionBackend::Cpu),
+        "gpu" => Ok(CollisionBackend::Gpu),
+        _ => Err(anyhow!("collision_backend must be cpu or gpu: {value}")),
+    }
+}
+
+fn parse_game_phase(value: &str) -> Result<Option<GameUiPhase>> {
+    match value.to_lowercase().as_str() {
+        "none" => Ok(None),
+        "welcome" => Ok(Some(GameUiPhase::Welcome)),
+        "help" => Ok(Some(GameUiPhase::Help)),
+        "paused" => Ok(Some(GameUiPhase::Paused)),
+        "game_over" => Ok(Some(GameUiPhase::GameOver)),
+        "winner" => Ok(Some(GameUiPhase::Winner)),
+        "high_scores" => Ok(Some(GameUiPhase::HighScores)),
+        _ => Err(anyhow!(
+            "initial_game_phase must be one of none, welcome, help, paused, game_over, winner, high_scores: {value}"
+        )),
+    }
+}
+
+// a comma separated list, e.g. ?post_fx=bloom,vignette or ?post_fx=none
+fn parse_post_passes(value: &str) -> Result<Vec<PostPass>> {
+    let value = value.to_lowercase();
+    if value.trim() == "none" {
+        return Ok(Vec::new());
     }
+
+    value
+        .split(',')
+        .map(|name| {
+            let name = name.trim();
+            PostPass::ALL
+                .into_iter()
+                .find(|pass| pass.name() == name)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = PostPass::ALL.iter().map(|pass| pass.name()).collect();
+                    anyhow!(
+                        "post_fx must be none or a list of {}: {name}",
+                        names.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+fn parse_post_quality(value: &str) -> Result<PostQuality> {
+    let value = value.to_lowercase();
+    PostQuality::ALL
+        .into_iter()
+        .find(|quality| quality.name() == value)
+        .ok_or_else(|| anyhow!("post_quality must be off, low or high: {value}"))
+}
+
+fn parse_scale_mode(value: &str) -> Result<ScaleMode> {
+    let value = value.to_lowercase();
+    ScaleMode::ALL
+        .into_iter()
+        .find(|mode| mode.name() == value)
+        .ok_or_else(|| anyhow!("scale_mode must be letterbox, crop or stretch: {value}"))
+}
+
+// "min,max", e.g. ?drop_interval=100,200
+fn parse_interval(value: &str) -> Result<(f64, f64)> {
+    let parsed = value
+        .split_once(',')
+        .and_then(|(min, max)| Some((min.trim().parse().ok()?, max.trim().parse().ok()?)));
+
+    parsed.ok_or_else(|| anyhow!("drop_interval must be two numbers, like 100,200: {value}"))
+}
+
+fn parse_url_param<T>(name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    url_param(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|err| anyhow!("{name} has a bad value {value}: {err}"))
+        })
+        .transpose()
+}
+
+fn url_param(name: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(name)
+        .filter(|value| !value.is_empty())
 }
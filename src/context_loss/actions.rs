@@ -0,0 +1,59 @@
+use crate::{
+    atlas::data::{TextureAtlas, TextureAtlasViewMut},
+    background::data::BackgroundViewMut,
+    camera::CameraViewMut,
+    collision::{data::CollisionEventQueueViewMut, debug::CollisionDebugger},
+    media::MediaView,
+    particles::data::ParticleSystemViewMut,
+    prelude::*,
+    renderer::RendererViewMut,
+    tick::PauseTickViewMut,
+};
+
+// from the "webglcontextlost" listener
+// nothing gets updated or drawn until it's restored
+pub fn context_lost(world: &World) {
+    log::warn!("lost the webgl context, waiting for it to be restored");
+    world.run(|mut pause_tick: PauseTickViewMut| pause_tick.context_lost());
+}
+
+// from the "webglcontextrestored" listener
+// the framebuffers are left for the resize that comes after this, and the game is still paused
+// sprites only know their atlas page, not the texture, so nothing else needs to change
+pub fn rebuild_context(world: &World) -> Result<()> {
+    world.run(
+        |media: MediaView,
+         mut renderer: RendererViewMut,
+         mut atlas: TextureAtlasViewMut,
+         mut camera: CameraViewMut,
+         mut background: BackgroundViewMut,
+         mut collision_debugger: UniqueViewMut<CollisionDebugger>,
+         mut particles: ParticleSystemViewMut,
+         mut collision_events: CollisionEventQueueViewMut|
+         -> Result<()> {
+            renderer.rebuild()?;
+
+            // packed the same way as the first time, just onto new textures
+            *atlas = TextureAtlas::new(&mut renderer, &media)?;
+
+            camera.buffer_id = renderer.create_buffer()?;
+            background.rebuild(&mut renderer, &media)?;
+            *collision_debugger = CollisionDebugger {
+                draw: collision_debugger.draw,
+                ..CollisionDebugger::new(&mut renderer)?
+            };
+            particles.rebuild_textures(&mut renderer)?;
+
+            // their occlusion queries went along with the old context
+            collision_events.clear();
+
+            Ok(())
+        },
+    )
+}
+
+// once everything's rebuilt and resized
+pub fn resume_after_context_restored(world: &World) {
+    log::info!("webgl context restored");
+    world.run(|mut pause_tick: PauseTickViewMut| pause_tick.context_restored());
+}
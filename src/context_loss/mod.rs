@@ -0,0 +1,3 @@
+// Getting the gl context back after the browser took it away (gpu reset, driver update, too many tabs etc.)
+// the game waits in PauseTick::LostContext, then everything on the gpu is built again from the Media
+pub mod actions;
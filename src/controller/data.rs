@@ -3,12 +3,13 @@
 // like up/down/left/right, etc.
 // it does _not_ concern itself with actual controllers like jump/hide/shoot
 // that is handled by the enemy controller, ui controller, etc.
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicI32};
 use web_sys::KeyboardEvent;
 
 use crate::{enemy::data::EnemyKind, prelude::*};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Input {
     PointerDown(X, Y),
     PointerDrag(X, Y, DeltaX, DeltaY, DiffX, DiffY),
@@ -18,10 +19,14 @@ pub enum Input {
     KeyDown(Key),
     KeyUp(Key),
     Wheel(WheelDeltaMode, WheelX, WheelY, WheelZ),
+    // from the ui, rather than a keypress
+    SelectEnemy(EnemyKind),
+    // by preset name, see media/data/difficulty.json
+    SelectDifficulty(String),
     ResetButton,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum WheelDeltaMode {
     Pixel,
     Line,
@@ -43,7 +48,7 @@ impl std::convert::TryFrom<u32> for WheelDeltaMode {
 
 // can add more fields as-needed to map from
 // https://rustwasm.github.io/wasm-bindgen/api/web_sys/struct.KeyboardEvent.html
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Key {
     Unknown(String),
     Space,
@@ -1,14 +1,16 @@
-use crate::prelude::*;
+use crate::{config::CONFIG, dom::DomState, layout::scaling::ViewportFit, prelude::*};
 
-pub fn get_canvas_x_y(canvas: &web_sys::HtmlCanvasElement, event: &web_sys::Event) -> (i32, i32) {
+// where the pointer is in the design area, in design units with y going up (like the world)
+// the canvas' css size is what's on screen, its width/height attributes are the (device pixel) backing store
+pub fn get_design_x_y(dom: &DomState, event: &web_sys::Event) -> (i32, i32) {
     let event = event.dyn_ref::<web_sys::MouseEvent>().unwrap_ext();
-    let rect = canvas.get_bounding_client_rect();
-    let (client_x, client_y) = (event.client_x(), event.client_y());
-    let (x, y) = (
-        client_x - (rect.left().round() as i32),
-        client_y - (rect.top().round() as i32),
-    );
+    let rect = dom.canvas.get_bounding_client_rect();
+    let u = (event.client_x() as f64 - rect.left()) / rect.width().max(1.0);
+    let v = (event.client_y() as f64 - rect.top()) / rect.height().max(1.0);
 
-    let y = (canvas.height() as i32) - y;
-    (x, y)
+    let (window_width, window_height) = dom.window_size();
+    let fit = ViewportFit::new(CONFIG.scale_mode, window_width as f64, window_height as f64);
+    let (x, y) = fit.canvas_to_design(u, v);
+
+    (x.round() as i32, y.round() as i32)
 }
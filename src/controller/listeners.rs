@@ -1,7 +1,7 @@
 // These global listeners are started in main
 // They're pure input events and are added to a queue to be mapped and processed later
 use super::data::InputState;
-use super::helpers::get_canvas_x_y;
+use super::helpers::get_design_x_y;
 use super::queue::*;
 use crate::controller::data::{Input, Key};
 use crate::dom::{DomState, DomView};
@@ -29,7 +29,7 @@ impl InputListeners {
                 let state = state.clone();
                 let world = world.clone();
                 move |event| {
-                    let (x, y) = get_canvas_x_y(&dom_view(&world).canvas, event);
+                    let (x, y) = get_design_x_y(&dom_view(&world), event);
                     state.is_pointer_down.store(true, Ordering::SeqCst);
                     state.first_pointer_move_x.store(x, Ordering::SeqCst);
                     state.first_pointer_move_y.store(y, Ordering::SeqCst);
@@ -45,7 +45,7 @@ impl InputListeners {
                 let state = state.clone();
                 let world = world.clone();
                 move |event| {
-                    let (x, y) = get_canvas_x_y(&dom_view(&world).canvas, event);
+                    let (x, y) = get_design_x_y(&dom_view(&world), event);
                     if state.is_pointer_down.load(Ordering::SeqCst) {
                         let (first_x, first_y) = (
                             state.first_pointer_move_x.load(Ordering::SeqCst),
@@ -84,7 +84,7 @@ impl InputListeners {
                 let world = world.clone();
                 move |event| {
                     if state.is_pointer_down.load(Ordering::SeqCst) {
-                        let (x, y) = get_canvas_x_y(&dom_view(&world).canvas, event);
+                        let (x, y) = get_design_x_y(&dom_view(&world), event);
 
                         let (first_x, first_y) = (
                             state.first_pointer_move_x.load(Ordering::SeqCst),
@@ -117,7 +117,7 @@ impl InputListeners {
             EventListener::new(&dom_view(&world).canvas, "click", {
                 let world = world.clone();
                 move |event| {
-                    let (x, y) = get_canvas_x_y(&dom_view(&world).canvas, event);
+                    let (x, y) = get_design_x_y(&dom_view(&world), event);
                     world.run(|mut queue: InputQueueViewMut| {
                         queue.insert_replace(Input::PointerClick(x, y));
                     });
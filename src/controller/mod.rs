@@ -2,4 +2,5 @@ pub mod data;
 pub mod helpers;
 pub mod listeners;
 pub mod queue;
+pub mod recording;
 pub mod systems;
@@ -0,0 +1,184 @@
+// Every Input passes through the InputQueue, so that's where we record
+// A recording is just the seed plus each input and the frame it was processed on
+// replaying feeds those inputs back into the queue on the same frames, instead of the live listeners
+use awsm_web::loaders;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::*,
+    tick::{FrameCount, FrameCountView},
+};
+
+use super::{
+    data::{Input, Key},
+    queue::InputQueueViewMut,
+};
+
+pub type InputRecorderViewMut<'a> = UniqueViewMut<'a, InputRecorder>;
+pub type InputRecorderView<'a> = UniqueView<'a, InputRecorder>;
+pub type InputReplayViewMut<'a> = UniqueViewMut<'a, InputReplay>;
+pub type InputReplayView<'a> = UniqueView<'a, InputReplay>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputRecording {
+    pub version: u32,
+    pub seed: u64,
+    // the level name, if it wasn't the endless bomber
+    #[serde(default)]
+    pub level: Option<String>,
+    // (frame, input) - kept as tuples so the json stays compact
+    pub inputs: Vec<(u64, Input)>,
+}
+
+impl InputRecording {
+    // 2: bomb drops are paced by the difficulty presets
+    pub const VERSION: u32 = 2;
+
+    pub fn new(seed: u64, level: Option<String>) -> Self {
+        Self {
+            version: Self::VERSION,
+            seed,
+            level,
+            inputs: Vec::new(),
+        }
+    }
+
+    pub async fn load(url: &str) -> Result<Self> {
+        let recording: Self = loaders::fetch::fetch_url(url)
+            .await?
+            .json_from_str()
+            .await?;
+
+        if recording.version != Self::VERSION {
+            return Err(anyhow!(
+                "unsupported recording version {} (expected {})",
+                recording.version,
+                Self::VERSION
+            ));
+        }
+
+        Ok(recording)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    // triggers a browser download of the recording
+    pub fn download(&self, filename: &str) -> Result<()> {
+        let json = self.to_json()?;
+        let href = format!(
+            "data:application/json;charset=utf-8,{}",
+            String::from(js_sys::encode_uri_component(&json))
+        );
+
+        let anchor: web_sys::HtmlAnchorElement = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| anyhow!("no document"))?
+            .create_element("a")
+            .map_err(|_| anyhow!("could not create anchor"))?
+            .unchecked_into();
+
+        anchor.set_href(&href);
+        anchor.set_download(filename);
+        anchor.click();
+
+        Ok(())
+    }
+}
+
+#[derive(Component, Unique)]
+pub struct InputRecorder {
+    // None if we're not recording (e.g. while replaying)
+    pub recording: Option<InputRecording>,
+}
+
+impl InputRecorder {
+    pub fn new(seed: u64, level: Option<String>) -> Self {
+        Self {
+            recording: Some(InputRecording::new(seed, level)),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self { recording: None }
+    }
+
+    pub fn download(&self) -> Result<()> {
+        match &self.recording {
+            Some(recording) => {
+                recording.download(&format!("not-a-game-replay-{}.json", recording.seed))
+            }
+            None => Err(anyhow!("nothing was recorded")),
+        }
+    }
+
+    pub fn record(&mut self, frame: u64, input: &Input) {
+        if let Some(recording) = &mut self.recording {
+            match input {
+                // pure cursor noise, nothing in the game reacts to it
+                Input::PointerHover(..) => {}
+                _ => recording.inputs.push((frame, input.clone())),
+            }
+        }
+    }
+}
+
+#[derive(Component, Unique)]
+pub struct InputReplay {
+    pub recording: Option<InputRecording>,
+    pub cursor: usize,
+}
+
+impl InputReplay {
+    pub fn new(recording: Option<InputRecording>) -> Self {
+        Self {
+            recording,
+            cursor: 0,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        match &self.recording {
+            Some(recording) => self.cursor >= recording.inputs.len(),
+            None => true,
+        }
+    }
+}
+
+// Runs at the start of the "controller" workload
+// while replaying, live input is dropped and the recorded inputs that are due on this frame are queued up instead
+// pause is the exception: it doesn't affect the simulation, so the viewer controls it rather than the recording
+pub fn input_replay_sys(
+    mut replay: InputReplayViewMut,
+    mut input_queue: InputQueueViewMut,
+    frame_count: FrameCountView,
+) {
+    let replay = &mut *replay;
+
+    if let Some(recording) = &replay.recording {
+        input_queue.0.retain(|input| match input {
+            Input::KeyDown(Key::Pause) | Input::KeyUp(Key::Pause) => true,
+            _ => false,
+        });
+
+        while let Some((frame, input)) = recording.inputs.get(replay.cursor) {
+            if *frame > frame_count.0 {
+                break;
+            }
+            match input {
+                Input::KeyDown(Key::Pause) | Input::KeyUp(Key::Pause) => {}
+                _ => input_queue.insert_always(input.clone()),
+            }
+            replay.cursor += 1;
+        }
+    }
+}
@@ -2,8 +2,13 @@ use crate::{
     audio::AudioEventQueue,
     collision::debug::CollisionDebugger,
     config::CONFIG,
+    difficulty::data::DifficultyViewMut,
     dispatch_select_event,
-    dom::DomViewMut,
+    dom::{
+        events::{UiEvent, UiEventQueue},
+        ui::game::GameUiPhase,
+        DomViewMut,
+    },
     enemy::{
         controller::{data::ActiveEnemyController, process::EnemyControllerInput},
         data::{Enemy, EnemyKind},
@@ -11,12 +16,15 @@ use crate::{
     },
     layout::data::LayoutPosition,
     prelude::*,
-    tick::PauseTick,
+    restart::data::RestartRequestViewMut,
+    stats::data::RunStatsViewMut,
+    tick::{FrameCountView, PauseTick},
 };
 
 use super::{
     data::{Input, Key},
     queue::InputQueueViewMut,
+    recording::InputRecorderViewMut,
 };
 
 // The input_queue itself was added to via DOM events (see Listener)
@@ -27,24 +35,54 @@ pub fn controller_process_queue_sys(
     mut enemy_select_events: ViewMut<EnemySelectEvent>,
     mut collision_debugger: UniqueViewMut<CollisionDebugger>,
     mut pause_tick: UniqueViewMut<PauseTick>,
-    mut audio_events: UniqueViewMut<AudioEventQueue>,
+    feedback: (UniqueViewMut<AudioEventQueue>, RunStatsViewMut),
     active_controllers: View<ActiveEnemyController>,
     positions: View<LayoutPosition>,
+    recording: (InputRecorderViewMut, FrameCountView),
+    game_flow: (
+        RestartRequestViewMut,
+        UniqueViewMut<UiEventQueue>,
+        DifficultyViewMut,
+    ),
 ) {
+    let (mut recorder, frame_count) = recording;
+    let (mut restart, mut ui_events, mut difficulty) = game_flow;
+    let (mut audio_events, mut stats) = feedback;
+
     for input in input_queue.0.drain(..) {
+        recorder.record(frame_count.0, &input);
+
         match &input {
             Input::KeyDown(key) => match key {
                 Key::Pause => match *pause_tick {
                     PauseTick::Running => {
                         *pause_tick = PauseTick::ManuallyPaused;
+                        ui_events.push(UiEvent::GamePhase(Some(GameUiPhase::Paused)));
                     }
                     PauseTick::ManuallyPaused => {
                         *pause_tick = PauseTick::Running;
+                        ui_events.push(UiEvent::GamePhase(None));
                     }
                     _ => {}
                 },
                 _ => {}
             },
+            // Select enemy via the ui (footer is clickable while paused too)
+            Input::SelectEnemy(kind) => {
+                dispatch_select_event!(&enemies, &mut enemy_select_events, *kind);
+            }
+            // from the game over / winner / pause screens, the actual restart happens after this workload
+            Input::ResetButton => {
+                restart.requested = true;
+            }
+            // from the welcome screen, takes effect on the next drop
+            Input::SelectDifficulty(name) => {
+                if difficulty.select(name) {
+                    ui_events.push(UiEvent::Difficulty(name.clone()));
+                } else {
+                    log::warn!("no such difficulty: {}", name);
+                }
+            }
             _ => {}
         }
 
@@ -66,6 +104,7 @@ pub fn controller_process_queue_sys(
                     position: &position,
                 },
                 &mut audio_events,
+                &mut stats,
             );
         }
 
@@ -0,0 +1,167 @@
+use serde::Deserialize;
+
+use crate::{prelude::*, stats::data::RunStats};
+
+pub type DifficultyViewMut<'a> = UniqueViewMut<'a, Difficulty>;
+pub type DifficultyView<'a> = UniqueView<'a, Difficulty>;
+
+// the one that's selected until the player picks something else
+pub const DEFAULT_PRESET: &str = "normal";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DifficultyPresets {
+    // in the order they're shown on the welcome screen
+    pub presets: Vec<DifficultyPreset>,
+}
+
+impl DifficultyPresets {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let presets: Self = serde_json::from_str(json)?;
+        presets.validate()?;
+        Ok(presets)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.presets.is_empty() {
+            return Err(anyhow!("no difficulty presets"));
+        }
+        for preset in &self.presets {
+            preset.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DifficultyPreset {
+    // stable id, this is what goes into recordings and high scores
+    pub name: String,
+    pub label: String,
+    pub ramp: DifficultyRamp,
+    // sorted by `at`, settings are interpolated between neighbouring stages
+    pub stages: Vec<DifficultyStage>,
+}
+
+// what `DifficultyStage::at` is measured in
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DifficultyRamp {
+    // seconds survived
+    Time,
+    // bombs taken out
+    Intercepts,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DifficultyStage {
+    pub at: f64,
+    // milliseconds between drops, (min, max)
+    pub drop_interval: (f64, f64),
+    pub bombs_per_drop: u32,
+    // vertical acceleration of each bomb, (min, max), negative is down
+    pub acceleration: (f64, f64),
+    // how much of the screen width bombs can land on, centered. 1.0 is all of it
+    pub spread: f64,
+}
+
+impl DifficultyPreset {
+    fn validate(&self) -> Result<()> {
+        let name = &self.name;
+        if self.stages.is_empty() {
+            return Err(anyhow!("difficulty {name} has no stages"));
+        }
+        for stage in &self.stages {
+            if stage.drop_interval.0 <= 0.0 || stage.drop_interval.0 > stage.drop_interval.1 {
+                return Err(anyhow!("difficulty {name} has a bad drop interval"));
+            }
+            if stage.acceleration.0 > stage.acceleration.1 {
+                return Err(anyhow!("difficulty {name} has a bad acceleration range"));
+            }
+            if !(0.0..=1.0).contains(&stage.spread) {
+                return Err(anyhow!("difficulty {name} spread must be within 0..1"));
+            }
+        }
+        if self.stages.windows(2).any(|pair| pair[0].at >= pair[1].at) {
+            return Err(anyhow!("difficulty {name} stages must be sorted by `at`"));
+        }
+        Ok(())
+    }
+
+    pub fn stage_at(&self, progress: f64) -> DifficultyStage {
+        let next = self.stages.iter().position(|stage| stage.at > progress);
+
+        match next {
+            // before the first stage, or past the last one
+            Some(0) => self.stages[0].clone(),
+            None => self.stages[self.stages.len() - 1].clone(),
+            Some(index) => {
+                let prev = &self.stages[index - 1];
+                let next = &self.stages[index];
+                let t = (progress - prev.at) / (next.at - prev.at);
+                let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+                DifficultyStage {
+                    at: progress,
+                    drop_interval: (
+                        lerp(prev.drop_interval.0, next.drop_interval.0),
+                        lerp(prev.drop_interval.1, next.drop_interval.1),
+                    ),
+                    // only step up once the next stage is actually reached
+                    bombs_per_drop: prev.bombs_per_drop,
+                    acceleration: (
+                        lerp(prev.acceleration.0, next.acceleration.0),
+                        lerp(prev.acceleration.1, next.acceleration.1),
+                    ),
+                    spread: lerp(prev.spread, next.spread),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Component, Unique)]
+pub struct Difficulty {
+    pub presets: DifficultyPresets,
+    selected: usize,
+}
+
+impl Difficulty {
+    pub fn new(presets: DifficultyPresets) -> Self {
+        let selected = presets
+            .presets
+            .iter()
+            .position(|preset| preset.name == DEFAULT_PRESET)
+            .unwrap_or_default();
+
+        Self { presets, selected }
+    }
+
+    pub fn selected(&self) -> &DifficultyPreset {
+        &self.presets.presets[self.selected]
+    }
+
+    // returns false (and keeps the current one) if there's no such preset
+    pub fn select(&mut self, name: &str) -> bool {
+        match self
+            .presets
+            .presets
+            .iter()
+            .position(|preset| preset.name == name)
+        {
+            Some(index) => {
+                self.selected = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn current(&self, stats: &RunStats) -> DifficultyStage {
+        let preset = self.selected();
+        let progress = match preset.ramp {
+            DifficultyRamp::Time => stats.time_survived / 1000.0,
+            DifficultyRamp::Intercepts => stats.total_intercepts() as f64,
+        };
+        preset.stage_at(progress)
+    }
+}
@@ -0,0 +1,3 @@
+// How hard the bomber pushes, as a function of how far into the run we are
+// the curves are data (media/data/difficulty.json), the selected preset lives in the world
+pub mod data;
@@ -0,0 +1,107 @@
+// Systems don't touch the dom directly, they push UiEvents instead
+// and the host drains them (in the browser that's ui_event_process_sys)
+// this keeps the simulation systems runnable without a browser
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    controller::recording::InputReplayView,
+    dom::ui::game::GameUiPhase,
+    enemy::{attack::data::WeaponStatus, data::EnemyKind},
+    high_scores::data::RunRecord,
+    prelude::*,
+    stats::data::RunStats,
+};
+
+use super::DomView;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UiEvent {
+    EnemySelected(EnemyKind),
+    EnemyDestroyed(EnemyKind),
+    GamePhase(Option<GameUiPhase>),
+    // the new run's seed
+    Restarted(u64),
+    RunStats(RunStats),
+    // the final stats, sent once when the run is over (won or lost)
+    RunEnded(RunStats),
+    WeaponStatus(EnemyKind, WeaponStatus),
+    // preset name
+    Difficulty(String),
+}
+
+#[derive(Unique, Component, Default)]
+pub struct UiEventQueue(Vec<UiEvent>);
+impl UiEventQueue {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl Deref for UiEventQueue {
+    type Target = Vec<UiEvent>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for UiEventQueue {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+pub fn ui_event_process_sys(
+    mut event_queue: UniqueViewMut<UiEventQueue>,
+    dom: DomView,
+    replay: InputReplayView,
+) {
+    if event_queue.is_empty() {
+        return;
+    }
+
+    let game_ui = dom.ui.game_ui_unchecked();
+
+    for event in event_queue.drain(..) {
+        match event {
+            UiEvent::EnemySelected(kind) => {
+                game_ui.selected_kind.set_neq(Some(kind));
+            }
+            UiEvent::EnemyDestroyed(kind) => {
+                game_ui.destroyed_kinds.lock_mut().insert(kind);
+            }
+            UiEvent::GamePhase(phase) => {
+                game_ui.phase.set_neq(phase);
+            }
+            UiEvent::WeaponStatus(kind, status) => {
+                game_ui.weapons.lock_mut().insert(kind, status);
+            }
+            UiEvent::Difficulty(name) => {
+                game_ui.difficulty.set_neq(name);
+            }
+            UiEvent::RunStats(stats) => {
+                game_ui.stats.set(stats);
+            }
+            UiEvent::RunEnded(stats) => {
+                // a replay already got its score when it was played for real
+                if !replay.is_active() {
+                    let record = RunRecord::new(
+                        &stats,
+                        js_sys::Date::now() as u64,
+                        game_ui.seed.get(),
+                        &game_ui.mode(),
+                    );
+                    if let Err(err) = game_ui.high_scores.lock_mut().add(record) {
+                        log::warn!("couldn't save high score: {}", err);
+                    }
+                }
+                game_ui.stats.set(stats);
+            }
+            UiEvent::Restarted(seed) => {
+                game_ui.seed.set_neq(seed);
+                game_ui.destroyed_kinds.lock_mut().clear();
+                game_ui.phase.set_neq(None);
+            }
+        }
+    }
+}
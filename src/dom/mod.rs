@@ -1,5 +1,6 @@
 pub mod atoms;
 pub mod canvas;
+pub mod events;
 pub mod state;
 pub mod theme;
 pub mod ui;
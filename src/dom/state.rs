@@ -9,6 +9,7 @@ use web_sys::{
 
 use super::canvas::Canvas;
 use super::ui::Ui;
+use crate::layout::scaling::ViewportFit;
 
 pub type DomViewMut<'a> = NonSendSync<UniqueViewMut<'a, DomState>>;
 pub type DomView<'a> = NonSendSync<UniqueView<'a, DomState>>;
@@ -51,6 +52,41 @@ impl DomState {
         get_window_size(&self.window).unwrap_ext()
     }
 
+    // the canvas is only as big as the game area in the window, see layout/scaling.rs
+    // its backing store (the width/height attributes) is sized separately, by the renderer
+    // the footer goes along with it, via css variables (see ui/game/footer.rs)
+    pub fn place_canvas(&self, fit: &ViewportFit) {
+        let style = self.canvas.style();
+        for (name, value) in [
+            ("left", fit.canvas_x),
+            ("top", fit.canvas_y),
+            ("width", fit.canvas_width),
+            ("height", fit.canvas_height),
+        ] {
+            style
+                .set_property(name, &format!("{}px", value))
+                .unwrap_ext();
+        }
+
+        let (left, top, width, height) = fit.footer();
+        let style = self.body.style();
+        for (name, value) in [
+            ("--footer-left", left),
+            ("--footer-top", top),
+            ("--footer-width", width),
+            ("--footer-height", height),
+        ] {
+            style
+                .set_property(name, &format!("{}px", value))
+                .unwrap_ext();
+        }
+    }
+
+    // 1 on regular screens, usually 2 or more on high-dpi ones
+    pub fn pixel_ratio(&self) -> f64 {
+        self.window.device_pixel_ratio()
+    }
+
     pub fn create_gl_context(&self) -> WebGl2RenderingContext {
         get_webgl_context_2(
             &self.canvas,
@@ -1,4 +1,4 @@
-use super::{responsive::WindowSizeListener, typography::FONT_FAMILY_INTER};
+use super::{color::Color, responsive::WindowSizeListener, typography::FONT_FAMILY_INTER};
 use crate::prelude::*;
 use dominator::stylesheet;
 
@@ -20,6 +20,9 @@ pub fn init() {
         .style("width", "100%")
         .style("height", "100%")
         .style("font-family", FONT_FAMILY_INTER)
+        // what shows around a letterboxed canvas
+        .style("background-color", Color::Darkest.hex_str())
+        .style("overflow", "hidden")
         .style(["-moz-user-select", "user-select"], "none")
     });
 
@@ -17,6 +17,8 @@ pub enum UiPhase {
     Shaders,
     Initializing,
     Playing(Arc<GameUi>),
+    // something went wrong before the game could start (e.g. bad config)
+    Error(String),
 }
 
 impl Ui {
@@ -75,6 +77,12 @@ impl Ui {
                     },
                     UiPhase::Playing(game_ui) => {
                         Some(game_ui.render())
+                    },
+                    UiPhase::Error(message) => {
+                        Some(html!("div", {
+                            .class([&*FULL_SCREEN, &*SECTION, Color::Red.class(), &*TEXT_SIZE_MD])
+                            .text(&message)
+                        }))
                     }
                 }
             }))
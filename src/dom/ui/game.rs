@@ -1,25 +1,36 @@
 mod footer;
 mod game_over;
 mod help;
+mod high_scores;
+mod paused;
+mod stats;
 mod welcome;
 mod winner;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use game_over::GameOver;
 use gloo_timers::future::TimeoutFuture;
 use help::Help;
+use high_scores::HighScoresView;
+use paused::Paused;
 use wasm_bindgen_futures::spawn_local;
 use welcome::Welcome;
 use winner::Winner;
 
 use crate::{
+    controller::{data::Input, queue::InputQueueViewMut},
+    difficulty::data::DifficultyView,
     dispatch_select_event,
     enemy::{
+        attack::data::WeaponStatus,
         data::{Enemy, EnemyKind},
         events::EnemySelectEvent,
     },
+    high_scores::{data::HighScores, storage::LocalStorage},
+    level::data::{Level, LevelRunnerView},
     prelude::*,
+    stats::data::RunStats,
 };
 
 #[derive(Clone)]
@@ -27,7 +38,15 @@ pub struct GameUi {
     pub world: Arc<World>,
     pub selected_kind: Mutable<Option<EnemyKind>>,
     pub destroyed_kinds: Mutable<HashSet<EnemyKind>>,
+    pub weapons: Mutable<HashMap<EnemyKind, WeaponStatus>>,
     pub phase: Mutable<Option<GameUiPhase>>,
+    pub seed: Mutable<u64>,
+    pub stats: Mutable<RunStats>,
+    pub high_scores: Mutable<HighScores>,
+    // selected preset name
+    pub difficulty: Mutable<String>,
+    // fixed for the session, None is the endless bomber
+    pub level: Option<Level>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -35,16 +54,35 @@ pub enum GameUiPhase {
     Welcome,
     Winner,
     Help,
+    Paused,
     GameOver,
+    HighScores,
 }
 
 impl GameUi {
     pub fn new(world: Arc<World>) -> Arc<Self> {
+        let seed = world.borrow::<RandView>().unwrap_ext().seed();
+        let difficulty = world
+            .borrow::<DifficultyView>()
+            .unwrap_ext()
+            .selected()
+            .name
+            .clone();
+        let level = world.borrow::<LevelRunnerView>().unwrap_ext().level.clone();
+
         let _self = Arc::new(Self {
             world,
             selected_kind: Mutable::new(None),
             destroyed_kinds: Mutable::new(HashSet::new()),
+            weapons: Mutable::new(HashMap::new()),
             phase: Mutable::new(None),
+            seed: Mutable::new(seed),
+            stats: Mutable::new(RunStats::new()),
+            high_scores: Mutable::new(HighScores::load(Box::new(LocalStorage::new(
+                LocalStorage::DEFAULT_KEY,
+            )))),
+            difficulty: Mutable::new(difficulty),
+            level,
         });
 
         _self
@@ -68,16 +106,22 @@ impl GameUi {
                     Some(phase) => {
                         match phase {
                             GameUiPhase::Welcome => {
-                                Some(Welcome::new(state.world.clone(), state.phase.clone()).render())
+                                Some(Welcome::new(state.world.clone(), state.phase.clone(), state.difficulty.clone(), state.level.clone()).render())
                             },
                             GameUiPhase::Help=> {
                                 Some(Help::new(state.world.clone(), state.phase.clone()).render())
                             },
+                            GameUiPhase::Paused => {
+                                Some(Paused::new(state.world.clone()).render())
+                            },
                             GameUiPhase::GameOver => {
-                                Some(GameOver::new().render())
+                                Some(GameOver::new(state.world.clone(), state.phase.clone(), state.stats.clone()).render())
+                            },
+                            GameUiPhase::HighScores => {
+                                Some(HighScoresView::new(state.world.clone(), state.phase.clone(), state.high_scores.clone()).render())
                             },
                             GameUiPhase::Winner => {
-                                Some(Winner::new().render())
+                                Some(Winner::new(state.world.clone(), state.stats.clone()).render())
                             },
                         }
                     }
@@ -86,15 +130,29 @@ impl GameUi {
         })
     }
 
+    // what a run is filed under in the high scores
+    pub fn mode(&self) -> String {
+        match &self.level {
+            Some(level) => format!("level: {}", level.name),
+            None => self.difficulty.get_cloned(),
+        }
+    }
+
+    pub fn enemy_kinds(&self) -> Vec<EnemyKind> {
+        match &self.level {
+            Some(level) => level.enemies.clone(),
+            None => Level::all_enemies(),
+        }
+    }
+
     pub fn select_enemy(&self, kind: EnemyKind) {
-        // just dispatch the event, the system will handle it
+        // just queue the input, the systems will handle it
         // and ultimately set the local mutable
         // perhaps we could make it a _little_ more responsive by setting it here too
         // but better for sanity checking to just let it flow from the system
-        self.world.run(
-            |mut events: ViewMut<EnemySelectEvent>, enemies: View<Enemy>| {
-                dispatch_select_event!(enemies, &mut events, kind);
-            },
-        );
+        // (going through the input queue also means it gets recorded for replays)
+        self.world.run(|mut queue: InputQueueViewMut| {
+            queue.insert_always(Input::SelectEnemy(kind));
+        });
     }
 }
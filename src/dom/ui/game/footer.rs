@@ -17,9 +17,11 @@ use super::GameUi;
 static CONTAINER: LazyLock<String> = LazyLock::new(|| {
     class! {
         .style("position", "absolute")
-        .style("width", "100vw")
-        .style("top", "calc(100vh - 64px)")
-        .style("height", "64px")
+        // the bottom of the design area, wherever that ended up in the window (see DomState::place_canvas)
+        .style("left", "var(--footer-left, 0px)")
+        .style("top", "var(--footer-top, calc(100vh - 64px))")
+        .style("width", "var(--footer-width, 100vw)")
+        .style("height", "var(--footer-height, 64px)")
         .style("overflow", "hidden")
     }
 });
@@ -45,6 +47,15 @@ static CONTENT: LazyLock<String> = LazyLock::new(|| {
     }
 });
 
+static SEED: LazyLock<String> = LazyLock::new(|| {
+    class! {
+        .style("position", "absolute")
+        .style("right", "1rem")
+        .style("bottom", "0.5rem")
+        .style("user-select", "text")
+    }
+});
+
 impl GameUi {
     pub fn render_footer(self: &Arc<Self>) -> Dom {
         let state = self;
@@ -55,19 +66,18 @@ impl GameUi {
             }))
             .child(html!("div", {
                 .class(&*CONTENT)
-                .children((0..4).map(|i| {
-                    let kind = match i {
-                        0 => EnemyKind::One,
-                        1 => EnemyKind::Two,
-                        2 => EnemyKind::Three,
-                        3 => EnemyKind::Four,
-                        _ => unreachable!("Invalid enemy index")
-                    };
+                // only the ones on the field, a level might not have them all
+                .children(state.enemy_kinds().into_iter().map(|kind| {
                     state.clone().render_enemy_box(kind)
                 }))
                 .child(HelpButton::render(ButtonSize::Md, clone!(state => move || {
                     state.phase.set_neq(Some(GameUiPhase::Help));
                 })))
+                .child(state.render_live_stats())
+            }))
+            .child(html!("div", {
+                .class([&*SEED, &*TEXT_SIZE_SM, Color::Whiteish.class()])
+                .text_signal(state.seed.signal().map(|seed| format!("seed: {seed}")))
             }))
         })
     }
@@ -86,6 +96,14 @@ impl GameUi {
             }
         });
 
+        static LABEL: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("display", "flex")
+                .style("flex-direction", "column")
+                .style("align-items", "center")
+            }
+        });
+
         static KILLED: LazyLock<String> = LazyLock::new(|| {
             class! {
                 .style("opacity", "0.2")
@@ -98,11 +116,30 @@ impl GameUi {
             .class_signal([Color::Accent.class()], state.selected_kind.signal().map(move |selected| selected == Some(kind)))
             .class_signal([Color::Red.class()], state.selected_kind.signal().map(move |selected| selected != Some(kind)))
             .class_signal([&*KILLED], state.destroyed_kinds.signal_cloned().map(move |destroyed| destroyed.contains(&kind)))
-            .text(&format!("Enemy {}", match kind {
-                EnemyKind::One => "1",
-                EnemyKind::Two => "2",
-                EnemyKind::Three => "3",
-                EnemyKind::Four => "4",
+            .child(html!("div", {
+                .class(&*LABEL)
+                .child(html!("div", {
+                    .text(&format!("Enemy {}", match kind {
+                        EnemyKind::One => "1",
+                        EnemyKind::Two => "2",
+                        EnemyKind::Three => "3",
+                        EnemyKind::Four => "4",
+                    }))
+                }))
+                .child(html!("div", {
+                    .class(&*TEXT_SIZE_SM)
+                    .text_signal(state.weapons.signal_ref(move |weapons| {
+                        match weapons.get(&kind) {
+                            None => String::new(),
+                            Some(status) if status.reloading => "reloading...".to_string(),
+                            Some(status) => match status.charged {
+                                Some(false) => "charging...".to_string(),
+                                Some(true) => "release!".to_string(),
+                                None => format!("ammo {}/{}", status.ammo, status.magazine),
+                            },
+                        }
+                    }))
+                }))
             }))
             .event(clone!(state => move |_: events::Click| {
                 state.select_enemy(kind);
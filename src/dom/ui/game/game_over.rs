@@ -2,19 +2,33 @@ use gloo_timers::future::TimeoutFuture;
 
 use crate::{
     config::CONFIG,
+    controller::{data::Input, queue::InputQueueViewMut, recording::InputRecorderView},
     prelude::*,
+    stats::data::RunStats,
     tick::{PauseTick, PauseTickViewMut},
 };
 
 use crate::dom::atoms::buttons::*;
 
-use super::GameUiPhase;
+use super::{stats::render_run_stats, GameUiPhase};
 
-pub struct GameOver {}
+pub struct GameOver {
+    pub world: Arc<World>,
+    pub game_phase: Mutable<Option<GameUiPhase>>,
+    pub stats: Mutable<RunStats>,
+}
 
 impl GameOver {
-    pub fn new() -> Arc<Self> {
-        Arc::new(Self {})
+    pub fn new(
+        world: Arc<World>,
+        game_phase: Mutable<Option<GameUiPhase>>,
+        stats: Mutable<RunStats>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            world,
+            game_phase,
+            stats,
+        })
     }
 }
 
@@ -66,12 +80,41 @@ impl GameOver {
                                 .class(&*TEXT_SIZE_XLG)
                                 .text("You lose!")
                             }),
+                            html!("div", {
+                                // the final numbers may arrive a moment after the phase change
+                                .child_signal(state.stats.signal_ref(|stats| Some(render_run_stats(stats))))
+                            }),
+                            Button::new()
+                                .with_text("Play again")
+                                .with_color(ButtonColor::Green)
+                                .with_on_click(clone!(state => move || {
+                                    state.world.run(|mut queue: InputQueueViewMut| {
+                                        queue.insert_always(Input::ResetButton);
+                                    });
+                                }))
+                                .render(),
+                            Button::new()
+                                .with_text("High scores")
+                                .with_color(ButtonColor::Blue)
+                                .with_on_click(clone!(state => move || {
+                                    state.game_phase.set_neq(Some(GameUiPhase::HighScores));
+                                }))
+                                .render(),
                             Button::new()
                                 .with_text("What's this all about?")
                                 .with_color(ButtonColor::Blue)
                                 .with_on_click(clone!(state => move || {
                                     web_sys::window().unwrap_ext().location().set_href(CONFIG.html_url("about.html").as_str()).unwrap();
                                 }))
+                                .render(),
+                            Button::new()
+                                .with_text("Download replay")
+                                .with_color(ButtonColor::Darkish)
+                                .with_on_click(clone!(state => move || {
+                                    if let Err(err) = state.world.borrow::<InputRecorderView>().unwrap_ext().download() {
+                                        log::warn!("couldn't download replay: {}", err);
+                                    }
+                                }))
                                 .render()
                         ])
                     }))
@@ -111,6 +111,9 @@ fn render_controls() -> Dom {
                     html!("li", {
                         .text("Space to shoot")
                     }),
+                    html!("li", {
+                        .text("Enemy 4 has to charge: hold Space, then release")
+                    }),
                     html!("li", {
                         .text("P to pause")
                     }),
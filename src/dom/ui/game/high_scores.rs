@@ -0,0 +1,198 @@
+use crate::{
+    high_scores::data::{HighScores, RunRecord},
+    prelude::*,
+    tick::{PauseTick, PauseTickView},
+    utils::time::display_timestamp,
+};
+
+use crate::dom::atoms::buttons::*;
+
+use super::GameUiPhase;
+
+pub struct HighScoresView {
+    world: Arc<World>,
+    game_phase: Mutable<Option<GameUiPhase>>,
+    high_scores: Mutable<HighScores>,
+}
+
+impl HighScoresView {
+    pub fn new(
+        world: Arc<World>,
+        game_phase: Mutable<Option<GameUiPhase>>,
+        high_scores: Mutable<HighScores>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            world,
+            game_phase,
+            high_scores,
+        })
+    }
+
+    // back to whichever screen we came from, which the pause state still tells us
+    fn back(&self) {
+        let phase = match *self.world.borrow::<PauseTickView>().unwrap_ext() {
+            PauseTick::GameOver {} => GameUiPhase::GameOver,
+            PauseTick::Winner {} => GameUiPhase::Winner,
+            _ => GameUiPhase::Welcome,
+        };
+        self.game_phase.set_neq(Some(phase));
+    }
+}
+
+impl HighScoresView {
+    pub fn render(self: &Arc<Self>) -> Dom {
+        let state = self;
+
+        static CONTAINER: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("display", "flex")
+                .style("flex-direction", "column")
+                .style("justify-content", "center")
+                .style("align-items", "center")
+            }
+        });
+        static BG: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("background-color", Color::Darkish.hex_str())
+                .style("opacity", "0.5")
+
+            }
+        });
+
+        static CONTENT: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("width", "80%")
+                .style("max-height", "80%")
+                .style("overflow-y", "auto")
+                .style("padding", "2rem 0")
+                .style("background-color", Color::Whiteish.hex_str())
+                .style("display", "flex")
+                .style("border-radius", "8px")
+                .style("gap", "1rem")
+                .style("flex-direction", "column")
+                .style("justify-content", "center")
+                .style("align-items", "center")
+            }
+        });
+
+        static BUTTON_ROW: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("display", "flex")
+                .style("align-items", "center")
+                .style("gap", "1rem")
+            }
+        });
+
+        html!("div", {
+            .child(html!("div", {
+                .child(html!("div", {
+                    .class([&*FULL_SCREEN, &*BG])
+                }))
+                .child(html!("div", {
+                    .class([&*FULL_SCREEN, &*CONTAINER])
+                    .child(html!("div", {
+                        .class(&*CONTENT)
+                        .child_signal(state.high_scores.signal_ref(|high_scores| {
+                            Some(html!("div", {
+                                .children(&mut [
+                                    html!("div", {
+                                        .class(&*TEXT_SIZE_XLG)
+                                        .text("High scores")
+                                    }),
+                                    render_table(&high_scores.best, true),
+                                    html!("div", {
+                                        .class(&*TEXT_SIZE_XLG)
+                                        .style("margin-top", "1rem")
+                                        .text("Recent runs")
+                                    }),
+                                    render_table(&high_scores.history, false),
+                                ])
+                            }))
+                        }))
+                        .child(html!("div", {
+                            .class(&*BUTTON_ROW)
+                            .children(&mut [
+                                Button::new()
+                                    .with_text("Back")
+                                    .with_color(ButtonColor::Green)
+                                    .with_on_click(clone!(state => move || {
+                                        state.back();
+                                    }))
+                                    .render(),
+                                Button::new()
+                                    .with_text("Clear")
+                                    .with_color(ButtonColor::Red)
+                                    .with_on_click(clone!(state => move || {
+                                        if let Err(err) = state.high_scores.lock_mut().clear() {
+                                            log::warn!("couldn't clear high scores: {}", err);
+                                        }
+                                    }))
+                                    .render(),
+                            ])
+                        }))
+                    }))
+                }))
+            }))
+        })
+    }
+}
+
+fn render_table(records: &[RunRecord], ranked: bool) -> Dom {
+    static TABLE: LazyLock<String> = LazyLock::new(|| {
+        class! {
+            .style("display", "grid")
+            .style("grid-template-columns", "repeat(6, auto)")
+            .style("column-gap", "1.5rem")
+            .style("row-gap", "0.25rem")
+        }
+    });
+
+    if records.is_empty() {
+        return html!("div", {
+            .class([&*TEXT_SIZE_MD, Color::Darkish.class()])
+            .text("Nothing yet")
+        });
+    }
+
+    let header = [
+        if ranked { "#" } else { "" },
+        "Date",
+        "Duration",
+        "Score",
+        "Seed",
+        "Mode",
+    ]
+    .into_iter()
+    .map(|label| {
+        html!("div", {
+            .class(&*TEXT_WEIGHT_BOLD)
+            .text(label)
+        })
+    });
+
+    let rows = records.iter().enumerate().flat_map(|(index, record)| {
+        [
+            if ranked {
+                format!("{}", index + 1)
+            } else {
+                String::new()
+            },
+            display_timestamp(record.timestamp),
+            format!("{}s", (record.duration / 1000.0) as u32),
+            record.score.to_string(),
+            record.seed.to_string(),
+            record.mode.clone(),
+        ]
+        .into_iter()
+        .map(|value| {
+            html!("div", {
+                .text(&value)
+            })
+        })
+    });
+
+    html!("div", {
+        .class([&*TABLE, &*TEXT_SIZE_MD, Color::Darkish.class()])
+        .children(header.chain(rows))
+    })
+}
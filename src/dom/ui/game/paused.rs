@@ -0,0 +1,158 @@
+use crate::{
+    controller::{
+        data::{Input, Key},
+        queue::InputQueueViewMut,
+    },
+    post::data::{PostPass, PostQuality, PostSettings, PostSettingsView, PostSettingsViewMut},
+    prelude::*,
+};
+
+use crate::dom::atoms::buttons::*;
+
+// Shown while manually paused (via the pause key)
+// both buttons go through the input queue, so they behave exactly like the keyboard would
+// the graphics settings don't affect the game itself, so they're changed directly
+pub struct Paused {
+    world: Arc<World>,
+    post_settings: Mutable<PostSettings>,
+}
+
+impl Paused {
+    pub fn new(world: Arc<World>) -> Arc<Self> {
+        let post_settings = world.borrow::<PostSettingsView>().unwrap_ext().clone();
+        Arc::new(Self {
+            world,
+            post_settings: Mutable::new(post_settings),
+        })
+    }
+
+    fn update_post_settings(&self, f: impl FnOnce(&mut PostSettings)) {
+        let mut settings = self.post_settings.lock_mut();
+        f(&mut settings);
+        *self.world.borrow::<PostSettingsViewMut>().unwrap_ext() = settings.clone();
+    }
+}
+
+impl Paused {
+    pub fn render(self: &Arc<Self>) -> Dom {
+        let state = self;
+
+        static CONTAINER: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("display", "flex")
+                .style("flex-direction", "column")
+                .style("justify-content", "center")
+                .style("align-items", "center")
+            }
+        });
+        static BG: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("background-color", Color::Darkish.hex_str())
+                .style("opacity", "0.5")
+
+            }
+        });
+
+        static BUTTON_ROW: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("display", "flex")
+                .style("flex-wrap", "wrap")
+                .style("justify-content", "center")
+                .style("align-items", "center")
+                .style("gap", "0.5rem")
+            }
+        });
+
+        static CONTENT: LazyLock<String> = LazyLock::new(|| {
+            class! {
+                .style("width", "50%")
+                .style("height", "50%")
+                .style("background-color", Color::Whiteish.hex_str())
+                .style("display", "flex")
+                .style("border-radius", "8px")
+                .style("gap", "1rem")
+                .style("flex-direction", "column")
+                .style("justify-content", "center")
+                .style("align-items", "center")
+            }
+        });
+
+        html!("div", {
+            .child(html!("div", {
+                .child(html!("div", {
+                    .class([&*FULL_SCREEN, &*BG])
+                }))
+                .child(html!("div", {
+                    .class([&*FULL_SCREEN, &*CONTAINER])
+                    .child(html!("div", {
+                        .class(&*CONTENT)
+                        .children(&mut [
+                            html!("div", {
+                                .class(&*TEXT_SIZE_XLG)
+                                .text("Paused")
+                            }),
+                            Button::new()
+                                .with_text("Resume")
+                                .with_color(ButtonColor::Green)
+                                .with_on_click(clone!(state => move || {
+                                    state.world.run(|mut queue: InputQueueViewMut| {
+                                        queue.insert_always(Input::KeyDown(Key::Pause));
+                                    });
+                                }))
+                                .render(),
+                            Button::new()
+                                .with_text("Restart")
+                                .with_color(ButtonColor::Red)
+                                .with_on_click(clone!(state => move || {
+                                    state.world.run(|mut queue: InputQueueViewMut| {
+                                        queue.insert_always(Input::ResetButton);
+                                    });
+                                }))
+                                .render(),
+                            html!("div", {
+                                .class(&*BUTTON_ROW)
+                                .child(html!("div", {
+                                    .class(&*TEXT_SIZE_MD)
+                                    .text("Graphics:")
+                                }))
+                                .children(PostQuality::ALL.into_iter().map(|quality| {
+                                    html!("div", {
+                                        .child_signal(state.post_settings.signal_ref(clone!(state => move |settings| {
+                                            Some(Button::new()
+                                                .with_text(quality.label())
+                                                .with_size(ButtonSize::Sm)
+                                                .with_color(if settings.quality == quality { ButtonColor::Green } else { ButtonColor::Darkish })
+                                                .with_on_click(clone!(state => move || {
+                                                    state.update_post_settings(|settings| settings.quality = quality);
+                                                }))
+                                                .render()
+                                            )
+                                        })))
+                                    })
+                                }))
+                            }),
+                            html!("div", {
+                                .class(&*BUTTON_ROW)
+                                .children(PostPass::ALL.into_iter().map(|pass| {
+                                    html!("div", {
+                                        .child_signal(state.post_settings.signal_ref(clone!(state => move |settings| {
+                                            Some(Button::new()
+                                                .with_text(pass.label())
+                                                .with_size(ButtonSize::Sm)
+                                                .with_color(if settings.is_enabled(pass) { ButtonColor::Green } else { ButtonColor::Darkish })
+                                                .with_on_click(clone!(state => move || {
+                                                    state.update_post_settings(|settings| settings.toggle(pass));
+                                                }))
+                                                .render()
+                                            )
+                                        })))
+                                    })
+                                }))
+                            }),
+                        ])
+                    }))
+                }))
+            }))
+        })
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{enemy::data::EnemyKind, prelude::*, stats::data::RunStats};
+
+use super::GameUi;
+
+static TABLE: LazyLock<String> = LazyLock::new(|| {
+    class! {
+        .style("display", "grid")
+        .style("grid-template-columns", "auto auto")
+        .style("column-gap", "2rem")
+        .style("row-gap", "0.25rem")
+    }
+});
+
+static VALUE: LazyLock<String> = LazyLock::new(|| {
+    class! {
+        .style("text-align", "right")
+    }
+});
+
+// the full breakdown, for the end-of-run screens
+pub fn render_run_stats(stats: &RunStats) -> Dom {
+    let mut rows = vec![
+        ("Score".to_string(), stats.score().to_string()),
+        (
+            "Time survived".to_string(),
+            format!("{}s", stats.seconds_survived()),
+        ),
+        (
+            "Bombs intercepted".to_string(),
+            stats.total_intercepts().to_string(),
+        ),
+    ];
+
+    for kind in [EnemyKind::Two, EnemyKind::Three, EnemyKind::Four] {
+        rows.push((
+            format!("  by {}", kind_label(kind)),
+            stats
+                .intercepts
+                .get(&kind)
+                .copied()
+                .unwrap_or(0)
+                .to_string(),
+        ));
+    }
+
+    for kind in [
+        EnemyKind::One,
+        EnemyKind::Two,
+        EnemyKind::Three,
+        EnemyKind::Four,
+    ] {
+        rows.push((
+            format!("Shots fired by {}", kind_label(kind)),
+            stats
+                .shots_fired
+                .get(&kind)
+                .copied()
+                .unwrap_or(0)
+                .to_string(),
+        ));
+    }
+
+    rows.push(("Hides".to_string(), stats.hides.to_string()));
+    rows.push(("Jumps".to_string(), stats.jumps.to_string()));
+
+    for (kind, time) in &stats.deaths {
+        rows.push((
+            format!("{} died at", kind_label(*kind)),
+            format!("{}s", (time / 1000.0) as u32),
+        ));
+    }
+
+    html!("div", {
+        .class([&*TABLE, &*TEXT_SIZE_MD, Color::Darkish.class()])
+        .children(rows.into_iter().flat_map(|(label, value)| {
+            [
+                html!("div", {
+                    .text(&label)
+                }),
+                html!("div", {
+                    .class(&*VALUE)
+                    .text(&value)
+                }),
+            ]
+        }))
+    })
+}
+
+fn kind_label(kind: EnemyKind) -> &'static str {
+    match kind {
+        EnemyKind::One => "Enemy 1",
+        EnemyKind::Two => "Enemy 2",
+        EnemyKind::Three => "Enemy 3",
+        EnemyKind::Four => "Enemy 4",
+    }
+}
+
+impl GameUi {
+    // just the headline numbers, for the footer
+    pub fn render_live_stats(self: &Arc<Self>) -> Dom {
+        let state = self;
+        html!("div", {
+            .class([&*TEXT_SIZE_MD, &*TEXT_WEIGHT_BOLD, Color::Whiteish.class()])
+            .text_signal(state.stats.signal_ref(|stats| {
+                format!(
+                    "score: {} | intercepts: {} | time: {}s",
+                    stats.score(),
+                    stats.total_intercepts(),
+                    stats.seconds_survived()
+                )
+            }))
+        })
+    }
+}
@@ -2,6 +2,9 @@ use gloo_timers::future::TimeoutFuture;
 
 use crate::{
     config::CONFIG,
+    controller::{data::Input, queue::InputQueueViewMut},
+    difficulty::data::DifficultyView,
+    level::data::Level,
     prelude::*,
     tick::{PauseTick, PauseTickViewMut},
 };
@@ -13,11 +16,23 @@ use super::GameUiPhase;
 pub struct Welcome {
     world: Arc<World>,
     game_phase: Mutable<Option<GameUiPhase>>,
+    difficulty: Mutable<String>,
+    level: Option<Level>,
 }
 
 impl Welcome {
-    pub fn new(world: Arc<World>, game_phase: Mutable<Option<GameUiPhase>>) -> Arc<Self> {
-        Arc::new(Self { world, game_phase })
+    pub fn new(
+        world: Arc<World>,
+        game_phase: Mutable<Option<GameUiPhase>>,
+        difficulty: Mutable<String>,
+        level: Option<Level>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            world,
+            game_phase,
+            difficulty,
+            level,
+        })
     }
 }
 
@@ -64,6 +79,17 @@ impl Welcome {
                 .style("text-align", "center")
             }
         });
+        // (name, label)
+        let presets: Vec<(String, String)> = state
+            .world
+            .borrow::<DifficultyView>()
+            .unwrap_ext()
+            .presets
+            .presets
+            .iter()
+            .map(|preset| (preset.name.clone(), preset.label.clone()))
+            .collect();
+
         html!("div", {
             .future(clone!(state => async move {
                 state.world.run(|mut pause_tick: PauseTickViewMut| {
@@ -107,6 +133,44 @@ impl Welcome {
                                     .render()
                                 )
                             }),
+                            // a level has its own pacing, so there's nothing to pick
+                            // otherwise this goes through the input queue so that replays pick the same difficulty
+                            match &state.level {
+                                Some(level) => html!("div", {
+                                    .class(&*TEXT_SIZE_LG)
+                                    .text(&format!("Level: {}", level.label))
+                                }),
+                                None => html!("div", {
+                                    .class(&*BUTTON_ROW)
+                                    .child(html!("div", {
+                                        .class(&*TEXT_SIZE_LG)
+                                        .text("Difficulty:")
+                                    }))
+                                    .children(presets.into_iter().map(|(name, label)| {
+                                        html!("div", {
+                                            .child_signal(state.difficulty.signal_ref(clone!(state, name, label => move |selected| {
+                                                Some(Button::new()
+                                                    .with_text(&label)
+                                                    .with_color(if *selected == name { ButtonColor::Green } else { ButtonColor::Darkish })
+                                                    .with_on_click(clone!(state, name => move || {
+                                                        state.world.run(|mut queue: InputQueueViewMut| {
+                                                            queue.insert_always(Input::SelectDifficulty(name.clone()));
+                                                        });
+                                                    }))
+                                                    .render()
+                                                )
+                                            })))
+                                        })
+                                    }))
+                                }),
+                            },
+                            Button::new()
+                                .with_text("High scores")
+                                .with_color(ButtonColor::Blue)
+                                .with_on_click(clone!(state => move || {
+                                    state.game_phase.set_neq(Some(GameUiPhase::HighScores));
+                                }))
+                                .render(),
                             Button::new()
                                 .with_text("What's this all about?")
                                 .with_color(ButtonColor::Blue)
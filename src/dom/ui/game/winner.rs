@@ -1,26 +1,37 @@
 use gloo_timers::future::TimeoutFuture;
 
 use crate::{
+    bomber::data::BomberView,
     config::CONFIG,
+    controller::{data::Input, queue::InputQueueViewMut, recording::InputRecorderView},
     prelude::*,
+    stats::data::RunStats,
     tick::{PauseTick, PauseTickViewMut},
 };
 
 use crate::dom::atoms::buttons::*;
 
-use super::GameUiPhase;
+use super::{stats::render_run_stats, GameUiPhase};
 
-pub struct Winner {}
+pub struct Winner {
+    pub world: Arc<World>,
+    pub stats: Mutable<RunStats>,
+}
 
 impl Winner {
-    pub fn new() -> Arc<Self> {
-        Arc::new(Self {})
+    pub fn new(world: Arc<World>, stats: Mutable<RunStats>) -> Arc<Self> {
+        Arc::new(Self { world, stats })
     }
 }
 
 impl Winner {
     pub fn render(self: &Arc<Self>) -> Dom {
         let state = self;
+        let bombs_survived = state
+            .world
+            .borrow::<BomberView>()
+            .unwrap_ext()
+            .bombs_dropped;
 
         static CONTAINER: LazyLock<String> = LazyLock::new(|| {
             class! {
@@ -65,6 +76,18 @@ impl Winner {
                             html!("div", {
                                 .text("Congratulations! You've won the game!")
                             }),
+                            html!("div", {
+                                // skipped entirely if you chose peace from the start
+                                .child_signal(state.stats.signal_ref(move |stats| {
+                                    (stats.time_survived > 0.0).then(|| html!("div", {
+                                        .child(html!("div", {
+                                            .class(&*TEXT_SIZE_LG)
+                                            .text(&format!("You survived {} bombs", bombs_survived))
+                                        }))
+                                        .child(render_run_stats(stats))
+                                    }))
+                                }))
+                            }),
                             html!("div", {
                                 .text("Israel is recognized internationally as the ancestral homeland of the Jewish people")
                             }),
@@ -77,12 +100,30 @@ impl Winner {
                             html!("div", {
                                 .text("That wasn't so hard, now was it?")
                             }),
+                            Button::new()
+                                .with_text("Play again")
+                                .with_color(ButtonColor::Green)
+                                .with_on_click(clone!(state => move || {
+                                    state.world.run(|mut queue: InputQueueViewMut| {
+                                        queue.insert_always(Input::ResetButton);
+                                    });
+                                }))
+                                .render(),
                             Button::new()
                                 .with_text("What's this all about?")
                                 .with_color(ButtonColor::Blue)
                                 .with_on_click(clone!(state => move || {
                                     web_sys::window().unwrap_ext().location().set_href(CONFIG.html_url("about.html").as_str()).unwrap();
                                 }))
+                                .render(),
+                            Button::new()
+                                .with_text("Download replay")
+                                .with_color(ButtonColor::Darkish)
+                                .with_on_click(clone!(state => move || {
+                                    if let Err(err) = state.world.borrow::<InputRecorderView>().unwrap_ext().download() {
+                                        log::warn!("couldn't download replay: {}", err);
+                                    }
+                                }))
                                 .render()
                         ])
                     }))
@@ -6,6 +6,7 @@ use crate::{
     enemy::{
         controller::data::{ActiveEnemyController, HorizontalMovement},
         data::{Enemy, EnemyFourPhase, EnemyOnePhase, EnemyThreePhase, EnemyTwoPhase},
+        health::data::EnemyHealth,
         launcher::data::{EnemyLauncher, LauncherSide},
         physics::data::EnemyDirection,
     },
@@ -21,10 +22,12 @@ pub fn enemy_animation_sys(
     mut animations: ViewMut<Animation>,
     mut launchers: ViewMut<EnemyLauncher>,
     mut projectile_spawner: ProjectileSpawnerViewMut,
-    tick: BeginTickView,
+    healths: View<EnemyHealth>,
+    tick: UpdateTickView,
 ) {
-    for (enemy, animation) in (&mut enemies, &mut animations).iter() {
+    for (enemy, animation, health) in (&mut enemies, &mut animations, &healths).iter() {
         let mut reset_animation = false;
+        let is_hurt = health.is_hurt();
 
         match enemy {
             Enemy::One {
@@ -42,6 +45,10 @@ pub fn enemy_animation_sys(
                     new_phase = EnemyOnePhase::Blast;
                 }
 
+                if is_hurt {
+                    new_phase = EnemyOnePhase::Hurt;
+                }
+
                 if old_phase != new_phase {
                     *phase = new_phase;
                     reset_animation = true;
@@ -63,6 +70,10 @@ pub fn enemy_animation_sys(
                     new_phase = EnemyTwoPhase::Shooting;
                 };
 
+                if is_hurt {
+                    new_phase = EnemyTwoPhase::Hurt;
+                }
+
                 if old_phase != new_phase {
                     *phase = new_phase;
                     reset_animation = true;
@@ -84,6 +95,10 @@ pub fn enemy_animation_sys(
                     new_phase = EnemyThreePhase::Shoot;
                 };
 
+                if is_hurt {
+                    new_phase = EnemyThreePhase::Hurt;
+                }
+
                 if old_phase != new_phase {
                     *phase = new_phase;
                     reset_animation = true;
@@ -100,6 +115,10 @@ pub fn enemy_animation_sys(
                     new_phase = EnemyFourPhase::Shoot;
                 };
 
+                if is_hurt {
+                    new_phase = EnemyFourPhase::Hurt;
+                }
+
                 if old_phase != new_phase {
                     *phase = new_phase;
                     reset_animation = true;
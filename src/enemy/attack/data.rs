@@ -1,4 +1,6 @@
-use crate::prelude::*;
+use serde::Deserialize;
+
+use crate::{enemy::data::EnemyKind, prelude::*};
 
 #[derive(Debug)]
 pub struct AttackOne {}
@@ -34,3 +36,176 @@ impl AttackFour {
         Self {}
     }
 }
+
+// How an enemy's attack is limited, independent of how long its animation is
+// all durations are in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttackStats {
+    // minimum time between two attacks
+    pub cooldown: f64,
+    // attacks before a reload is needed
+    pub magazine: u32,
+    // starts automatically when the magazine is empty
+    pub reload: f64,
+    // if set, the attack key has to be held this long, and it fires on release
+    pub charge: Option<f64>,
+}
+
+pub type WeaponStatsView<'a> = UniqueView<'a, WeaponStats>;
+
+// The tuning for every kind of enemy (see media/data/weapons.json)
+#[derive(Component, Unique, Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponStats {
+    one: AttackStats,
+    two: AttackStats,
+    three: AttackStats,
+    four: AttackStats,
+}
+
+impl WeaponStats {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let weapons: Self = serde_json::from_str(json)?;
+
+        for kind in [
+            EnemyKind::One,
+            EnemyKind::Two,
+            EnemyKind::Three,
+            EnemyKind::Four,
+        ] {
+            let stats = weapons.get(kind);
+            if stats.magazine == 0 {
+                return Err(anyhow!(
+                    "{kind:?} would never be able to fire, the magazine is empty"
+                ));
+            }
+            if stats.cooldown < 0.0 || stats.reload < 0.0 || stats.charge.unwrap_or(0.0) < 0.0 {
+                return Err(anyhow!("{kind:?} has a negative duration"));
+            }
+        }
+
+        Ok(weapons)
+    }
+
+    pub fn get(&self, kind: EnemyKind) -> AttackStats {
+        match kind {
+            EnemyKind::One => self.one,
+            EnemyKind::Two => self.two,
+            EnemyKind::Three => self.three,
+            EnemyKind::Four => self.four,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Weapon {
+    pub stats: AttackStats,
+    pub ammo: u32,
+    pub cooldown_remaining: f64,
+    pub reload_remaining: f64,
+    // how long the attack key has been held, if we're charging
+    pub charging: Option<f64>,
+    // the last status the ui was told about
+    pub reported: Option<WeaponStatus>,
+}
+
+impl Weapon {
+    pub fn new(stats: AttackStats) -> Self {
+        Self {
+            stats,
+            ammo: stats.magazine,
+            cooldown_remaining: 0.0,
+            reload_remaining: 0.0,
+            charging: None,
+            reported: None,
+        }
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload_remaining > 0.0
+    }
+
+    pub fn can_fire(&self) -> bool {
+        self.ammo > 0 && self.cooldown_remaining <= 0.0 && !self.is_reloading()
+    }
+
+    // returns true if the attack should start now
+    pub fn trigger_pressed(&mut self) -> bool {
+        // key repeat while holding shouldn't restart the charge
+        if !self.can_fire() || self.charging.is_some() {
+            return false;
+        }
+
+        match self.stats.charge {
+            None => {
+                self.fire();
+                true
+            }
+            Some(_) => {
+                self.charging = Some(0.0);
+                false
+            }
+        }
+    }
+
+    // returns true if the attack should start now
+    pub fn trigger_released(&mut self) -> bool {
+        match (self.charging.take(), self.stats.charge) {
+            (Some(charged), Some(charge)) if charged >= charge && self.can_fire() => {
+                self.fire();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn cancel_charge(&mut self) {
+        self.charging = None;
+    }
+
+    fn fire(&mut self) {
+        self.ammo -= 1;
+        self.cooldown_remaining = self.stats.cooldown;
+        if self.ammo == 0 {
+            self.reload_remaining = self.stats.reload;
+        }
+    }
+
+    pub fn update(&mut self, delta: f64) {
+        self.cooldown_remaining = (self.cooldown_remaining - delta).max(0.0);
+
+        if self.is_reloading() {
+            self.reload_remaining = (self.reload_remaining - delta).max(0.0);
+            if !self.is_reloading() {
+                self.ammo = self.stats.magazine;
+            }
+        }
+
+        if let Some(charging) = &mut self.charging {
+            *charging += delta;
+        }
+    }
+
+    pub fn status(&self) -> WeaponStatus {
+        WeaponStatus {
+            ammo: self.ammo,
+            magazine: self.stats.magazine,
+            reloading: self.is_reloading(),
+            charged: match (self.charging, self.stats.charge) {
+                (Some(charged), Some(charge)) => Some(charged >= charge),
+                _ => None,
+            },
+        }
+    }
+}
+
+// just what the ui shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeaponStatus {
+    pub ammo: u32,
+    pub magazine: u32,
+    pub reloading: bool,
+    // None if not charging, otherwise whether it's ready to release
+    pub charged: Option<bool>,
+}
@@ -0,0 +1,27 @@
+use crate::{
+    dom::events::{UiEvent, UiEventQueue},
+    enemy::data::Enemy,
+    prelude::*,
+    tick::UpdateTickView,
+};
+
+// ticks down cooldowns and reloads for every enemy, selected or not
+// and lets the ui know whenever what it shows has changed
+pub fn enemy_weapon_sys(
+    mut enemies: ViewMut<Enemy>,
+    mut ui_events: UniqueViewMut<UiEventQueue>,
+    tick: UpdateTickView,
+) {
+    for enemy in (&mut enemies).iter() {
+        let kind = enemy.kind();
+        let weapon = enemy.controller_mut().weapon_mut();
+
+        weapon.update(tick.delta);
+
+        let status = weapon.status();
+        if weapon.reported != Some(status) {
+            weapon.reported = Some(status);
+            ui_events.push(UiEvent::WeaponStatus(kind, status));
+        }
+    }
+}
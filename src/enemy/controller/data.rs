@@ -2,11 +2,13 @@ use crate::{
     audio::AudioEventQueue,
     controller::data::{Input, Key},
     enemy::{
-        attack::data::{AttackFour, AttackOne, AttackThree, AttackTwo},
+        attack::data::{AttackFour, AttackOne, AttackStats, AttackThree, AttackTwo, Weapon},
+        data::EnemyKind,
         launcher::data::LauncherSide,
         physics::data::EnemyDirection,
     },
     prelude::*,
+    stats::data::RunStats,
 };
 
 use super::process::EnemyControllerInput;
@@ -27,6 +29,9 @@ pub trait EnemyController: EnemyControllerProcessInput {
 
     fn stop_attack(&mut self);
 
+    fn weapon(&self) -> &Weapon;
+    fn weapon_mut(&mut self) -> &mut Weapon;
+
     fn clear(&mut self);
 
     fn apply_update(&mut self, update: ControllerUpdate) {
@@ -45,7 +50,12 @@ pub trait EnemyController: EnemyControllerProcessInput {
 }
 
 pub trait EnemyControllerProcessInput {
-    fn process_input(&mut self, input: EnemyControllerInput, audio_events: &mut AudioEventQueue);
+    fn process_input(
+        &mut self,
+        input: EnemyControllerInput,
+        audio_events: &mut AudioEventQueue,
+        stats: &mut RunStats,
+    );
 }
 
 #[derive(Default)]
@@ -60,15 +70,17 @@ pub struct EnemyControllerOne {
     pub horizontal_movement: Option<HorizontalMovement>,
     pub hiding: Option<Hiding>,
     pub attack: Option<AttackOne>,
+    pub weapon: Weapon,
     pub jump: Option<Jump>,
     pub direction: EnemyDirection,
 }
 impl EnemyControllerOne {
-    pub fn new(direction: EnemyDirection) -> Self {
+    pub fn new(direction: EnemyDirection, attack_stats: AttackStats) -> Self {
         Self {
             horizontal_movement: None,
             hiding: None,
             attack: None,
+            weapon: Weapon::new(attack_stats),
             jump: None,
             direction,
         }
@@ -104,10 +116,19 @@ impl EnemyController for EnemyControllerOne {
         self.attack = None;
     }
 
+    fn weapon(&self) -> &Weapon {
+        &self.weapon
+    }
+
+    fn weapon_mut(&mut self) -> &mut Weapon {
+        &mut self.weapon
+    }
+
     fn clear(&mut self) {
         self.horizontal_movement = None;
         self.hiding = None;
         self.attack = None;
+        self.weapon.cancel_charge();
         self.jump = None;
     }
 }
@@ -117,16 +138,18 @@ pub struct EnemyControllerTwo {
     pub horizontal_movement: Option<HorizontalMovement>,
     pub hiding: Option<Hiding>,
     pub attack: Option<AttackTwo>,
+    pub weapon: Weapon,
     pub jump: Option<Jump>,
     pub direction: EnemyDirection,
 }
 
 impl EnemyControllerTwo {
-    pub fn new(direction: EnemyDirection) -> Self {
+    pub fn new(direction: EnemyDirection, attack_stats: AttackStats) -> Self {
         Self {
             horizontal_movement: None,
             hiding: None,
             attack: None,
+            weapon: Weapon::new(attack_stats),
             jump: None,
             direction,
         }
@@ -161,10 +184,20 @@ impl EnemyController for EnemyControllerTwo {
     fn stop_attack(&mut self) {
         self.attack = None;
     }
+
+    fn weapon(&self) -> &Weapon {
+        &self.weapon
+    }
+
+    fn weapon_mut(&mut self) -> &mut Weapon {
+        &mut self.weapon
+    }
+
     fn clear(&mut self) {
         self.horizontal_movement = None;
         self.hiding = None;
         self.attack = None;
+        self.weapon.cancel_charge();
         self.jump = None;
     }
 }
@@ -174,16 +207,18 @@ pub struct EnemyControllerThree {
     pub horizontal_movement: Option<HorizontalMovement>,
     pub hiding: Option<Hiding>,
     pub attack: Option<AttackThree>,
+    pub weapon: Weapon,
     pub jump: Option<Jump>,
     pub direction: EnemyDirection,
 }
 
 impl EnemyControllerThree {
-    pub fn new(direction: EnemyDirection) -> Self {
+    pub fn new(direction: EnemyDirection, attack_stats: AttackStats) -> Self {
         Self {
             horizontal_movement: None,
             hiding: None,
             attack: None,
+            weapon: Weapon::new(attack_stats),
             jump: None,
             direction,
         }
@@ -217,10 +252,20 @@ impl EnemyController for EnemyControllerThree {
     fn stop_attack(&mut self) {
         self.attack = None;
     }
+
+    fn weapon(&self) -> &Weapon {
+        &self.weapon
+    }
+
+    fn weapon_mut(&mut self) -> &mut Weapon {
+        &mut self.weapon
+    }
+
     fn clear(&mut self) {
         self.horizontal_movement = None;
         self.hiding = None;
         self.attack = None;
+        self.weapon.cancel_charge();
         self.jump = None;
     }
 }
@@ -230,16 +275,18 @@ pub struct EnemyControllerFour {
     pub side: LauncherSide,
     pub hiding: Option<Hiding>,
     pub attack: Option<AttackFour>,
+    pub weapon: Weapon,
     pub jump: Option<Jump>,
     pub direction: EnemyDirection,
 }
 
 impl EnemyControllerFour {
-    pub fn new(direction: EnemyDirection) -> Self {
+    pub fn new(direction: EnemyDirection, attack_stats: AttackStats) -> Self {
         Self {
             side: LauncherSide::Left,
             hiding: None,
             attack: None,
+            weapon: Weapon::new(attack_stats),
             jump: None,
             direction,
         }
@@ -273,9 +320,19 @@ impl EnemyController for EnemyControllerFour {
     fn stop_attack(&mut self) {
         self.attack = None;
     }
+
+    fn weapon(&self) -> &Weapon {
+        &self.weapon
+    }
+
+    fn weapon_mut(&mut self) -> &mut Weapon {
+        &mut self.weapon
+    }
+
     fn clear(&mut self) {
         self.hiding = None;
         self.attack = None;
+        self.weapon.cancel_charge();
         self.jump = None;
     }
 }
@@ -3,10 +3,12 @@ use crate::{
     audio::{AudioEvent, AudioEventQueue},
     controller::data::{Input, Key},
     enemy::{
-        attack::data::{AttackFour, AttackOne, AttackThree, AttackTwo},
+        attack::data::{AttackFour, AttackOne, AttackThree, AttackTwo, Weapon},
+        data::EnemyKind,
         launcher::data::LauncherSide,
     },
     prelude::*,
+    stats::data::RunStats,
 };
 
 use super::data::*;
@@ -26,13 +28,14 @@ impl EnemyControllerProcessInput for EnemyControllerOne {
             position,
         }: EnemyControllerInput,
         audio_events: &mut AudioEventQueue,
+        stats: &mut RunStats,
     ) {
         if let Some(update) = process_horizontal(self.horizontal_movement, input) {
             self.horizontal_movement = update;
         }
 
         if self.jump.is_none() {
-            if process_hiding(self.hiding, input, audio_events) {
+            if process_hiding(self.hiding, input, audio_events, stats) {
                 self.hiding = Some(Hiding::Down {
                     start_y: position.y,
                 });
@@ -40,12 +43,13 @@ impl EnemyControllerProcessInput for EnemyControllerOne {
         }
 
         if self.hiding.is_none() {
-            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events) {
+            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events, stats) {
                 self.jump = Some(jump);
             }
 
-            if process_attack(&self.attack, input) {
+            if process_attack(&self.attack, &mut self.weapon, input) {
                 audio_events.push(AudioEvent::WeaponExplode);
+                stats.record_shot(EnemyKind::One);
                 self.attack = Some(AttackOne::new());
             }
         }
@@ -61,13 +65,14 @@ impl EnemyControllerProcessInput for EnemyControllerTwo {
             position,
         }: EnemyControllerInput,
         audio_events: &mut AudioEventQueue,
+        stats: &mut RunStats,
     ) {
         if let Some(update) = process_horizontal(self.horizontal_movement, input) {
             self.horizontal_movement = update;
         }
 
         if self.jump.is_none() {
-            if process_hiding(self.hiding, input, audio_events) {
+            if process_hiding(self.hiding, input, audio_events, stats) {
                 self.hiding = Some(Hiding::Down {
                     start_y: position.y,
                 });
@@ -75,12 +80,13 @@ impl EnemyControllerProcessInput for EnemyControllerTwo {
         }
 
         if self.hiding.is_none() {
-            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events) {
+            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events, stats) {
                 self.jump = Some(jump);
             }
 
-            if process_attack(&self.attack, input) {
+            if process_attack(&self.attack, &mut self.weapon, input) {
                 audio_events.push(AudioEvent::WeaponBullet);
+                stats.record_shot(EnemyKind::Two);
                 self.attack = Some(AttackTwo::new());
             }
         }
@@ -95,13 +101,14 @@ impl EnemyControllerProcessInput for EnemyControllerThree {
             position,
         }: EnemyControllerInput,
         audio_events: &mut AudioEventQueue,
+        stats: &mut RunStats,
     ) {
         if let Some(update) = process_horizontal(self.horizontal_movement, input) {
             self.horizontal_movement = update;
         }
 
         if self.jump.is_none() {
-            if process_hiding(self.hiding, input, audio_events) {
+            if process_hiding(self.hiding, input, audio_events, stats) {
                 self.hiding = Some(Hiding::Down {
                     start_y: position.y,
                 });
@@ -109,12 +116,13 @@ impl EnemyControllerProcessInput for EnemyControllerThree {
         }
 
         if self.hiding.is_none() {
-            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events) {
+            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events, stats) {
                 self.jump = Some(jump);
             }
 
-            if process_attack(&self.attack, input) {
+            if process_attack(&self.attack, &mut self.weapon, input) {
                 audio_events.push(AudioEvent::WeaponRpg);
+                stats.record_shot(EnemyKind::Three);
                 self.attack = Some(AttackThree::new());
             }
         }
@@ -129,6 +137,7 @@ impl EnemyControllerProcessInput for EnemyControllerFour {
             position,
         }: EnemyControllerInput,
         audio_events: &mut AudioEventQueue,
+        stats: &mut RunStats,
     ) {
         match input {
             Input::KeyDown(key) => match key {
@@ -144,18 +153,19 @@ impl EnemyControllerProcessInput for EnemyControllerFour {
         }
 
         if self.jump.is_none() {
-            // if process_hiding(self.hiding, input, audio_events) {
+            // if process_hiding(self.hiding, input, audio_events, stats) {
             //     self.hiding = Some(Hiding::Down{start_y: position.y});
             // }
         }
 
         if self.hiding.is_none() {
-            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events) {
+            if let Some(jump) = process_jump(&self.jump, input, position.y, audio_events, stats) {
                 self.jump = Some(jump);
             }
 
-            if process_attack(&self.attack, input) {
+            if process_attack(&self.attack, &mut self.weapon, input) {
                 audio_events.push(AudioEvent::WeaponLauncher);
+                stats.record_shot(EnemyKind::Four);
                 self.attack = Some(AttackFour::new());
             }
         }
@@ -188,6 +198,7 @@ fn process_jump(
     input: &Input,
     start_y: f32,
     audio_events: &mut AudioEventQueue,
+    stats: &mut RunStats,
 ) -> Option<Jump> {
     let jump = match (prev, input) {
         (None, Input::KeyDown(Key::Up)) => Some(Jump::new(start_y)),
@@ -199,11 +210,17 @@ fn process_jump(
 
     if jump.is_some() {
         audio_events.push(AudioEvent::MoveJump);
+        stats.record_jump();
     }
 
     jump
 }
-fn process_hiding(prev: Option<Hiding>, input: &Input, audio_events: &mut AudioEventQueue) -> bool {
+fn process_hiding(
+    prev: Option<Hiding>,
+    input: &Input,
+    audio_events: &mut AudioEventQueue,
+    stats: &mut RunStats,
+) -> bool {
     let hiding = match (prev, input) {
         (None, Input::KeyDown(Key::Down)) => true,
         _ => false,
@@ -211,14 +228,17 @@ fn process_hiding(prev: Option<Hiding>, input: &Input, audio_events: &mut AudioE
 
     if hiding {
         audio_events.push(AudioEvent::MoveDuck);
+        stats.record_hide();
     }
 
     hiding
 }
 
-fn process_attack<T>(prev: &Option<T>, input: &Input) -> bool {
+// the weapon decides whether it's ready (cooldown, ammo, reload, charge)
+fn process_attack<T>(prev: &Option<T>, weapon: &mut Weapon, input: &Input) -> bool {
     match (prev, input) {
-        (None, Input::KeyDown(Key::Space)) => true,
+        (None, Input::KeyDown(Key::Space)) => weapon.trigger_pressed(),
+        (None, Input::KeyUp(Key::Space)) => weapon.trigger_released(),
         _ => false,
     }
 }
@@ -2,13 +2,15 @@ use awsm_web::webgl::{
     AttributeOptions, DataType, NameOrLoc, PixelFormat, SimpleTextureOptions, TextureTarget,
     TextureWrapMode, VertexArray, WebGl2Renderer, WebGlTextureSource,
 };
+use serde::{Deserialize, Serialize};
 use web_sys::HtmlImageElement;
 
 use crate::{
+    atlas::data::TextureAtlas,
     config::CONFIG,
     media::{Media, SpriteSheetMediaInfo},
     prelude::*,
-    renderer::{shaders::ShaderProgram, Renderer},
+    renderer::shaders::ShaderProgram,
     spritesheet::{self, SpriteSheet},
 };
 
@@ -121,7 +123,7 @@ impl Enemy {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum EnemyKind {
     One,
     Two,
@@ -190,43 +192,43 @@ pub struct EnemySpriteSheets {
 }
 
 impl EnemySpriteSheets {
-    pub fn new(renderer: &mut Renderer, media: &Media) -> Result<Self> {
+    pub fn new(atlas: &TextureAtlas, media: &Media) -> Result<Self> {
         let one = match &media.terrorists.one {
             None => None,
             Some(t) => Some(EnemySpriteSheetsOne {
-                blast: SpriteSheet::new(renderer, &t.blast_img, &t.blast_info)?,
-                hurt: SpriteSheet::new(renderer, &t.hurt_img, &t.hurt_info)?,
-                idle: SpriteSheet::new(renderer, &t.idle_img, &t.idle_info)?,
-                walk: SpriteSheet::new(renderer, &t.walk_img, &t.walk_info)?,
+                blast: SpriteSheet::new(atlas, &t.blast_img, &t.blast_info)?,
+                hurt: SpriteSheet::new(atlas, &t.hurt_img, &t.hurt_info)?,
+                idle: SpriteSheet::new(atlas, &t.idle_img, &t.idle_info)?,
+                walk: SpriteSheet::new(atlas, &t.walk_img, &t.walk_info)?,
             }),
         };
 
         let two = match &media.terrorists.two {
             None => None,
             Some(t) => Some(EnemySpriteSheetsTwo {
-                hurt: SpriteSheet::new(renderer, &t.hurt_img, &t.hurt_info)?,
-                idle: SpriteSheet::new(renderer, &t.idle_img, &t.idle_info)?,
-                shooting: SpriteSheet::new(renderer, &t.shooting_img, &t.shooting_info)?,
-                walk: SpriteSheet::new(renderer, &t.walk_img, &t.walk_info)?,
+                hurt: SpriteSheet::new(atlas, &t.hurt_img, &t.hurt_info)?,
+                idle: SpriteSheet::new(atlas, &t.idle_img, &t.idle_info)?,
+                shooting: SpriteSheet::new(atlas, &t.shooting_img, &t.shooting_info)?,
+                walk: SpriteSheet::new(atlas, &t.walk_img, &t.walk_info)?,
             }),
         };
 
         let three = match &media.terrorists.three {
             None => None,
             Some(t) => Some(EnemySpriteSheetsThree {
-                hurt: SpriteSheet::new(renderer, &t.hurt_img, &t.hurt_info)?,
-                idle: SpriteSheet::new(renderer, &t.idle_img, &t.idle_info)?,
-                shoot: SpriteSheet::new(renderer, &t.shoot_img, &t.shoot_info)?,
-                walk: SpriteSheet::new(renderer, &t.walk_img, &t.walk_info)?,
+                hurt: SpriteSheet::new(atlas, &t.hurt_img, &t.hurt_info)?,
+                idle: SpriteSheet::new(atlas, &t.idle_img, &t.idle_info)?,
+                shoot: SpriteSheet::new(atlas, &t.shoot_img, &t.shoot_info)?,
+                walk: SpriteSheet::new(atlas, &t.walk_img, &t.walk_info)?,
             }),
         };
 
         let four = match &media.terrorists.four {
             None => None,
             Some(t) => Some(EnemySpriteSheetsFour {
-                hurt: SpriteSheet::new(renderer, &t.hurt_img, &t.hurt_info)?,
-                idle: SpriteSheet::new(renderer, &t.idle_img, &t.idle_info)?,
-                shoot: SpriteSheet::new(renderer, &t.shoot_img, &t.shoot_info)?,
+                hurt: SpriteSheet::new(atlas, &t.hurt_img, &t.hurt_info)?,
+                idle: SpriteSheet::new(atlas, &t.idle_img, &t.idle_info)?,
+                shoot: SpriteSheet::new(atlas, &t.shoot_img, &t.shoot_info)?,
             }),
         };
 
@@ -2,8 +2,9 @@ use crate::{
     audio::{AudioEvent, AudioEventQueue},
     delete::data::MarkForDeletion,
     dispatch_select_event,
-    dom::DomView,
+    dom::events::{UiEvent, UiEventQueue},
     prelude::*,
+    stats::data::RunStatsViewMut,
     tick::PauseTick,
 };
 
@@ -21,16 +22,14 @@ pub fn enemy_destroy_event_sys(
     mut deletions: ViewMut<MarkForDeletion>,
     mut pause_tick: UniqueViewMut<PauseTick>,
     mut audio_events: UniqueViewMut<AudioEventQueue>,
-    dom: DomView,
+    mut ui_events: UniqueViewMut<UiEventQueue>,
+    mut stats: RunStatsViewMut,
 ) {
     let mut did_destroy = false;
     for (id, (_, enemy)) in (&mut destroy_events, &mut enemies).iter().with_id() {
         deletions.add_component_unchecked(id, MarkForDeletion {});
-        dom.ui
-            .game_ui_unchecked()
-            .destroyed_kinds
-            .lock_mut()
-            .insert(enemy.kind());
+        ui_events.push(UiEvent::EnemyDestroyed(enemy.kind()));
+        stats.record_death(enemy.kind());
         did_destroy = true;
     }
 
@@ -0,0 +1,76 @@
+use crate::prelude::*;
+
+use crate::enemy::data::EnemyKind;
+
+#[derive(Component, Debug)]
+pub struct EnemyHealth {
+    pub current: f32,
+    pub max: f32,
+    // counts down while in the Hurt phase
+    pub hurt_remaining: f64,
+    // counts down while damage is ignored, a bit longer than the hurt phase itself
+    pub invulnerable_remaining: f64,
+    // horizontal velocity, decays back to zero
+    pub knockback: f32,
+}
+
+impl EnemyHealth {
+    // all in milliseconds
+    pub const HURT_DURATION: f64 = 400.0;
+    pub const INVULNERABLE_DURATION: f64 = 800.0;
+    // per millisecond, in normalized layout units
+    pub const KNOCKBACK_SPEED: f32 = 0.001;
+    // how much of the knockback is left after each millisecond
+    pub const KNOCKBACK_DECAY: f32 = 0.99;
+
+    pub fn new(kind: EnemyKind) -> Self {
+        let max = match kind {
+            EnemyKind::One => 80.0,
+            EnemyKind::Two => 100.0,
+            EnemyKind::Three => 120.0,
+            EnemyKind::Four => 150.0,
+        };
+
+        Self {
+            current: max,
+            max,
+            hurt_remaining: 0.0,
+            invulnerable_remaining: 0.0,
+            knockback: 0.0,
+        }
+    }
+
+    pub fn is_hurt(&self) -> bool {
+        self.hurt_remaining > 0.0
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_remaining > 0.0
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    // returns false if the damage was ignored
+    // direction is which way to get knocked back, -1.0 for left and 1.0 for right
+    pub fn take_damage(&mut self, damage: f32, direction: f32) -> bool {
+        if self.is_invulnerable() || self.is_dead() {
+            return false;
+        }
+
+        self.current = (self.current - damage).max(0.0);
+        self.hurt_remaining = Self::HURT_DURATION;
+        self.invulnerable_remaining = Self::INVULNERABLE_DURATION;
+        self.knockback = Self::KNOCKBACK_SPEED * direction;
+
+        true
+    }
+}
+
+// added to an enemy when something hits it, consumed by enemy_health_sys
+#[derive(Component, Debug)]
+pub struct EnemyDamageEvent {
+    pub damage: f32,
+    pub direction: f32,
+}
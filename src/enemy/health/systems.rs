@@ -0,0 +1,73 @@
+use crate::{
+    collision::data::Collider,
+    enemy::{controller::data::EnemyController, data::Enemy},
+    explosion::data::ExplosionSpawnerViewMut,
+    hit::data::HitEventQueueView,
+    layout::data::LayoutPosition,
+    prelude::*,
+    projectiles::data::Projectile,
+    tick::UpdateTickView,
+};
+
+use super::data::{EnemyDamageEvent, EnemyHealth};
+
+// Applies damage, ticks down the hurt/invulnerable timers, and moves enemies that were knocked back
+// at zero HP the enemy explodes, and from there on it's the usual destroy flow
+pub fn enemy_health_sys(
+    mut healths: ViewMut<EnemyHealth>,
+    mut damage_events: ViewMut<EnemyDamageEvent>,
+    mut enemies: ViewMut<Enemy>,
+    mut positions: ViewMut<LayoutPosition>,
+    mut explosion_spawner: ExplosionSpawnerViewMut,
+    tick: UpdateTickView,
+) {
+    for (id, (health, event, enemy)) in (&mut healths, &damage_events, &mut enemies)
+        .iter()
+        .with_id()
+    {
+        if health.take_damage(event.damage, event.direction) {
+            // getting hit interrupts whatever attack was in progress
+            enemy.controller_mut().stop_attack();
+
+            if health.is_dead() {
+                explosion_spawner.to_spawn.insert(id);
+            }
+        }
+    }
+
+    damage_events.clear();
+
+    for (health, pos) in (&mut healths, &mut positions).iter() {
+        health.hurt_remaining = (health.hurt_remaining - tick.delta).max(0.0);
+        health.invulnerable_remaining = (health.invulnerable_remaining - tick.delta).max(0.0);
+
+        if health.knockback != 0.0 {
+            pos.x = (pos.x + health.knockback * tick.delta as f32).clamp(0.0, 1.0);
+            health.knockback *= EnemyHealth::KNOCKBACK_DECAY.powf(tick.delta as f32);
+            if health.knockback.abs() < 0.00001 {
+                health.knockback = 0.0;
+            }
+        }
+    }
+}
+
+// Turns hits on enemies into damage, applied at the start of the next update (see enemy_health_sys)
+// knockback pushes the enemy away from where it was hit
+pub fn enemy_hit_sys(
+    hit_events: HitEventQueueView,
+    projectiles: View<Projectile>,
+    colliders: View<Collider>,
+    mut damage_events: ViewMut<EnemyDamageEvent>,
+) {
+    for hit in hit_events.iter().filter(|hit| hit.victim_kind.is_enemy()) {
+        let damage = projectiles
+            .get(hit.attacker)
+            .map(|projectile| projectile.damage())
+            .unwrap_or_default();
+        let direction = match colliders.get(hit.victim) {
+            Ok(collider) if collider.center().x < hit.contact.x => -1.0,
+            _ => 1.0,
+        };
+        damage_events.add_component_unchecked(hit.victim, EnemyDamageEvent { damage, direction });
+    }
+}
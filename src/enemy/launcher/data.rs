@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{prelude::*, renderer::Renderer, spritesheet::SpriteSheet};
 
 #[derive(Component)]
@@ -17,7 +19,7 @@ impl EnemyLauncher {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum LauncherSide {
     Left,
     Right,
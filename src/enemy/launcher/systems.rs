@@ -26,7 +26,7 @@ pub fn launcher_animation_sys(
     mut animations: ViewMut<Animation>,
     mut projectile_spawner: ProjectileSpawnerViewMut,
     enemies: View<Enemy>,
-    tick: BeginTickView,
+    tick: UpdateTickView,
 ) {
     for (launcher, animation) in (&mut launchers, &mut animations).iter() {
         if launcher.launching {
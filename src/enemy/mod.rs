@@ -5,6 +5,7 @@ pub mod data;
 pub mod destroy;
 pub mod effects;
 pub mod events;
+pub mod health;
 pub mod launcher;
 pub mod physics;
 pub mod render;
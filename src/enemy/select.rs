@@ -1,4 +1,9 @@
-use crate::{dom::DomView, layout::data::LayoutPosition, prelude::*, tick::BeginTickView};
+use crate::{
+    dom::events::{UiEvent, UiEventQueue},
+    layout::data::LayoutPosition,
+    prelude::*,
+    tick::UpdateTickView,
+};
 
 use super::{controller::data::ActiveEnemyController, data::Enemy, events::EnemySelectEvent};
 
@@ -7,8 +12,8 @@ pub fn enemy_select_event_sys(
     mut select_events: ViewMut<EnemySelectEvent>,
     mut enemies: ViewMut<Enemy>,
     positions: View<LayoutPosition>,
-    dom: DomView,
-    tick: BeginTickView,
+    mut ui_events: UniqueViewMut<UiEventQueue>,
+    tick: UpdateTickView,
 ) {
     let select_enemy_with_id = (&mut select_events, &mut enemies).iter().with_id().last();
 
@@ -18,9 +23,6 @@ pub fn enemy_select_event_sys(
 
         enemy.controller_mut().clear();
         active_controllers.add_component_unchecked(id, ActiveEnemyController {});
-        dom.ui
-            .game_ui_unchecked()
-            .selected_kind
-            .set_neq(Some(enemy.kind()));
+        ui_events.push(UiEvent::EnemySelected(enemy.kind()));
     }
 }
@@ -2,29 +2,38 @@ use nalgebra_glm::Vec2;
 
 use crate::{
     animation::data::Animation,
-    collision::data::Collider,
+    collision::{
+        data::Collider,
+        layer::{CollisionLayer, CollisionLayerKind, CollisionRulesView},
+    },
     config::CONFIG,
     dom::{ui::UiPhase, DomView},
     enemy::{
+        attack::data::WeaponStatsView,
         controller::data::{
             EnemyControllerFour, EnemyControllerOne, EnemyControllerThree, EnemyControllerTwo,
         },
         data::{Enemy, EnemyFourPhase, EnemyKind, EnemyOnePhase, EnemyThreePhase, EnemyTwoPhase},
         effects::data::EnemyEffect,
         events::EnemySelectEvent,
+        health::data::EnemyHealth,
         launcher::data::{EnemyLauncher, LauncherSide},
         physics::data::EnemyDirection,
     },
     layout::data::{LayoutAnchor, LayoutPosition},
-    media::MediaView,
     prelude::*,
-    renderer::RendererViewMut,
     tick::{BeginTickView, UpdateTickView},
 };
 
 use super::data::{EnemySpawner, EnemySpawnerViewMut};
 
-pub fn spawn_enemies(world: &World) {
+// only the given kinds, the first of them is selected if the configured one isn't there
+pub fn spawn_enemies(world: &World, kinds: &[EnemyKind]) {
+    let selected_kind = CONFIG
+        .selected_enemy
+        .filter(|kind| kinds.contains(kind))
+        .or_else(|| kinds.first().copied());
+
     world.run(
         |mut spawner: EnemySpawnerViewMut,
          mut sg_storages: SceneGraphStoragesMut,
@@ -38,35 +47,62 @@ pub fn spawn_enemies(world: &World) {
             ViewMut<EnemySelectEvent>,
             ViewMut<EnemyEffect>,
         ),
-         mut colliders: ViewMut<Collider>,
-         mut renderer: RendererViewMut,
-         media: MediaView| {
+         collision_group: (
+            ViewMut<Collider>,
+            ViewMut<CollisionLayer>,
+            CollisionRulesView,
+        ),
+         mut healths: ViewMut<EnemyHealth>,
+         weapons: WeaponStatsView| {
             let (mut enemy_positions, mut enemy_anchors, mut enemy_select, mut enemy_effects) =
                 enemy_group;
+            let (mut colliders, mut collision_layers, collision_rules) = collision_group;
 
             for spawn_index in 0..4 {
+                let kind = match spawn_index {
+                    0 => EnemyKind::One,
+                    1 => EnemyKind::Two,
+                    2 => EnemyKind::Three,
+                    _ => EnemyKind::Four,
+                };
+                if !kinds.contains(&kind) {
+                    continue;
+                }
+
                 let entity = sg_storages.spawn_child_trs(None, None, None, None);
 
                 let enemy = match spawn_index {
                     0 => Enemy::One {
                         phase: EnemyOnePhase::Idle,
                         spritesheet: spawner.sprite_sheets.one.clone().unwrap(),
-                        controller: EnemyControllerOne::new(EnemyDirection::Right),
+                        controller: EnemyControllerOne::new(
+                            EnemyDirection::Right,
+                            weapons.get(EnemyKind::One),
+                        ),
                     },
                     1 => Enemy::Two {
                         phase: EnemyTwoPhase::Idle,
                         spritesheet: spawner.sprite_sheets.two.clone().unwrap(),
-                        controller: EnemyControllerTwo::new(EnemyDirection::Left),
+                        controller: EnemyControllerTwo::new(
+                            EnemyDirection::Left,
+                            weapons.get(EnemyKind::Two),
+                        ),
                     },
                     2 => Enemy::Three {
                         phase: EnemyThreePhase::Idle,
                         spritesheet: spawner.sprite_sheets.three.clone().unwrap(),
-                        controller: EnemyControllerThree::new(EnemyDirection::Right),
+                        controller: EnemyControllerThree::new(
+                            EnemyDirection::Right,
+                            weapons.get(EnemyKind::Three),
+                        ),
                     },
                     3 => Enemy::Four {
                         phase: EnemyFourPhase::Idle,
                         spritesheet: spawner.sprite_sheets.four.clone().unwrap(),
-                        controller: EnemyControllerFour::new(EnemyDirection::Left),
+                        controller: EnemyControllerFour::new(
+                            EnemyDirection::Left,
+                            weapons.get(EnemyKind::Four),
+                        ),
                     },
                     _ => unreachable!(),
                 };
@@ -104,7 +140,9 @@ pub fn spawn_enemies(world: &World) {
                     &mut enemy_positions,
                     &mut enemy_anchors,
                     &mut colliders,
+                    &mut collision_layers,
                     &mut enemy_effects,
+                    &mut healths,
                 )
                     .add_component_unchecked(
                         entity,
@@ -114,11 +152,13 @@ pub fn spawn_enemies(world: &World) {
                             LayoutPosition::new(position),
                             LayoutAnchor::new(Vec3::zeros()),
                             Collider::default(),
+                            collision_rules.layer(CollisionLayerKind::Enemy),
                             EnemyEffect::new(),
+                            EnemyHealth::new(enemy_kind),
                         ),
                     );
 
-                if enemy_kind == CONFIG.selected_enemy.unwrap_or(EnemyKind::One) {
+                if Some(enemy_kind) == selected_kind {
                     enemy_select.add_component_unchecked(entity, EnemySelectEvent {});
                 }
             }
@@ -133,9 +173,7 @@ pub fn spawn_launcher(world: &World, side: LauncherSide) {
          // mut entities: EntitiesViewMut,
          mut launchers: ViewMut<EnemyLauncher>,
          mut animations: ViewMut<Animation>,
-         mut renderer: RendererViewMut,
-         mut positions: ViewMut<LayoutPosition>,
-         media: MediaView| {
+         mut positions: ViewMut<LayoutPosition>| {
             let scale = match side {
                 LauncherSide::Left => Vec3::new(1.0, 1.0, 1.0),
                 LauncherSide::Right => Vec3::new(-1.0, 1.0, 1.0),
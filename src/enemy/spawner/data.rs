@@ -1,6 +1,6 @@
 use crate::{
-    config::CONFIG, enemy::data::EnemySpriteSheets, media::Media, prelude::*, renderer::Renderer,
-    spritesheet::SpriteSheet,
+    atlas::data::TextureAtlas, config::CONFIG, enemy::data::EnemySpriteSheets, media::Media,
+    prelude::*, spritesheet::SpriteSheet,
 };
 
 pub type EnemySpawnerViewMut<'a> = UniqueViewMut<'a, EnemySpawner>;
@@ -13,11 +13,11 @@ pub struct EnemySpawner {
 }
 
 impl EnemySpawner {
-    pub fn new(renderer: &mut Renderer, media: &Media) -> Result<Self> {
+    pub fn new(atlas: &TextureAtlas, media: &Media) -> Result<Self> {
         Ok(Self {
-            sprite_sheets: EnemySpriteSheets::new(renderer, media)?,
+            sprite_sheets: EnemySpriteSheets::new(atlas, media)?,
             launcher_sprite_sheet: SpriteSheet::new(
-                renderer,
+                atlas,
                 &media.objects.launcher_img,
                 &media.objects.launcher_info,
             )?,
@@ -28,7 +28,7 @@ pub fn explosion_animation_sys(
     mut enemy_destroy_events: ViewMut<EnemyDestroyEvent>,
     enemies: View<Enemy>,
     explosions: View<Explosion>,
-    tick: BeginTickView,
+    tick: UpdateTickView,
 ) {
     for (entity, (explosion, animation)) in (&explosions, &mut animations).iter().with_id() {
         let next = match animation.timeout {
@@ -1,33 +1,38 @@
 use std::collections::HashSet;
 
-use awsm_web::webgl::{
-    PixelFormat, SimpleTextureOptions, TextureTarget, TextureWrapMode, WebGlTextureSource,
-};
-
-use crate::{media::Media, prelude::*, renderer::Renderer, spritesheet::SpriteSheet};
+use crate::{atlas::data::TextureAtlas, media::Media, prelude::*, spritesheet::SpriteSheet};
 
 pub type ExplosionSpawnerViewMut<'a> = UniqueViewMut<'a, ExplosionSpawner>;
 pub type ExplosionSpawnerView<'a> = UniqueView<'a, ExplosionSpawner>;
 
-#[derive(Component, Unique)]
+// just the queue, so it's part of the sim (see ExplosionSprites for what they look like)
+#[derive(Component, Unique, Default)]
 pub struct ExplosionSpawner {
     pub to_spawn: HashSet<EntityId>,
     pub spawned: HashSet<EntityId>,
-    pub spritesheet: SpriteSheet,
 }
 
 impl ExplosionSpawner {
-    pub fn new(renderer: &mut Renderer, media: &Media) -> Result<Self> {
-        let spritesheet = SpriteSheet::new(
-            renderer,
-            &media.objects.explosion_img,
-            &media.objects.explosion_info,
-        )?;
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+pub type ExplosionSpritesView<'a> = UniqueView<'a, ExplosionSprites>;
+
+#[derive(Component, Unique)]
+pub struct ExplosionSprites {
+    pub spritesheet: SpriteSheet,
+}
 
+impl ExplosionSprites {
+    pub fn new(atlas: &TextureAtlas, media: &Media) -> Result<Self> {
         Ok(Self {
-            to_spawn: HashSet::new(),
-            spawned: HashSet::new(),
-            spritesheet,
+            spritesheet: SpriteSheet::new(
+                atlas,
+                &media.objects.explosion_img,
+                &media.objects.explosion_info,
+            )?,
         })
     }
 }
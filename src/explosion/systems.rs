@@ -1,14 +1,12 @@
 use nalgebra::Point3;
 
-use super::data::{Explosion, ExplosionSpawnerViewMut};
+use super::data::{Explosion, ExplosionSpawnerViewMut, ExplosionSpritesView};
 use crate::{
     animation::data::Animation,
-    audio::{AudioEvent, AudioEventQueue},
-    camera::CameraView,
-    collision::data::Collider,
+    collision::{data::Collider, layer::CollisionLayer},
+    hit::data::HitEventQueueView,
     layout::data::{LayoutAnchor, LayoutPosition},
     prelude::*,
-    tick::UpdateTickView,
 };
 
 pub fn explosion_spawn_sys(
@@ -20,24 +18,20 @@ pub fn explosion_spawn_sys(
         ViewMut<Animation>,
     ),
     mut sg_storages: SceneGraphStoragesMut,
-    mut rand: RandViewMut,
-    mut audio_events: UniqueViewMut<AudioEventQueue>,
-    camera: CameraView,
+    sprites: ExplosionSpritesView,
     colliders: View<Collider>,
-    tick: UpdateTickView,
 ) {
     let (mut explosions, mut positions, mut anchors, mut animations) = group;
 
     let (width, height) = (
-        spawner.spritesheet.max_cell_width,
-        spawner.spritesheet.max_cell_height,
+        sprites.spritesheet.max_cell_width,
+        sprites.spritesheet.max_cell_height,
     );
 
-    let animation = Animation::new(&spawner.spritesheet);
+    let animation = Animation::new(&sprites.spritesheet);
 
     let spawner = &mut *spawner;
 
-    let mut should_play_sound = false;
     for to_spawn in spawner.to_spawn.drain() {
         // comment this out to trace the explosion along the path, helpful for debugging
         if spawner.spawned.contains(&to_spawn) {
@@ -69,11 +63,28 @@ pub fn explosion_spawn_sys(
 
         (&mut explosions, &mut animations)
             .add_component_unchecked(entity, (explosion, animation.clone()));
-
-        should_play_sound = true;
     }
+}
 
-    if should_play_sound {
-        audio_events.push(AudioEvent::CollisionImpact);
+// projectiles blow up on impact, enemies take damage instead (see enemy_hit_sys)
+// they stay around until the explosion covers them, but can't hit anything else in the meantime
+// otherwise the same pair would keep colliding (and scoring) every tick
+pub fn explosion_hit_sys(
+    hit_events: HitEventQueueView,
+    mut spawner: ExplosionSpawnerViewMut,
+    mut layers: ViewMut<CollisionLayer>,
+) {
+    for hit in hit_events.iter() {
+        for (entity, kind) in [
+            (hit.attacker, hit.attacker_kind),
+            (hit.victim, hit.victim_kind),
+        ] {
+            if !kind.is_enemy() {
+                spawner.to_spawn.insert(entity);
+                if let Ok(layer) = (&mut layers).get(entity) {
+                    layer.mask = CollisionLayer::NONE;
+                }
+            }
+        }
     }
 }
@@ -1,8 +1,12 @@
 use crate::{
     delete::data::MarkForDeletion,
-    dom::{ui::game::GameUiPhase, DomView},
+    dom::{
+        events::{UiEvent, UiEventQueue},
+        ui::game::GameUiPhase,
+    },
     enemy::data::Enemy,
     prelude::*,
+    stats::data::RunStatsView,
     tick::PauseTick,
 };
 
@@ -10,13 +14,12 @@ pub fn game_over_sys(
     mut pause_tick: UniqueViewMut<PauseTick>,
     mut enemies: ViewMut<Enemy>,
     mut deletions: ViewMut<MarkForDeletion>,
-    dom: DomView,
+    mut ui_events: UniqueViewMut<UiEventQueue>,
+    stats: RunStatsView,
 ) {
     if (&enemies, !&deletions).iter().next().is_none() {
         *pause_tick = PauseTick::GameOver {};
-        dom.ui
-            .game_ui_unchecked()
-            .phase
-            .set_neq(Some(GameUiPhase::GameOver));
+        ui_events.push(UiEvent::RunEnded(stats.clone()));
+        ui_events.push(UiEvent::GamePhase(Some(GameUiPhase::GameOver)));
     }
 }
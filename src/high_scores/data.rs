@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::*, stats::data::RunStats};
+
+use super::storage::ScoreStorage;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    // unix time in milliseconds, when the run ended
+    pub timestamp: u64,
+    // in milliseconds, same as RunStats::time_survived
+    pub duration: f64,
+    pub score: u32,
+    pub seed: u64,
+    // the difficulty preset name
+    pub mode: String,
+}
+
+impl RunRecord {
+    pub fn new(stats: &RunStats, timestamp: u64, seed: u64, mode: &str) -> Self {
+        Self {
+            timestamp,
+            duration: stats.time_survived,
+            score: stats.score(),
+            seed,
+            mode: mode.to_string(),
+        }
+    }
+}
+
+// what actually gets written to storage
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredScores {
+    version: u32,
+    best: Vec<RunRecord>,
+    history: Vec<RunRecord>,
+}
+
+pub struct HighScores {
+    // highest score first
+    pub best: Vec<RunRecord>,
+    // most recent first
+    pub history: Vec<RunRecord>,
+    storage: Box<dyn ScoreStorage>,
+}
+
+impl HighScores {
+    pub const VERSION: u32 = 1;
+    pub const MAX_BEST: usize = 10;
+    pub const MAX_HISTORY: usize = 20;
+
+    // corrupt or outdated data isn't worth failing over, we just start fresh
+    pub fn load(storage: Box<dyn ScoreStorage>) -> Self {
+        let stored = match storage.read() {
+            Ok(Some(data)) => match serde_json::from_str::<StoredScores>(&data) {
+                Ok(stored) if stored.version == Self::VERSION => stored,
+                Ok(stored) => {
+                    log::warn!("ignoring high scores with version {}", stored.version);
+                    StoredScores::default()
+                }
+                Err(err) => {
+                    log::warn!("ignoring unreadable high scores: {}", err);
+                    StoredScores::default()
+                }
+            },
+            Ok(None) => StoredScores::default(),
+            Err(err) => {
+                log::warn!("couldn't load high scores: {}", err);
+                StoredScores::default()
+            }
+        };
+
+        Self {
+            best: stored.best,
+            history: stored.history,
+            storage,
+        }
+    }
+
+    // returns the rank in the best list (0 is the top), if it made it in
+    pub fn add(&mut self, record: RunRecord) -> Result<Option<usize>> {
+        self.history.insert(0, record.clone());
+        self.history.truncate(Self::MAX_HISTORY);
+
+        // ties go to the earlier run
+        let rank = self
+            .best
+            .iter()
+            .position(|best| record.score > best.score)
+            .unwrap_or(self.best.len());
+
+        let rank = if rank < Self::MAX_BEST {
+            self.best.insert(rank, record);
+            self.best.truncate(Self::MAX_BEST);
+            Some(rank)
+        } else {
+            None
+        };
+
+        self.save()?;
+
+        Ok(rank)
+    }
+
+    pub fn clear(&mut self) -> Result<()> {
+        self.best.clear();
+        self.history.clear();
+        self.save()
+    }
+
+    fn save(&mut self) -> Result<()> {
+        let data = serde_json::to_string(&StoredScores {
+            version: Self::VERSION,
+            best: self.best.clone(),
+            history: self.history.clone(),
+        })?;
+
+        self.storage.write(&data)
+    }
+}
@@ -0,0 +1,4 @@
+// Best runs and recent run history, persisted between sessions
+// everything goes through the ScoreStorage trait so it isn't tied to the browser
+pub mod data;
+pub mod storage;
@@ -0,0 +1,64 @@
+use crate::prelude::*;
+
+// Just reads and writes the serialized scores, HighScores takes care of the format
+pub trait ScoreStorage {
+    fn read(&self) -> Result<Option<String>>;
+    fn write(&mut self, data: &str) -> Result<()>;
+}
+
+pub struct LocalStorage {
+    key: String,
+}
+
+impl LocalStorage {
+    pub const DEFAULT_KEY: &'static str = "not-a-game-high-scores";
+
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into() }
+    }
+
+    fn storage() -> Result<web_sys::Storage> {
+        web_sys::window()
+            .ok_or_else(|| anyhow!("no window"))?
+            .local_storage()
+            .map_err(|_| anyhow!("local storage is not accessible"))?
+            .ok_or_else(|| anyhow!("local storage is not available"))
+    }
+}
+
+impl ScoreStorage for LocalStorage {
+    fn read(&self) -> Result<Option<String>> {
+        Self::storage()?
+            .get_item(&self.key)
+            .map_err(|_| anyhow!("could not read {} from local storage", self.key))
+    }
+
+    fn write(&mut self, data: &str) -> Result<()> {
+        Self::storage()?
+            .set_item(&self.key, data)
+            .map_err(|_| anyhow!("could not write {} to local storage", self.key))
+    }
+}
+
+// for tests and the headless sim, nothing survives the process
+#[derive(Default)]
+pub struct MemoryStorage {
+    data: Option<String>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ScoreStorage for MemoryStorage {
+    fn read(&self) -> Result<Option<String>> {
+        Ok(self.data.clone())
+    }
+
+    fn write(&mut self, data: &str) -> Result<()> {
+        self.data = Some(data.to_string());
+        Ok(())
+    }
+}
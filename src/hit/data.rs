@@ -0,0 +1,90 @@
+use std::ops::Deref;
+
+use nalgebra_glm::Vec2;
+
+use crate::{
+    enemy::data::EnemyKind,
+    level::data::BombKind,
+    prelude::*,
+    projectiles::data::{Projectile, ProjectileMovement},
+};
+
+pub type HitEventQueueViewMut<'a> = UniqueViewMut<'a, HitEventQueue>;
+pub type HitEventQueueView<'a> = UniqueView<'a, HitEventQueue>;
+
+#[derive(Debug, Clone)]
+pub struct HitEvent {
+    pub attacker: EntityId,
+    pub victim: EntityId,
+    pub attacker_kind: HitKind,
+    pub victim_kind: HitKind,
+    // world space, the middle of where the two overlap
+    pub contact: Vec2,
+    // FrameCount at the time of the hit
+    pub frame: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HitKind {
+    Enemy(EnemyKind),
+    Bomb(BombKind),
+    // shooter is the kind of enemy that fired it
+    Bullet { shooter: Option<EnemyKind> },
+    Rocket { shooter: Option<EnemyKind> },
+}
+
+impl HitKind {
+    pub fn from_projectile(projectile: &Projectile) -> Self {
+        match projectile.movement {
+            ProjectileMovement::Bomb { kind, .. } => Self::Bomb(kind),
+            ProjectileMovement::Bullet { .. } => Self::Bullet {
+                shooter: projectile.shooter,
+            },
+            ProjectileMovement::Rocket { .. } => Self::Rocket {
+                shooter: projectile.shooter,
+            },
+        }
+    }
+
+    pub fn is_enemy(&self) -> bool {
+        matches!(self, Self::Enemy(_))
+    }
+
+    pub fn is_bomb(&self) -> bool {
+        matches!(self, Self::Bomb(_))
+    }
+
+    pub fn shooter(&self) -> Option<EnemyKind> {
+        match self {
+            Self::Bullet { shooter } | Self::Rocket { shooter } => *shooter,
+            Self::Enemy(_) | Self::Bomb(_) => None,
+        }
+    }
+}
+
+// The hits from the latest collision check, every subscriber reads the same list
+// it's cleared right before the next check (see hit_event_clear_sys), so each hit is seen once
+#[derive(Component, Unique, Default)]
+pub struct HitEventQueue(Vec<HitEvent>);
+
+impl HitEventQueue {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn push(&mut self, event: HitEvent) {
+        self.0.push(event);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl Deref for HitEventQueue {
+    type Target = Vec<HitEvent>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
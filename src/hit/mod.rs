@@ -0,0 +1,4 @@
+// What hit what, once pixel-perfect collision has confirmed it
+// collision only produces these, anything that reacts to a hit (explosions, damage, scoring, audio) reads them
+pub mod data;
+pub mod systems;
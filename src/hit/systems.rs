@@ -0,0 +1,9 @@
+use crate::prelude::*;
+
+use super::data::HitEventQueueViewMut;
+
+// runs right before the collision checks, so subscribers later in the tick see this tick's hits
+// and subscribers earlier in the tick see the previous one's
+pub fn hit_event_clear_sys(mut hit_events: HitEventQueueViewMut) {
+    hit_events.clear();
+}
@@ -28,7 +28,7 @@ impl DerefMut for LayoutPosition {
     }
 }
 
-// LayoutAnchor is not normalized and places things in an *absolute* position on the screen
+// LayoutAnchor is not normalized and places things in an *absolute* position, in design units
 // It's also used as an offset, e.g. to nudge things over by their original bitmap size
 #[derive(Component, Debug)]
 pub struct LayoutAnchor {
@@ -0,0 +1,135 @@
+// Fitting the design resolution into whatever window we actually get
+// the world is always laid out in design units, this only decides where the canvas goes
+// and how much of the world ends up on it
+//
+// std only, so it can be tested natively (see tests/scaling.rs)
+
+// The virtual resolution, same as the background images
+pub const DESIGN_WIDTH: f64 = 1920.0;
+pub const DESIGN_HEIGHT: f64 = 1080.0;
+
+// room left at the bottom of the design area for the footer
+// the dom footer is sized to cover exactly this, see ViewportFit::footer
+pub const FOOTER_HEIGHT: f64 = 64.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleMode {
+    // the whole design area, as big as it fits, with bars on the sides or top and bottom
+    Letterbox,
+    // fills the window, cutting off the edges of the design area that don't fit
+    Crop,
+    // fills the window by squashing the design area out of shape
+    Stretch,
+}
+
+impl ScaleMode {
+    pub const ALL: [Self; 3] = [Self::Letterbox, Self::Crop, Self::Stretch];
+
+    // as it is in the config
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Letterbox => "letterbox",
+            Self::Crop => "crop",
+            Self::Stretch => "stretch",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportFit {
+    // where the canvas goes in the window, in css pixels
+    pub canvas_x: f64,
+    pub canvas_y: f64,
+    pub canvas_width: f64,
+    pub canvas_height: f64,
+    // how much of the world is on the canvas, in design units
+    pub view_width: f64,
+    pub view_height: f64,
+}
+
+impl ViewportFit {
+    pub fn new(mode: ScaleMode, window_width: f64, window_height: f64) -> Self {
+        // e.g. minimized, there's nothing sensible to fit into
+        let window_width = window_width.max(1.0);
+        let window_height = window_height.max(1.0);
+
+        let scale_x = window_width / DESIGN_WIDTH;
+        let scale_y = window_height / DESIGN_HEIGHT;
+
+        match mode {
+            ScaleMode::Letterbox => {
+                let scale = scale_x.min(scale_y);
+                let canvas_width = DESIGN_WIDTH * scale;
+                let canvas_height = DESIGN_HEIGHT * scale;
+                Self {
+                    canvas_x: (window_width - canvas_width) / 2.0,
+                    canvas_y: (window_height - canvas_height) / 2.0,
+                    canvas_width,
+                    canvas_height,
+                    view_width: DESIGN_WIDTH,
+                    view_height: DESIGN_HEIGHT,
+                }
+            }
+            ScaleMode::Crop => {
+                let scale = scale_x.max(scale_y);
+                Self {
+                    view_width: window_width / scale,
+                    view_height: window_height / scale,
+                    ..Self::full_window(window_width, window_height)
+                }
+            }
+            ScaleMode::Stretch => Self {
+                view_width: DESIGN_WIDTH,
+                view_height: DESIGN_HEIGHT,
+                ..Self::full_window(window_width, window_height)
+            },
+        }
+    }
+
+    // the canvas' backing store in device pixels, so it's sharp on high-dpi screens
+    pub fn backing_size(&self, pixel_ratio: f64) -> (u32, u32) {
+        let pixel_ratio = if pixel_ratio > 0.0 { pixel_ratio } else { 1.0 };
+        (
+            ((self.canvas_width * pixel_ratio).round() as u32).max(1),
+            ((self.canvas_height * pixel_ratio).round() as u32).max(1),
+        )
+    }
+
+    // u and v are 0..1 across the canvas, left to right and top to bottom (like the dom)
+    // returns design units, from the bottom-left of the design area and with y going up (like the world)
+    pub fn canvas_to_design(&self, u: f64, v: f64) -> (f64, f64) {
+        (
+            (DESIGN_WIDTH - self.view_width) / 2.0 + u * self.view_width,
+            (DESIGN_HEIGHT - self.view_height) / 2.0 + (1.0 - v) * self.view_height,
+        )
+    }
+
+    // the other way around, all the way out to css pixels in the window
+    pub fn design_to_window(&self, x: f64, y: f64) -> (f64, f64) {
+        let u = (x - (DESIGN_WIDTH - self.view_width) / 2.0) / self.view_width;
+        let v = 1.0 - (y - (DESIGN_HEIGHT - self.view_height) / 2.0) / self.view_height;
+        (
+            self.canvas_x + u * self.canvas_width,
+            self.canvas_y + v * self.canvas_height,
+        )
+    }
+
+    // where the footer goes in the window, in css pixels: (left, top, width, height)
+    // as wide as the canvas, and as tall as FOOTER_HEIGHT ends up at this scale
+    pub fn footer(&self) -> (f64, f64, f64, f64) {
+        let (_, top) = self.design_to_window(0.0, FOOTER_HEIGHT);
+        let (_, bottom) = self.design_to_window(0.0, 0.0);
+        (self.canvas_x, top, self.canvas_width, bottom - top)
+    }
+
+    fn full_window(window_width: f64, window_height: f64) -> Self {
+        Self {
+            canvas_x: 0.0,
+            canvas_y: 0.0,
+            canvas_width: window_width,
+            canvas_height: window_height,
+            view_width: DESIGN_WIDTH,
+            view_height: DESIGN_HEIGHT,
+        }
+    }
+}
@@ -1,30 +1,32 @@
-use crate::{prelude::*, tick::UpdateTickView};
+use crate::prelude::*;
 
-use super::data::{LayoutAnchor, LayoutPosition};
+use super::{
+    data::{LayoutAnchor, LayoutPosition},
+    scaling::{DESIGN_HEIGHT, DESIGN_WIDTH, FOOTER_HEIGHT},
+};
 
-const FOOTER_HEIGHT: f32 = 64.0;
-
-// Translate from normalized units to screen space
+// Translate from normalized units to world space, which is the design resolution
+// so it's the same on every screen, see layout/scaling.rs for how that's fit to the window
 pub fn flush_layout_sys(
     mut sg_storages: SceneGraphStoragesMut,
     positions: View<LayoutPosition>,
     anchors: View<LayoutAnchor>,
-    tick: UpdateTickView,
 ) {
-    let viewport_width = tick.viewport_width as f32;
-    let viewport_height = tick.viewport_height as f32;
+    let design_width = DESIGN_WIDTH as f32;
+    let design_height = DESIGN_HEIGHT as f32;
 
     for (mut translation, position) in (&mut sg_storages.translations, &positions).iter() {
-        translation.x = (position.x * viewport_width) - (viewport_width / 2.0);
-        translation.y = (position.y * viewport_height) - (viewport_height / 2.0);
-        translation.y += FOOTER_HEIGHT;
+        translation.x = (position.x * design_width) - (design_width / 2.0);
+        translation.y = (position.y * design_height) - (design_height / 2.0);
+        translation.y += FOOTER_HEIGHT as f32;
 
         //log_n!("flush_layout_sys", 10, "translation: {:?}, position: {:?}", translation, position);
     }
 
+    // already in design units
     for (mut translation, anchor) in (&mut sg_storages.translations, &anchors).iter() {
-        translation.x += (anchor.x / viewport_width) * viewport_width;
-        translation.y += (anchor.y / viewport_height) * viewport_height;
+        translation.x += anchor.x;
+        translation.y += anchor.y;
         // translation.z += anchor.z;
     }
 }
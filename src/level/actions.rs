@@ -0,0 +1,22 @@
+use crate::{
+    enemy::spawner::actions::{spawn_enemies, spawn_launcher},
+    prelude::*,
+};
+
+use super::data::{Level, LevelRunnerView};
+
+// Who's on the field is up to the level, without one it's everybody
+pub fn spawn_level(world: &World) {
+    let (enemies, launchers) = {
+        let runner = world.borrow::<LevelRunnerView>().unwrap_ext();
+        match &runner.level {
+            Some(level) => (level.enemies.clone(), level.launchers.clone()),
+            None => (Level::all_enemies(), Level::all_launchers()),
+        }
+    };
+
+    spawn_enemies(world, &enemies);
+    for side in launchers {
+        spawn_launcher(world, side);
+    }
+}
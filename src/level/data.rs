@@ -0,0 +1,206 @@
+use serde::Deserialize;
+
+use crate::{
+    enemy::{data::EnemyKind, launcher::data::LauncherSide},
+    prelude::*,
+};
+
+pub type LevelRunnerViewMut<'a> = UniqueViewMut<'a, LevelRunner>;
+pub type LevelRunnerView<'a> = UniqueView<'a, LevelRunner>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Levels {
+    pub levels: Vec<Level>,
+}
+
+impl Levels {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let levels: Self = serde_json::from_str(json)?;
+        for level in &levels.levels {
+            level.validate()?;
+        }
+        Ok(levels)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Level> {
+        self.levels.iter().find(|level| level.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Level {
+    // stable id, this is what's in the url, recordings and high scores
+    pub name: String,
+    pub label: String,
+    #[serde(default = "Level::all_enemies")]
+    pub enemies: Vec<EnemyKind>,
+    #[serde(default = "Level::all_launchers")]
+    pub launchers: Vec<LauncherSide>,
+    pub victory: VictoryCondition,
+    // sorted by `at`
+    pub waves: Vec<Wave>,
+    // which of media/image/bg/1..4 to show, if not set it rotates between runs
+    #[serde(default)]
+    pub background: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VictoryCondition {
+    // every wave has dropped, and none of its bombs are still in the air
+    SurviveAllWaves,
+    SurviveTime { seconds: f64 },
+    Intercepts { count: u32 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Wave {
+    // seconds since the level started (pauses don't count)
+    pub at: f64,
+    pub drops: Vec<BombDrop>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BombDrop {
+    pub x: DropPosition,
+    #[serde(default)]
+    pub kind: BombKind,
+    // multiplies the base acceleration
+    #[serde(default = "BombDrop::default_speed")]
+    pub speed: f32,
+}
+
+// 0..1 across the screen, either exact or picked (by the seeded rng) from a range
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(untagged)]
+pub enum DropPosition {
+    At(f32),
+    Range(f32, f32),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BombKind {
+    #[default]
+    Standard,
+    // bigger, and hits twice as hard
+    Heavy,
+}
+
+impl BombKind {
+    pub fn damage(&self) -> f32 {
+        match self {
+            Self::Standard => 40.0,
+            Self::Heavy => 80.0,
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self {
+            Self::Standard => 1.0,
+            Self::Heavy => 1.5,
+        }
+    }
+}
+
+impl BombDrop {
+    // what a speed of 1.0 means, roughly the middle of the random bomber's range
+    pub const BASE_ACCELERATION: f32 = -0.00000005;
+
+    fn default_speed() -> f32 {
+        1.0
+    }
+}
+
+impl Level {
+    pub fn all_enemies() -> Vec<EnemyKind> {
+        vec![
+            EnemyKind::One,
+            EnemyKind::Two,
+            EnemyKind::Three,
+            EnemyKind::Four,
+        ]
+    }
+
+    pub fn all_launchers() -> Vec<LauncherSide> {
+        vec![LauncherSide::Left, LauncherSide::Right]
+    }
+
+    fn validate(&self) -> Result<()> {
+        let name = &self.name;
+        if self.enemies.is_empty() {
+            return Err(anyhow!("level {name} has no enemies"));
+        }
+        // enemy four fires from the launchers
+        if self.enemies.contains(&EnemyKind::Four) && self.launchers.is_empty() {
+            return Err(anyhow!("level {name} has enemy four but no launchers"));
+        }
+        if self.waves.windows(2).any(|pair| pair[0].at > pair[1].at) {
+            return Err(anyhow!("level {name} waves must be sorted by `at`"));
+        }
+        for drop in self.waves.iter().flat_map(|wave| wave.drops.iter()) {
+            let in_range = |x: f32| (0.0..=1.0).contains(&x);
+            let valid = match drop.x {
+                DropPosition::At(x) => in_range(x),
+                DropPosition::Range(min, max) => in_range(min) && in_range(max) && min <= max,
+            };
+            if !valid {
+                return Err(anyhow!("level {name} drop positions must be within 0..1"));
+            }
+            if drop.speed <= 0.0 {
+                return Err(anyhow!("level {name} drop speed must be positive"));
+            }
+        }
+        if let Some(pane) = self.background {
+            if !(1..=4).contains(&pane) {
+                return Err(anyhow!("level {name} background must be within 1..4"));
+            }
+        }
+        if self.victory == VictoryCondition::SurviveAllWaves && self.waves.is_empty() {
+            return Err(anyhow!("level {name} has no waves to survive"));
+        }
+        Ok(())
+    }
+}
+
+// Plays back the selected level's waves, if there is one
+// otherwise the bomber does its own (difficulty-driven) thing
+#[derive(Component, Unique)]
+pub struct LevelRunner {
+    pub level: Option<Level>,
+    // in milliseconds, only advances while running
+    pub elapsed: f64,
+    pub next_wave: usize,
+}
+
+impl LevelRunner {
+    pub fn new(level: Option<Level>) -> Self {
+        Self {
+            level,
+            elapsed: 0.0,
+            next_wave: 0,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.level.is_some()
+    }
+
+    pub fn victory(&self) -> Option<VictoryCondition> {
+        self.level.as_ref().map(|level| level.victory)
+    }
+
+    // all waves have been dropped
+    pub fn finished(&self) -> bool {
+        match &self.level {
+            Some(level) => self.next_wave >= level.waves.len(),
+            None => false,
+        }
+    }
+
+    // same level, from the top
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.next_wave = 0;
+    }
+}
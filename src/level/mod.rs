@@ -0,0 +1,6 @@
+// Handcrafted levels, as an alternative to the endless difficulty-driven bomber
+// levels are data (media/data/levels.json) and picked with ?level=<name>
+// a level decides who's on the field, when and where bombs drop, and what counts as winning
+pub mod actions;
+pub mod data;
+pub mod systems;
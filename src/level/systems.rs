@@ -0,0 +1,49 @@
+use crate::{
+    bomber::data::BomberViewMut,
+    prelude::*,
+    projectiles::data::{ProjectileSpawnerViewMut, ProjectileToSpawn},
+    tick::UpdateTickView,
+};
+
+use super::data::{BombDrop, DropPosition, LevelRunnerViewMut};
+
+pub fn level_wave_sys(
+    mut runner: LevelRunnerViewMut,
+    mut bomber: BomberViewMut,
+    mut projectile_spawner: ProjectileSpawnerViewMut,
+    mut rand: RandViewMut,
+    tick: UpdateTickView,
+) {
+    let runner = &mut *runner;
+
+    let level = match &runner.level {
+        Some(level) => level,
+        None => return,
+    };
+
+    runner.elapsed += tick.delta;
+
+    while let Some(wave) = level.waves.get(runner.next_wave) {
+        if wave.at * 1000.0 > runner.elapsed {
+            break;
+        }
+
+        for drop in &wave.drops {
+            let x = match drop.x {
+                DropPosition::At(x) => x,
+                DropPosition::Range(min, max) if min < max => rand.gen_range(min..max),
+                DropPosition::Range(min, _) => min,
+            };
+
+            projectile_spawner.to_spawn.push(ProjectileToSpawn::Bomb {
+                x,
+                acceleration: BombDrop::BASE_ACCELERATION * drop.speed,
+                kind: drop.kind,
+            });
+        }
+
+        // still counted, for the winner screen
+        bomber.bombs_dropped += wave.drops.len() as u32;
+        runner.next_wave += 1;
+    }
+}
@@ -0,0 +1,41 @@
+// Everything except the browser entry point in main.rs
+// it's a plain library so that native tests and benches can use the game too, e.g. tests/headless.rs
+#![allow(dead_code)]
+#![allow(warnings)]
+
+pub mod animation;
+pub mod atlas;
+pub mod audio;
+pub mod background;
+pub mod bomber;
+pub mod camera;
+pub mod collision;
+pub mod config;
+pub mod context_loss;
+pub mod controller;
+pub mod delete;
+pub mod difficulty;
+pub mod dom;
+pub mod enemy;
+pub mod explosion;
+pub mod game_over;
+pub mod high_scores;
+pub mod hit;
+pub mod layout;
+pub mod level;
+pub mod logging;
+pub mod media;
+pub mod particles;
+pub mod post;
+pub mod prelude;
+pub mod projectiles;
+pub mod rand_helpers;
+pub mod renderer;
+pub mod restart;
+pub mod route;
+pub mod sim;
+pub mod spritesheet;
+pub mod stats;
+pub mod tick;
+pub mod utils;
+pub mod winner;
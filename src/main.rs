@@ -1,99 +1,75 @@
 #![allow(dead_code)]
 #![allow(warnings)]
 
-pub mod animation;
-pub mod audio;
-pub mod background;
-pub mod bomber;
-pub mod camera;
-pub mod collision;
-pub mod config;
-pub mod controller;
-pub mod delete;
-pub mod dom;
-pub mod enemy;
-pub mod explosion;
-pub mod game_over;
-pub mod layout;
-pub mod logging;
-pub mod media;
-pub mod prelude;
-pub mod projectiles;
-pub mod rand_helpers;
-pub mod renderer;
-pub mod route;
-pub mod spritesheet;
-pub mod tick;
-pub mod utils;
-
 use std::{borrow::BorrowMut, sync::atomic::AtomicU64};
 
-use audio::{audio_event_process_sys, AudioEventQueue, AudioPlayer};
 use awsm_web::{
     tick::{MainLoop, MainLoopOptions, Raf},
     webgl::ResizeStrategy,
 };
-use background::{
-    data::{Background, BackgroundViewMut},
-    systems::background_move_sys,
-};
-use bomber::{data::Bomber, systems::bomber_drop_sys};
-use camera::{systems::camera_update_ubo_sys, Camera, CameraViewMut};
-use collision::{
-    data::CollisionEventQueue,
-    debug::CollisionDebugger,
-    systems::{
-        detect_geometric_collision_sys, pixel_collision_check_sys, pixel_collision_render_sys,
-        update_collider_sys,
+use gloo_events::EventListener;
+use gloo_timers::future::TimeoutFuture;
+use not_a_game::{
+    atlas::data::TextureAtlas,
+    audio::{audio_event_process_sys, AudioEventQueue, AudioPlayer},
+    background::{data::Background, systems::background_move_sys},
+    bomber::data::Bomber,
+    camera::{
+        systems::{camera_effects_sys, camera_hit_sys, camera_update_ubo_sys},
+        Camera, CameraEffects, CameraViewMut,
     },
-};
-use config::CONFIG;
-use controller::{
-    listeners::InputListeners, queue::InputQueue, systems::controller_process_queue_sys,
-};
-use delete::systems::delete_sys;
-use dom::{
-    theme,
-    ui::{
-        game::{GameUi, GameUiPhase},
-        UiPhase,
+    collision::{
+        data::CollisionEventQueue,
+        debug::CollisionDebugger,
+        systems::{pixel_collision_check_sys, pixel_collision_render_sys},
     },
-    DomState, DomView,
-};
-use enemy::{
-    animation::systems::enemy_animation_sys,
-    controller::systems::enemy_controller_physics_sys,
-    data::Enemy,
-    destroy::enemy_destroy_event_sys,
-    launcher::{data::LauncherSide, systems::launcher_animation_sys},
-    physics::systems::enemy_position_sys,
-    select::enemy_select_event_sys,
-    spawner::{
-        actions::{spawn_enemies, spawn_launcher},
-        EnemySpawner,
+    config::{Config, CONFIG},
+    context_loss::actions::{context_lost, rebuild_context, resume_after_context_restored},
+    controller::{
+        listeners::InputListeners,
+        queue::InputQueue,
+        recording::{
+            input_replay_sys, InputRecorder, InputRecording, InputReplay, InputReplayView,
+        },
+        systems::controller_process_queue_sys,
+    },
+    dom::{
+        events::ui_event_process_sys,
+        theme,
+        ui::{
+            game::{GameUi, GameUiPhase},
+            UiPhase,
+        },
+        DomState, DomView,
+    },
+    enemy::{data::Enemy, spawner::EnemySpawner},
+    explosion::data::ExplosionSprites,
+    layout::scaling::ViewportFit,
+    level::actions::spawn_level,
+    media::Media,
+    particles::{
+        data::ParticleSystem,
+        systems::{particle_attach_sys, particle_update_sys},
+    },
+    post::{
+        data::{PostEffects, PostSettings},
+        systems::{post_effects_sys, post_hit_sys},
+    },
+    prelude::*,
+    projectiles::data::ProjectileSprites,
+    renderer::{
+        framebuffers::FrameBuffers,
+        systems::{draw_stats_log_sys, render_sys},
+        Renderer, RendererViewMut,
+    },
+    restart::actions::restart_if_requested,
+    sim::workloads::{init_sim_world, update_workload},
+    tick::{
+        BeginTick, BeginTickViewMut, DrawTick, DrawTickViewMut, EndTick, EndTickViewMut, PauseTick,
+        PauseTickView, UpdateTick, UpdateTickViewMut,
     },
-};
-use explosion::{
-    animation::explosion_animation_sys, data::ExplosionSpawner, systems::explosion_spawn_sys,
-};
-use game_over::systems::game_over_sys;
-use gloo_events::EventListener;
-use gloo_timers::future::TimeoutFuture;
-use layout::systems::flush_layout_sys;
-use media::Media;
-use prelude::*;
-use projectiles::{
-    data::ProjectileSpawner,
-    systems::{projectile_physics_sys, projectile_spawn_sys},
-};
-use renderer::{
-    framebuffers::FrameBuffers, shaders::Shaders, systems::render_sys, Renderer, RendererViewMut,
 };
 use shipyard_scenegraph::init::init_scenegraph;
-use tick::{
-    BeginTick, BeginTickViewMut, DrawTick, DrawTickViewMut, EndTick, EndTickViewMut, PauseTick,
-    PauseTickView, UpdateTick, UpdateTickViewMut,
-};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::console::clear;
 
@@ -114,82 +90,132 @@ async fn start() {
     let world = Arc::new(World::new());
     {
         let dom = DomState::new().await;
+        // before anything else reads the config
+        if let Err(err) = Config::init().await {
+            log::error!("{:#}", err);
+            dom.ui
+                .phase
+                .set(UiPhase::Error(format!("Bad config: {:#}", err)));
+            return;
+        }
         let audio_player = AudioPlayer::new();
         let media = Media::load(&dom, &audio_player).await.unwrap_ext();
         let mut renderer = Renderer::new(&dom).unwrap_ext();
 
         dom.ui.phase.set(UiPhase::Initializing);
-        let background = Background::new(&mut renderer, &media).unwrap_ext();
         let camera = Camera::new(&mut renderer).unwrap_ext();
-        let enemy_spawner = EnemySpawner::new(&mut renderer, &media).unwrap_ext();
-        let projectile_spawner = ProjectileSpawner::new(&mut renderer, &media).unwrap_ext();
-        let explosion_spawner = ExplosionSpawner::new(&mut renderer, &media).unwrap_ext();
+        // everything below gets its sprites from here, rather than a texture per image
+        let atlas = TextureAtlas::new(&mut renderer, &media).unwrap_ext();
+        let enemy_spawner = EnemySpawner::new(&atlas, &media).unwrap_ext();
+        let projectile_sprites = ProjectileSprites::new(&atlas, &media).unwrap_ext();
+        let explosion_sprites = ExplosionSprites::new(&atlas, &media).unwrap_ext();
         let collision_debugger = CollisionDebugger::new(&mut renderer).unwrap_ext();
+        let particle_system =
+            ParticleSystem::new(&mut renderer, media.data.particles.clone()).unwrap_ext();
+        // only needed again if the gl context is lost
+        world.add_unique(atlas);
+        let difficulty_presets = media.data.difficulty.clone();
+        let levels = media.data.levels.clone();
+        let collision_rules = media.data.collision.clone();
+        let weapon_stats = media.data.weapons.clone();
+        let replay = match CONFIG.replay_url() {
+            Some(url) => Some(InputRecording::load(&url).await.unwrap_ext()),
+            None => None,
+        };
+
+        // a replay brings its own seed and level, and we don't record over it
+        let (seed, level_name) = match &replay {
+            Some(replay) => (replay.seed, replay.level.clone()),
+            None => (CONFIG.session_seed(), CONFIG.level_name()),
+        };
+
+        let level = level_name.and_then(|name| {
+            let level = levels.find(&name).cloned();
+            if level.is_none() {
+                log::warn!("no such level: {}, using the endless bomber", name);
+            }
+            level
+        });
+
+        let mut background = Background::new(&mut renderer, &media, seed).unwrap_ext();
+        background.start_run(level.as_ref());
+        world.add_unique(background);
 
         world.add_unique_non_send_sync(media);
         world.add_unique_non_send_sync(renderer);
         world.add_unique_non_send_sync(dom);
         world.add_unique_non_send_sync(audio_player);
-        world.add_unique_non_send_sync(Rand::new());
-        world.add_unique(AudioEventQueue::new());
-        world.add_unique(background);
         world.add_unique(enemy_spawner);
-        world.add_unique(projectile_spawner);
-        world.add_unique(explosion_spawner);
-        world.add_unique_non_send_sync(CollisionEventQueue::new());
+        world.add_unique(projectile_sprites);
+        world.add_unique(explosion_sprites);
         world.add_unique(collision_debugger);
-        world.add_unique(Bomber::new());
+        world.add_unique(PostSettings::new());
+        world.add_unique(PostEffects::default());
+        world.add_unique(particle_system);
         world.add_unique(InputQueue::new());
         world.add_unique(camera);
+        world.add_unique(CameraEffects::default());
         world.add_unique(BeginTick::default());
-        world.add_unique(UpdateTick::default());
         world.add_unique(DrawTick::default());
         world.add_unique(EndTick::default());
-        world.add_unique(PauseTick::Running);
+
+        world.add_unique(match replay {
+            Some(_) => InputRecorder::disabled(),
+            None => InputRecorder::new(seed, level.as_ref().map(|level| level.name.clone())),
+        });
+        world.add_unique(InputReplay::new(replay));
+
+        // everything the simulation systems need, independent of the browser
+        init_sim_world(
+            &world,
+            seed,
+            difficulty_presets,
+            collision_rules,
+            weapon_stats,
+            level,
+        );
     }
 
     Workload::new("controller")
+        .with_system(input_replay_sys)
         .with_system(controller_process_queue_sys)
         .with_system(audio_event_process_sys)
+        .with_system(ui_event_process_sys)
         .add_to_world(&world)
         .unwrap_ext();
 
     Workload::new("begin")
         .with_system(background_move_sys)
-        .with_system(enemy_select_event_sys)
-        .with_system(enemy_animation_sys)
-        .with_system(launcher_animation_sys)
-        .with_system(explosion_animation_sys)
         .add_to_world(&world)
         .unwrap_ext();
 
-    Workload::new("update")
-        .with_system(game_over_sys)
-        .with_system(enemy_destroy_event_sys)
-        .with_system(enemy_controller_physics_sys)
-        .with_system(enemy_position_sys)
-        .with_system(bomber_drop_sys)
-        .with_system(explosion_spawn_sys)
-        .with_system(projectile_spawn_sys)
-        .with_system(projectile_physics_sys)
-        .with_system(flush_layout_sys)
-        .with_system(local_transform_sys)
-        .with_system(world_transform_sys)
-        .with_system(update_collider_sys)
-        .with_system(detect_geometric_collision_sys)
-        .with_system(pixel_collision_check_sys)
-        .with_system(delete_sys)
-        .add_to_world(&world)
-        .unwrap_ext();
+    // gameplay animations run here, in the fixed step, since they spawn projectiles etc.
+    // and that needs to be deterministic for replays
+    update_workload(
+        "update",
+        Workload::new("gpu_collision").with_system(pixel_collision_check_sys),
+        Workload::new("hit_effects")
+            .with_system(post_hit_sys)
+            .with_system(camera_hit_sys),
+    )
+    .add_to_world(&world)
+    .unwrap_ext();
 
     Workload::new("draw")
+        .with_system(camera_effects_sys)
         .with_system(camera_update_ubo_sys)
         .with_system(pixel_collision_render_sys)
+        .with_system(particle_attach_sys)
+        .with_system(particle_update_sys)
+        .with_system(post_effects_sys)
         .with_system(render_sys)
         .add_to_world(&world)
         .unwrap_ext();
 
-    Workload::new("end").add_to_world(&world).unwrap_ext();
+    Workload::new("end")
+        .with_system(draw_stats_log_sys)
+        .add_to_world(&world)
+        .unwrap_ext();
 
     init_scenegraph::<Vec3, Quat, Mat4, f32>(&world);
 
@@ -198,16 +224,17 @@ async fn start() {
         move |_: &web_sys::Event| {
             // This is a very heavy operation - at least in theory, for example we may want to recreate framebuffers
             // So only run it when the event is really triggered (i.e. startup, user resizes browser, etc.)
+            // the world is always the design resolution, this is just about how it's shown
             world.run(
-                |dom: DomView,
-                 mut renderer: RendererViewMut,
-                 mut camera: CameraViewMut,
-                 mut background: BackgroundViewMut| {
+                |dom: DomView, mut renderer: RendererViewMut, mut camera: CameraViewMut| {
                     let (width, height) = dom.window_size();
-                    renderer.resize(ResizeStrategy::All(width, height));
+                    let fit = ViewportFit::new(CONFIG.scale_mode, width as f64, height as f64);
+                    let (backing_width, backing_height) =
+                        fit.backing_size(dom.pixel_ratio().min(CONFIG.max_pixel_ratio));
+                    renderer.resize(ResizeStrategy::All(backing_width, backing_height));
                     renderer.resize_framebuffers();
-                    camera.resize(width as f64, height as f64);
-                    background.resize(width as f64, height as f64);
+                    dom.place_canvas(&fit);
+                    camera.resize(fit.view_width, fit.view_height);
                 },
             );
         }
@@ -221,6 +248,7 @@ async fn start() {
             let world = Arc::clone(&world);
             move |time, delta| {
                 world.run_workload("controller").unwrap_ext();
+                restart_if_requested(&world);
                 if *world.borrow::<PauseTickView>().unwrap_ext() == PauseTick::Running {
                     *world.borrow::<BeginTickViewMut>().unwrap_ext() = BeginTick { time, delta };
                     world.run_workload("begin").unwrap_ext();
@@ -231,16 +259,19 @@ async fn start() {
             let world = Arc::clone(&world);
 
             move |delta| {
+                // when replaying, inputs need to land on exactly the frame they were recorded on
+                // and there may be several update steps per "begin"
+                if world.borrow::<InputReplayView>().unwrap_ext().is_active() {
+                    world.run_workload("controller").unwrap_ext();
+                    restart_if_requested(&world);
+                }
                 if *world.borrow::<PauseTickView>().unwrap_ext() == PauseTick::Running {
-                    let viewport = world
+                    // the ui can start a run before a lost context has come back
+                    let context_lost = world
                         .borrow::<RendererViewMut>()
-                        .map(|renderer| renderer.get_viewport());
-                    if let Ok((_, _, viewport_width, viewport_height)) = viewport {
-                        *world.borrow::<UpdateTickViewMut>().unwrap_ext() = UpdateTick {
-                            delta,
-                            viewport_width: viewport_width as f64,
-                            viewport_height: viewport_height as f64,
-                        };
+                        .map_or(true, |renderer| renderer.is_context_lost());
+                    if !context_lost {
+                        *world.borrow::<UpdateTickViewMut>().unwrap_ext() = UpdateTick { delta };
                         world.run_workload("update").unwrap_ext();
                     }
                 }
@@ -249,11 +280,17 @@ async fn start() {
         {
             let world = Arc::clone(&world);
             move |interpolation| {
-                if *world.borrow::<PauseTickView>().unwrap_ext() == PauseTick::Running {
+                // still drawn after game over (but not updated), for the grayscale fade
+                if matches!(
+                    *world.borrow::<PauseTickView>().unwrap_ext(),
+                    PauseTick::Running | PauseTick::GameOver {}
+                ) {
                     let viewport = world
                         .borrow::<RendererViewMut>()
+                        .ok()
+                        .filter(|renderer| !renderer.is_context_lost())
                         .map(|renderer| renderer.get_viewport());
-                    if let Ok((_, _, viewport_width, viewport_height)) = viewport {
+                    if let Some((_, _, viewport_width, viewport_height)) = viewport {
                         *world.borrow::<DrawTickViewMut>().unwrap_ext() = DrawTick {
                             interpolation,
                             viewport_width: viewport_width as f64,
@@ -322,6 +359,39 @@ async fn start() {
         }
     };
 
+    // the default is to never give the context back, so the lost event has to be cancelled
+    let on_context_lost = {
+        let world = Arc::clone(&world);
+        move |event: &web_sys::Event| {
+            event.prevent_default();
+            context_lost(&world);
+        }
+    };
+
+    let on_context_restored = {
+        let world = Arc::clone(&world);
+        let on_resize = on_resize.clone();
+        move |event: &web_sys::Event| match rebuild_context(&world) {
+            Ok(()) => {
+                // which also brings back the framebuffers
+                on_resize(event);
+                resume_after_context_restored(&world);
+            }
+            Err(err) => {
+                log::error!("{:#}", err);
+                world
+                    .borrow::<DomView>()
+                    .unwrap_ext()
+                    .ui
+                    .phase
+                    .set(UiPhase::Error(format!(
+                        "Could not restore graphics: {:#}",
+                        err
+                    )));
+            }
+        }
+    };
+
     // these just run forever
     std::mem::forget(Box::new(tick));
     std::mem::forget(Box::new(InputListeners::new(Arc::clone(&world))));
@@ -337,6 +407,18 @@ async fn start() {
         on_visibility,
     )
     .forget();
+    EventListener::new(
+        &world.borrow::<DomView>().unwrap_ext().canvas,
+        "webglcontextlost",
+        on_context_lost,
+    )
+    .forget();
+    EventListener::new(
+        &world.borrow::<DomView>().unwrap_ext().canvas,
+        "webglcontextrestored",
+        on_context_restored,
+    )
+    .forget();
 
     // Update the UI - and we're off!
     world
@@ -345,9 +427,7 @@ async fn start() {
         .ui
         .phase
         .set(UiPhase::Playing(GameUi::new(Arc::clone(&world))));
-    spawn_enemies(&world);
-    spawn_launcher(&world, LauncherSide::Left);
-    spawn_launcher(&world, LauncherSide::Right);
+    spawn_level(&world);
 
     if let Some(phase) = CONFIG.initial_game_phase {
         world
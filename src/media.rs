@@ -9,9 +9,14 @@ use web_sys::{AudioBuffer, HtmlImageElement};
 
 use crate::{
     audio::AudioPlayer,
-    collision,
+    background::data::BackgroundLayers,
+    collision::{self, layer::CollisionRules},
     config::CONFIG,
+    difficulty::data::DifficultyPresets,
     dom::{ui::UiPhase, DomState},
+    enemy::attack::data::WeaponStats,
+    level::data::Levels,
+    particles::data::ParticleDefs,
     prelude::*,
 };
 
@@ -24,6 +29,17 @@ pub struct Media {
     pub terrorists: TerroristMedia,
     pub objects: ObjectMedia,
     pub audio: AudioMedia,
+    pub data: DataMedia,
+}
+
+// gameplay data, as opposed to images/audio
+pub struct DataMedia {
+    pub difficulty: DifficultyPresets,
+    pub levels: Levels,
+    pub collision: CollisionRules,
+    pub weapons: WeaponStats,
+    pub background: BackgroundLayers,
+    pub particles: ParticleDefs,
 }
 
 pub struct AudioMedia {
@@ -109,6 +125,23 @@ pub struct SpriteSheetMediaInfo {
     #[serde(rename = "SubTexture")]
     pub sub_textures: Vec<SpriteSheetSubtextureMediaInfo>,
 }
+impl SpriteSheetMediaInfo {
+    // where each cell is in the original image
+    pub fn cells(&self) -> Result<Vec<Bounds>> {
+        self.sub_textures
+            .iter()
+            .map(|info| {
+                Ok(Bounds {
+                    x: info.x.parse()?,
+                    y: info.y.parse()?,
+                    width: info.width.parse()?,
+                    height: info.height.parse()?,
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SpriteSheetSubtextureMediaInfo {
     pub name: String,
@@ -217,6 +250,31 @@ impl Media {
             config.image_url(&format!("object/rocket-good.png")),
         ));
 
+        urls.push(RawMedia::new_text(
+            "data-difficulty",
+            config.data_url("difficulty.json"),
+        ));
+        urls.push(RawMedia::new_text(
+            "data-levels",
+            config.data_url("levels.json"),
+        ));
+        urls.push(RawMedia::new_text(
+            "data-collision",
+            config.data_url("collision.json"),
+        ));
+        urls.push(RawMedia::new_text(
+            "data-weapons",
+            config.data_url("weapons.json"),
+        ));
+        urls.push(RawMedia::new_text(
+            "data-background",
+            config.data_url("background.json"),
+        ));
+        urls.push(RawMedia::new_text(
+            "data-particles",
+            config.data_url("particles.json"),
+        ));
+
         let audio_ctx = audio_player.mixer.clone_audio_ctx();
 
         let mut futures = urls
@@ -246,6 +304,11 @@ impl Media {
                             let info: SpriteSheetMediaInfo = result.json_from_str().await?;
                             anyhow::Ok((data.key, RawMediaResult::SpriteSheetInfo(info)))
                         }
+                        RawMediaKind::Text => {
+                            let result = loaders::fetch::fetch_url(&data.url).await?;
+                            let text = result.text().await?;
+                            anyhow::Ok((data.key, RawMediaResult::Text(text)))
+                        }
                     }
                 }
             })
@@ -455,11 +518,37 @@ impl Media {
                 .unwrap_audio_buffer(),
         };
 
+        let data = DataMedia {
+            difficulty: DifficultyPresets::from_json(
+                &results.remove("data-difficulty").unwrap_ext().unwrap_text(),
+            )
+            .context("difficulty.json")?,
+            levels: Levels::from_json(&results.remove("data-levels").unwrap_ext().unwrap_text())
+                .context("levels.json")?,
+            collision: CollisionRules::from_json(
+                &results.remove("data-collision").unwrap_ext().unwrap_text(),
+            )
+            .context("collision.json")?,
+            weapons: WeaponStats::from_json(
+                &results.remove("data-weapons").unwrap_ext().unwrap_text(),
+            )
+            .context("weapons.json")?,
+            background: BackgroundLayers::from_json(
+                &results.remove("data-background").unwrap_ext().unwrap_text(),
+            )
+            .context("background.json")?,
+            particles: ParticleDefs::from_json(
+                &results.remove("data-particles").unwrap_ext().unwrap_text(),
+            )
+            .context("particles.json")?,
+        };
+
         Ok(Self {
             bg,
             terrorists,
             objects,
             audio,
+            data,
         })
     }
 }
@@ -502,6 +591,14 @@ impl RawMedia {
             url,
         }
     }
+
+    fn new_text(key: impl ToString, url: String) -> Self {
+        Self {
+            key: key.to_string(),
+            kind: RawMediaKind::Text,
+            url,
+        }
+    }
 }
 
 enum RawMediaKind {
@@ -509,6 +606,7 @@ enum RawMediaKind {
     AudioBuffer,
     Image,
     SpriteSheetInfo,
+    Text,
 }
 
 enum RawMediaResult {
@@ -516,6 +614,7 @@ enum RawMediaResult {
     AudioBuffer(AudioBuffer),
     Image(HtmlImageElement),
     SpriteSheetInfo(SpriteSheetMediaInfo),
+    Text(String),
 }
 
 impl RawMediaResult {
@@ -546,4 +645,11 @@ impl RawMediaResult {
             _ => unreachable!("expected sprite sheet info"),
         }
     }
+
+    fn unwrap_text(self) -> String {
+        match self {
+            RawMediaResult::Text(text) => text,
+            _ => unreachable!("expected text"),
+        }
+    }
 }
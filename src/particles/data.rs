@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use awsm_web::webgl::{
+    PixelFormat, SimpleTextureOptions, TextureTarget, TextureWrapMode, WebGlTextureSource,
+};
+use serde::Deserialize;
+
+use super::sim::{Curve, EmitterDef, EmitterState, Particle, ParticleRng};
+use crate::{
+    atlas::data::{canvas_context, create_canvas},
+    prelude::*,
+    rand_helpers::Rand,
+    renderer::Renderer,
+};
+
+pub type ParticleSystemViewMut<'a> = UniqueViewMut<'a, ParticleSystem>;
+pub type ParticleSystemView<'a> = UniqueView<'a, ParticleSystem>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticleTexture {
+    // a blurry dot, for smoke and dust
+    Soft,
+    // a hard little chunk, for debris and dirt
+    Square,
+}
+
+// Which side of the sprites they're drawn on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticleLayer {
+    Back,
+    Front,
+}
+
+// One emitter definition from media/data/particles.json
+#[derive(Debug, Clone)]
+pub struct ParticleDef {
+    pub emitter: EmitterDef,
+    pub texture: ParticleTexture,
+    pub layer: ParticleLayer,
+}
+
+// All the definitions, the game looks them up by name when it attaches an emitter
+#[derive(Debug, Clone)]
+pub struct ParticleDefs {
+    defs: Vec<ParticleDef>,
+    names: HashMap<String, usize>,
+}
+
+// as it is in the json, curves are checked when it's turned into ParticleDefs
+#[derive(Deserialize)]
+struct ParticleDefsData {
+    emitters: HashMap<String, ParticleDefData>,
+}
+
+#[derive(Deserialize)]
+struct ParticleDefData {
+    #[serde(default)]
+    rate: f32,
+    #[serde(default)]
+    burst: u32,
+    #[serde(default)]
+    duration: Option<f32>,
+    lifetime: (f32, f32),
+    speed: (f32, f32),
+    #[serde(default)]
+    direction: f32,
+    #[serde(default)]
+    spread: f32,
+    #[serde(default)]
+    gravity: f32,
+    #[serde(default)]
+    drag: f32,
+    #[serde(default)]
+    spin: (f32, f32),
+    size: Vec<(f32, f32)>,
+    color: Vec<(f32, [f32; 4])>,
+    texture: ParticleTexture,
+    layer: ParticleLayer,
+}
+
+impl ParticleDefs {
+    // everything the game attaches itself (see particles/systems.rs)
+    pub const ROCKET_TRAIL: &'static str = "rocket_trail";
+    pub const BOMB_TRAIL: &'static str = "bomb_trail";
+    pub const EXPLOSION_DEBRIS: &'static str = "explosion_debris";
+    pub const EXPLOSION_DUST: &'static str = "explosion_dust";
+    pub const HIDE_DIRT: &'static str = "hide_dirt";
+    pub const REQUIRED: [&'static str; 5] = [
+        Self::ROCKET_TRAIL,
+        Self::BOMB_TRAIL,
+        Self::EXPLOSION_DEBRIS,
+        Self::EXPLOSION_DUST,
+        Self::HIDE_DIRT,
+    ];
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let data: ParticleDefsData = serde_json::from_str(json)?;
+
+        // sorted so the indices don't depend on the hashmap order
+        let mut emitters: Vec<(String, ParticleDefData)> = data.emitters.into_iter().collect();
+        emitters.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut defs = Vec::new();
+        let mut names = HashMap::new();
+        for (name, def) in emitters {
+            if def.lifetime.0 <= 0.0 || def.lifetime.0 > def.lifetime.1 {
+                return Err(anyhow!(
+                    "particle emitter {name} lifetime must be positive, min first"
+                ));
+            }
+            if def.rate < 0.0 || !(0.0..=1.0).contains(&def.drag) {
+                return Err(anyhow!(
+                    "particle emitter {name} has a negative rate or drag outside 0..1"
+                ));
+            }
+
+            let emitter = EmitterDef {
+                rate: def.rate,
+                burst: def.burst,
+                duration: def.duration,
+                lifetime: def.lifetime,
+                speed: def.speed,
+                direction: def.direction,
+                spread: def.spread,
+                gravity: def.gravity,
+                drag: def.drag,
+                spin: def.spin,
+                size: Curve::new(def.size).with_context(|| format!("{name} size"))?,
+                color: Curve::new(def.color).with_context(|| format!("{name} color"))?,
+            };
+
+            names.insert(name, defs.len());
+            defs.push(ParticleDef {
+                emitter,
+                texture: def.texture,
+                layer: def.layer,
+            });
+        }
+
+        for name in Self::REQUIRED {
+            if !names.contains_key(name) {
+                return Err(anyhow!("missing particle emitter: {name}"));
+            }
+        }
+
+        Ok(Self { defs, names })
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    pub fn get(&self, index: usize) -> &ParticleDef {
+        &self.defs[index]
+    }
+
+    pub fn count(&self) -> usize {
+        self.defs.len()
+    }
+}
+
+// Attached to anything that gives off particles, they're spawned at its WorldTransform
+// the particles themselves live in the ParticleSystem, so they outlive the entity
+#[derive(Component, Debug)]
+pub struct ParticleEmitter {
+    pub sources: Vec<ParticleSource>,
+}
+
+#[derive(Debug)]
+pub struct ParticleSource {
+    // into ParticleDefs
+    pub def: usize,
+    // in the entity's own space, e.g. the back of a rocket
+    pub offset: Vec3,
+    pub state: EmitterState,
+}
+
+impl ParticleEmitter {
+    // unknown names are skipped, ParticleDefs::from_json already made sure the ones we use are there
+    pub fn new(defs: &ParticleDefs, sources: &[(&str, Vec3)]) -> Self {
+        Self {
+            sources: sources
+                .iter()
+                .filter_map(|(name, offset)| {
+                    defs.index(name).map(|def| ParticleSource {
+                        def,
+                        offset: *offset,
+                        state: EmitterState::default(),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Component, Unique)]
+pub struct ParticleSystem {
+    pub defs: ParticleDefs,
+    // one pool per def, in the same order
+    pub pools: Vec<Vec<Particle>>,
+    pub rng: ParticleRng,
+    pub soft_texture_id: Id,
+    pub square_texture_id: Id,
+    // performance.now() of the last drawn frame
+    pub last_timestamp: Option<f64>,
+    // reused every frame, see ParticleSystem::render
+    pub(super) instances: Vec<f32>,
+}
+
+impl ParticleSystem {
+    // across all the pools, anything past this just doesn't spawn
+    pub const MAX_PARTICLES: usize = 4096;
+
+    pub fn new(renderer: &mut Renderer, defs: ParticleDefs) -> Result<Self> {
+        let pools = vec![Vec::new(); defs.count()];
+        Ok(Self {
+            pools,
+            defs,
+            rng: ParticleRng::new(Rand::random_seed() as u32),
+            soft_texture_id: particle_texture(renderer, ParticleTexture::Soft)?,
+            square_texture_id: particle_texture(renderer, ParticleTexture::Square)?,
+            last_timestamp: None,
+            instances: Vec::new(),
+        })
+    }
+
+    // the gl context was lost, the particles themselves carry on
+    pub fn rebuild_textures(&mut self, renderer: &mut Renderer) -> Result<()> {
+        self.soft_texture_id = particle_texture(renderer, ParticleTexture::Soft)?;
+        self.square_texture_id = particle_texture(renderer, ParticleTexture::Square)?;
+        Ok(())
+    }
+
+    // every live particle, e.g. on restart
+    pub fn clear(&mut self) {
+        for pool in self.pools.iter_mut() {
+            pool.clear();
+        }
+    }
+
+    pub fn texture_id(&self, texture: ParticleTexture) -> Id {
+        match texture {
+            ParticleTexture::Soft => self.soft_texture_id,
+            ParticleTexture::Square => self.square_texture_id,
+        }
+    }
+}
+
+// drawn once at startup, tinted per particle
+fn particle_texture(renderer: &mut Renderer, texture: ParticleTexture) -> Result<Id> {
+    let size = match texture {
+        ParticleTexture::Soft => 32,
+        ParticleTexture::Square => 8,
+    };
+    let canvas = create_canvas(size, size)?;
+    let ctx = canvas_context(&canvas)?;
+
+    match texture {
+        ParticleTexture::Soft => {
+            let center = size as f64 / 2.0;
+            let gradient = ctx
+                .create_radial_gradient(center, center, 0.0, center, center, center)
+                .map_err(|_| anyhow!("could not create particle gradient"))?;
+            gradient
+                .add_color_stop(0.0, "rgba(255, 255, 255, 1)")
+                .and_then(|_| gradient.add_color_stop(1.0, "rgba(255, 255, 255, 0)"))
+                .map_err(|_| anyhow!("could not create particle gradient"))?;
+            ctx.set_fill_style(&gradient);
+        }
+        ParticleTexture::Square => {
+            ctx.set_fill_style(&"white".into());
+        }
+    }
+    ctx.fill_rect(0.0, 0.0, size as f64, size as f64);
+
+    let texture_id = renderer.create_texture()?;
+    renderer.assign_simple_texture(
+        texture_id,
+        TextureTarget::Texture2d,
+        &SimpleTextureOptions {
+            pixel_format: PixelFormat::Rgba,
+            wrap_s: Some(TextureWrapMode::ClampToEdge),
+            wrap_t: Some(TextureWrapMode::ClampToEdge),
+            ..SimpleTextureOptions::default()
+        },
+        &WebGlTextureSource::CanvasElement(&canvas),
+    )?;
+
+    Ok(texture_id)
+}
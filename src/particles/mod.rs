@@ -0,0 +1,7 @@
+// Smoke trails, debris, dust and dirt
+// emitters are components (see ParticleEmitter), the definitions come from media/data/particles.json
+// and all the particles are drawn with one instanced call per definition
+pub mod data;
+pub mod render;
+pub mod sim;
+pub mod systems;
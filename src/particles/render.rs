@@ -0,0 +1,71 @@
+use awsm_web::webgl::{BeginMode, BlendFactor, BufferData, BufferTarget, BufferUsage, GlToggle};
+
+use super::data::{ParticleLayer, ParticleSystem};
+use crate::{prelude::*, renderer::Renderer};
+
+impl ParticleSystem {
+    // x, y, size, rotation (4), colour (4)
+    // the attribute layout in shaders.rs and particle.vert has to match
+    pub const FLOATS_PER_INSTANCE: usize = 8;
+    pub const INSTANCE_STRIDE: usize = Self::FLOATS_PER_INSTANCE * 4;
+
+    // one instanced draw per def that's on this layer and has anything alive
+    pub fn render(&mut self, renderer: &mut Renderer, layer: ParticleLayer) -> Result<()> {
+        let mut activated = false;
+
+        for (index, pool) in self.pools.iter().enumerate() {
+            let def = self.defs.get(index);
+            if def.layer != layer || pool.is_empty() {
+                continue;
+            }
+
+            if !activated {
+                renderer.toggle(GlToggle::Blend, true);
+                renderer.set_blend_func(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha);
+                renderer.set_depth_mask(false);
+                renderer.toggle(GlToggle::DepthTest, false);
+                let program = renderer.shaders.programs.particle.clone();
+                program.activate(renderer)?;
+                activated = true;
+            }
+
+            self.instances.clear();
+            for particle in pool {
+                let life = particle.life();
+                let size = def.emitter.size.sample(life);
+                let [r, g, b, a] = def.emitter.color.sample(life);
+                self.instances.extend_from_slice(&[
+                    particle.x,
+                    particle.y,
+                    size,
+                    particle.rotation,
+                    r,
+                    g,
+                    b,
+                    a,
+                ]);
+            }
+
+            renderer.upload_buffer(
+                renderer.buffers.particle_instances,
+                BufferData::new(
+                    &self.instances,
+                    BufferTarget::ArrayBuffer,
+                    BufferUsage::DynamicDraw,
+                ),
+            )?;
+            renderer.activate_texture_sampler_name(self.texture_id(def.texture), "u_sampler")?;
+            renderer.gl.gl.draw_arrays_instanced(
+                BeginMode::TriangleStrip as u32,
+                0,
+                4,
+                pool.len() as i32,
+            );
+
+            renderer.frame_draw_stats.draw_calls += 1;
+            renderer.frame_draw_stats.particles += pool.len() as u32;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+// The particle simulation itself: curves, emitters and moving particles along
+// nothing in here knows about the ECS or the renderer, positions are just world space, i.e. design units
+//
+// std only, so it can be tested natively (see tests/particles.rs)
+use std::fmt;
+
+pub trait Lerp: Copy {
+    fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for [f32; 4] {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        [
+            self[0].lerp(other[0], t),
+            self[1].lerp(other[1], t),
+            self[2].lerp(other[2], t),
+            self[3].lerp(other[3], t),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurveError {
+    Empty,
+    // keys have to be within 0..1
+    OutOfRange,
+    Unsorted,
+}
+
+impl fmt::Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "curve has no keys"),
+            Self::OutOfRange => write!(f, "curve keys must be within 0..1"),
+            Self::Unsorted => write!(f, "curve keys must be sorted"),
+        }
+    }
+}
+
+impl std::error::Error for CurveError {}
+
+// Keyframes over a particle's life, 0 is when it's born and 1 is when it dies
+// linear in between, and flat before the first key / after the last one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve<T> {
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: Lerp> Curve<T> {
+    pub fn new(keys: Vec<(f32, T)>) -> Result<Self, CurveError> {
+        if keys.is_empty() {
+            return Err(CurveError::Empty);
+        }
+        if keys.iter().any(|(t, _)| !(0.0..=1.0).contains(t)) {
+            return Err(CurveError::OutOfRange);
+        }
+        if keys.windows(2).any(|pair| pair[0].0 > pair[1].0) {
+            return Err(CurveError::Unsorted);
+        }
+
+        Ok(Self { keys })
+    }
+
+    pub fn constant(value: T) -> Self {
+        Self {
+            keys: vec![(0.0, value)],
+        }
+    }
+
+    pub fn sample(&self, t: f32) -> T {
+        let next = self.keys.iter().position(|(key_t, _)| *key_t > t);
+
+        match next {
+            None => self.keys[self.keys.len() - 1].1,
+            Some(0) => self.keys[0].1,
+            Some(index) => {
+                let (t0, a) = self.keys[index - 1];
+                let (t1, b) = self.keys[index];
+                a.lerp(b, (t - t0) / (t1 - t0))
+            }
+        }
+    }
+}
+
+// xorshift, not the seeded Rand since that has to stay in step for replays
+// and particles are purely visual
+#[derive(Debug, Clone)]
+pub struct ParticleRng(u32);
+
+impl ParticleRng {
+    pub fn new(seed: u32) -> Self {
+        // zero would get stuck
+        Self(seed.max(1))
+    }
+
+    // 0..1
+    pub fn next_f32(&mut self) -> f32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.0 = x;
+
+        (x >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    pub fn range(&mut self, (min, max): (f32, f32)) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    // pixels per second
+    pub vx: f32,
+    pub vy: f32,
+    // radians, and radians per second
+    pub rotation: f32,
+    pub spin: f32,
+    // seconds
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+impl Particle {
+    // 0..1, what the curves are sampled with
+    pub fn life(&self) -> f32 {
+        (self.age / self.lifetime).min(1.0)
+    }
+}
+
+// Everything about how an emitter spawns and moves its particles
+// (how they look is up to whoever draws them, see the curves)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmitterDef {
+    // particles per second, for as long as the emitter runs
+    pub rate: f32,
+    // spawned all at once, as soon as it starts
+    pub burst: u32,
+    // seconds, None keeps going for as long as the emitter is around
+    pub duration: Option<f32>,
+    // seconds, picked between (min, max) for each particle
+    pub lifetime: (f32, f32),
+    // pixels per second
+    pub speed: (f32, f32),
+    // degrees, 0 is to the right and 90 is up, relative to whatever it's attached to
+    pub direction: f32,
+    // degrees, the whole width of the cone
+    pub spread: f32,
+    // pixels per second squared, negative pulls down
+    pub gravity: f32,
+    // how much of the velocity is lost per second, 0..1
+    pub drag: f32,
+    // degrees per second
+    pub spin: (f32, f32),
+    // pixels across
+    pub size: Curve<f32>,
+    // rgba
+    pub color: Curve<[f32; 4]>,
+}
+
+impl EmitterDef {
+    // `rotation` is the radians of whatever it's attached to
+    pub fn spawn(&self, rng: &mut ParticleRng, x: f32, y: f32, rotation: f32) -> Particle {
+        let half_spread = self.spread / 2.0;
+        let angle =
+            (self.direction + rng.range((-half_spread, half_spread))).to_radians() + rotation;
+        let speed = rng.range(self.speed);
+
+        Particle {
+            x,
+            y,
+            vx: angle.cos() * speed,
+            vy: angle.sin() * speed,
+            rotation: rng.range((0.0, std::f32::consts::TAU)),
+            spin: rng.range(self.spin).to_radians(),
+            age: 0.0,
+            lifetime: rng.range(self.lifetime).max(0.001),
+        }
+    }
+}
+
+// Where one emitter is at, the definition is shared
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmitterState {
+    // seconds
+    pub elapsed: f32,
+    // part of a particle that didn't make it into the last step
+    pub carry: f32,
+    pub started: bool,
+}
+
+impl EmitterState {
+    // how many particles to spawn for this step of `dt` seconds
+    pub fn advance(&mut self, def: &EmitterDef, dt: f32) -> u32 {
+        let mut count = 0;
+        if !self.started {
+            self.started = true;
+            count += def.burst;
+        }
+
+        // only the part of the step that's still within the duration
+        let active = match def.duration {
+            Some(duration) => (duration - self.elapsed).clamp(0.0, dt),
+            None => dt,
+        };
+        self.elapsed += dt;
+
+        let wanted = def.rate * active + self.carry;
+        let whole = wanted.floor();
+        self.carry = wanted - whole;
+
+        count + whole as u32
+    }
+
+    pub fn is_finished(&self, def: &EmitterDef) -> bool {
+        match def.duration {
+            Some(duration) => self.started && self.elapsed >= duration,
+            None => false,
+        }
+    }
+}
+
+// moves everything on by `dt` seconds, and drops whatever's past its lifetime
+pub fn step(particles: &mut Vec<Particle>, def: &EmitterDef, dt: f32) {
+    let damping = (1.0 - def.drag).clamp(0.0, 1.0).powf(dt);
+
+    particles.retain_mut(|particle| {
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            return false;
+        }
+
+        particle.vy += def.gravity * dt;
+        particle.vx *= damping;
+        particle.vy *= damping;
+        particle.x += particle.vx * dt;
+        particle.y += particle.vy * dt;
+        particle.rotation += particle.spin * dt;
+
+        true
+    });
+}
@@ -0,0 +1,143 @@
+use nalgebra::Point3;
+
+use super::{
+    data::{
+        ParticleDefs, ParticleEmitter, ParticleSystem, ParticleSystemView, ParticleSystemViewMut,
+    },
+    sim::step,
+};
+use crate::{
+    dom::DomView,
+    enemy::data::Enemy,
+    explosion::data::{Explosion, ExplosionSpritesView},
+    prelude::*,
+    projectiles::data::{Projectile, ProjectileMovement},
+};
+
+// Hooks the built-in effects up to whatever should be giving them off
+// trails on rockets and bombs, debris and dust on explosions, and dirt while an enemy is hiding
+pub fn particle_attach_sys(
+    particles: ParticleSystemView,
+    mut emitters: ViewMut<ParticleEmitter>,
+    projectiles: View<Projectile>,
+    explosions: View<Explosion>,
+    enemies: View<Enemy>,
+    explosion_sprites: ExplosionSpritesView,
+) {
+    let defs = &particles.defs;
+    let mut to_add = Vec::new();
+
+    for (id, projectile) in projectiles.iter().with_id() {
+        if emitters.contains(id) {
+            continue;
+        }
+        // rockets point up with the exhaust at the bottom, bombs point down with the fins at the top
+        // the def's direction is what sends the smoke back out behind them
+        let source = match projectile.movement {
+            ProjectileMovement::Rocket { .. } => (
+                ParticleDefs::ROCKET_TRAIL,
+                Vec3::new(projectile.width / 2.0, 0.0, 0.0),
+            ),
+            ProjectileMovement::Bomb { .. } => (
+                ParticleDefs::BOMB_TRAIL,
+                Vec3::new(projectile.width / 2.0, projectile.height, 0.0),
+            ),
+            ProjectileMovement::Bullet { .. } => continue,
+        };
+        to_add.push((id, ParticleEmitter::new(defs, &[source])));
+    }
+
+    for (id, _) in explosions.iter().with_id() {
+        if emitters.contains(id) {
+            continue;
+        }
+        // explosions are placed by their corner, see explosion_spawn_sys
+        let center = Vec3::new(
+            explosion_sprites.spritesheet.max_cell_width / 2.0,
+            explosion_sprites.spritesheet.max_cell_height / 2.0,
+            0.0,
+        );
+        to_add.push((
+            id,
+            ParticleEmitter::new(
+                defs,
+                &[
+                    (ParticleDefs::EXPLOSION_DEBRIS, center),
+                    (ParticleDefs::EXPLOSION_DUST, center),
+                ],
+            ),
+        ));
+    }
+
+    let mut to_remove = Vec::new();
+    for (id, enemy) in enemies.iter().with_id() {
+        // kicked up once when they go down, and gone again once they're back up
+        match (enemy.controller().hiding().is_some(), emitters.contains(id)) {
+            (true, false) => {
+                let feet = Vec3::new(enemy.spritesheet().max_cell_width / 2.0, 0.0, 0.0);
+                to_add.push((
+                    id,
+                    ParticleEmitter::new(defs, &[(ParticleDefs::HIDE_DIRT, feet)]),
+                ));
+            }
+            (false, true) => to_remove.push(id),
+            _ => {}
+        }
+    }
+
+    for (id, emitter) in to_add {
+        emitters.add_component_unchecked(id, emitter);
+    }
+    for id in to_remove {
+        emitters.remove(id);
+    }
+}
+
+// Runs on every drawn frame by the wall clock, like post_effects_sys
+// particles are purely visual, so none of this feeds back into the simulation
+pub fn particle_update_sys(
+    dom: DomView,
+    mut particles: ParticleSystemViewMut,
+    mut emitters: ViewMut<ParticleEmitter>,
+    world_transforms: View<WorldTransform>,
+) {
+    let now = dom.window.performance().unwrap_ext().now();
+    // capped, so that coming back from a pause doesn't fast-forward everything
+    let elapsed = particles
+        .last_timestamp
+        .map_or(0.0, |last| now - last)
+        .clamp(0.0, 100.0);
+    particles.last_timestamp = Some(now);
+    let dt = (elapsed / 1000.0) as f32;
+
+    let ParticleSystem {
+        defs, pools, rng, ..
+    } = &mut *particles;
+
+    for (index, pool) in pools.iter_mut().enumerate() {
+        step(pool, &defs.get(index).emitter, dt);
+    }
+
+    let mut room =
+        ParticleSystem::MAX_PARTICLES.saturating_sub(pools.iter().map(|pool| pool.len()).sum());
+
+    for (emitter, world_transform) in (&mut emitters, &world_transforms).iter() {
+        // which way it's facing, e.g. a rocket's heading
+        let rotation = world_transform[(1, 0)].atan2(world_transform[(0, 0)]);
+
+        for source in emitter.sources.iter_mut() {
+            let def = &defs.get(source.def).emitter;
+            let count = (source.state.advance(def, dt) as usize).min(room);
+            if count == 0 {
+                continue;
+            }
+            room -= count;
+
+            let origin = world_transform.transform_point(&Point3::from(source.offset));
+            let pool = &mut pools[source.def];
+            for _ in 0..count {
+                pool.push(def.spawn(rng, origin.x, origin.y, rotation));
+            }
+        }
+    }
+}
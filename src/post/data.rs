@@ -0,0 +1,185 @@
+use crate::{config::CONFIG, prelude::*};
+
+pub type PostSettingsViewMut<'a> = UniqueViewMut<'a, PostSettings>;
+pub type PostSettingsView<'a> = UniqueView<'a, PostSettings>;
+pub type PostEffectsViewMut<'a> = UniqueViewMut<'a, PostEffects>;
+pub type PostEffectsView<'a> = UniqueView<'a, PostEffects>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PostPass {
+    // glow around bright things, mostly explosions
+    Bloom,
+    // a slightly warmer, punchier look
+    Grade,
+    // rgb split and a white flash, on impact
+    Chromatic,
+    Vignette,
+    // fades out the colour on game over
+    Grayscale,
+}
+
+impl PostPass {
+    // also the order they run in
+    pub const ALL: [PostPass; 5] = [
+        Self::Bloom,
+        Self::Grade,
+        Self::Chromatic,
+        Self::Vignette,
+        Self::Grayscale,
+    ];
+
+    // same as in the config, e.g. ?post_fx=bloom,vignette
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bloom => "bloom",
+            Self::Grade => "grade",
+            Self::Chromatic => "chromatic",
+            Self::Vignette => "vignette",
+            Self::Grayscale => "grayscale",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Bloom => "Bloom",
+            Self::Grade => "Colour grade",
+            Self::Chromatic => "Impact flash",
+            Self::Vignette => "Vignette",
+            Self::Grayscale => "Game over fade",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostQuality {
+    // straight to the screen, no passes at all
+    Off,
+    Low,
+    High,
+}
+
+impl PostQuality {
+    pub const ALL: [PostQuality; 3] = [Self::Off, Self::Low, Self::High];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Low => "low",
+            Self::High => "high",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Low => "Low",
+            Self::High => "High",
+        }
+    }
+
+    // how far out bloom looks (in pixels), and how many pixels it skips between samples
+    // low is 5x5 samples instead of 9x9, over the same area
+    pub fn bloom_kernel(self) -> (f32, f32) {
+        match self {
+            Self::Off => (0.0, 1.0),
+            Self::Low => (4.0, 2.0),
+            Self::High => (4.0, 1.0),
+        }
+    }
+}
+
+// What the player picked, starts out from the config and can be changed from the pause screen
+#[derive(Component, Unique, Debug, Clone, PartialEq)]
+pub struct PostSettings {
+    // always in PostPass::ALL order
+    pub passes: Vec<PostPass>,
+    pub quality: PostQuality,
+}
+
+impl PostSettings {
+    pub fn new() -> Self {
+        Self {
+            passes: PostPass::ALL
+                .into_iter()
+                .filter(|pass| CONFIG.post_passes.contains(pass))
+                .collect(),
+            quality: CONFIG.post_quality,
+        }
+    }
+
+    pub fn is_enabled(&self, pass: PostPass) -> bool {
+        self.passes.contains(&pass)
+    }
+
+    pub fn toggle(&mut self, pass: PostPass) {
+        let enabled = !self.is_enabled(pass);
+        self.passes = PostPass::ALL
+            .into_iter()
+            .filter(|&other| {
+                if other == pass {
+                    enabled
+                } else {
+                    self.is_enabled(other)
+                }
+            })
+            .collect();
+    }
+}
+
+// One pass of the chain, for this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostPassParams {
+    pub pass: PostPass,
+    // 0..1
+    pub amount: f32,
+    pub quality: PostQuality,
+}
+
+// How strong the reactive effects are right now, all 0..1
+// gameplay pushes these up (see post/systems.rs) and they settle back down over time
+#[derive(Component, Unique, Debug, Default)]
+pub struct PostEffects {
+    pub bloom: f32,
+    pub flash: f32,
+    pub grayscale: f32,
+    // performance.now() of the last drawn frame
+    pub last_timestamp: Option<f64>,
+}
+
+impl PostEffects {
+    // some glow even without explosions, so bright sky and muzzle flashes still pop a little
+    pub const BLOOM_BASE: f32 = 0.25;
+    pub const BLOOM_PER_EXPLOSION: f32 = 0.25;
+    // ms to ease halfway towards the target
+    pub const BLOOM_EASE: f32 = 150.0;
+    // ms for a full flash to go away
+    pub const FLASH_DURATION: f32 = 250.0;
+    // ms from game over to fully gray
+    pub const GRAYSCALE_DURATION: f32 = 1500.0;
+
+    // the passes to run this frame, anything that wouldn't change the picture is skipped
+    pub fn passes(&self, settings: &PostSettings) -> Vec<PostPassParams> {
+        if settings.quality == PostQuality::Off {
+            return Vec::new();
+        }
+
+        settings
+            .passes
+            .iter()
+            .filter_map(|&pass| {
+                let amount = match pass {
+                    PostPass::Bloom => self.bloom,
+                    PostPass::Chromatic => self.flash,
+                    PostPass::Grayscale => self.grayscale,
+                    PostPass::Grade | PostPass::Vignette => 1.0,
+                };
+
+                (amount > 0.0).then_some(PostPassParams {
+                    pass,
+                    amount,
+                    quality: settings.quality,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,4 @@
+// Full-screen passes that run on the finished frame, before it's put on screen
+// the gameplay side only sets how strong each effect is, the renderer runs the chain (see renderer/framebuffers.rs)
+pub mod data;
+pub mod systems;
@@ -0,0 +1,53 @@
+use crate::{
+    dom::DomView,
+    explosion::data::Explosion,
+    hit::data::HitEventQueueView,
+    prelude::*,
+    tick::{PauseTick, PauseTickView},
+};
+
+use super::data::{PostEffects, PostEffectsViewMut};
+
+// Kicks off the impact flash, harder when a bomb is involved since that's what the player is dodging
+pub fn post_hit_sys(hit_events: HitEventQueueView, mut effects: PostEffectsViewMut) {
+    for event in hit_events.iter() {
+        let flash = if event.attacker_kind.is_bomb() || event.victim_kind.is_bomb() {
+            1.0
+        } else {
+            0.5
+        };
+        effects.flash = effects.flash.max(flash);
+    }
+}
+
+// Runs on every drawn frame, including after game over when the fixed step has stopped
+// so it goes by the wall clock rather than the update tick
+pub fn post_effects_sys(
+    dom: DomView,
+    mut effects: PostEffectsViewMut,
+    explosions: View<Explosion>,
+    pause_tick: PauseTickView,
+) {
+    let now = dom.window.performance().unwrap_ext().now();
+    // capped, so that coming back from a pause doesn't skip the effects straight to the end
+    let elapsed = effects
+        .last_timestamp
+        .map_or(0.0, |last| (now - last) as f32)
+        .clamp(0.0, 100.0);
+    effects.last_timestamp = Some(now);
+
+    let bloom_target = (PostEffects::BLOOM_BASE
+        + explosions.iter().count() as f32 * PostEffects::BLOOM_PER_EXPLOSION)
+        .min(1.0);
+    let ease = 1.0 - 0.5f32.powf(elapsed / PostEffects::BLOOM_EASE);
+    effects.bloom += (bloom_target - effects.bloom) * ease;
+
+    effects.flash = (effects.flash - elapsed / PostEffects::FLASH_DURATION).max(0.0);
+
+    effects.grayscale = match *pause_tick {
+        PauseTick::GameOver {} => {
+            (effects.grayscale + elapsed / PostEffects::GRAYSCALE_DURATION).min(1.0)
+        }
+        _ => 0.0,
+    };
+}
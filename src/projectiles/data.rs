@@ -1,9 +1,13 @@
-use awsm_web::webgl::{
-    PixelFormat, SimpleTextureOptions, TextureTarget, TextureWrapMode, WebGlTextureSource,
-};
+use web_sys::HtmlImageElement;
 
 use crate::{
-    enemy::launcher::data::LauncherSide, media::Media, prelude::*, renderer::Renderer,
+    atlas::data::TextureAtlas,
+    collision::mask::AlphaMask,
+    enemy::{data::EnemyKind, launcher::data::LauncherSide},
+    level::data::BombKind,
+    media::Media,
+    prelude::*,
+    renderer::uvs::Uvs,
     spritesheet::SpriteSheet,
 };
 
@@ -11,8 +15,24 @@ use crate::{
 pub struct Projectile {
     pub width: f32,
     pub height: f32,
-    pub texture_id: Id,
+    // where the image is in the texture atlas
+    pub page: usize,
+    pub uvs: [f32; 8],
+    pub mask: Arc<AlphaMask>,
     pub movement: ProjectileMovement,
+    // the kind of enemy that fired it, None for bombs
+    pub shooter: Option<EnemyKind>,
+}
+
+impl Projectile {
+    // how much health it takes off an enemy on a hit
+    pub fn damage(&self) -> f32 {
+        match self.movement {
+            ProjectileMovement::Bomb { kind, .. } => kind.damage(),
+            ProjectileMovement::Rocket { .. } => 30.0,
+            ProjectileMovement::Bullet { .. } => 10.0,
+        }
+    }
 }
 
 pub enum ProjectileMovement {
@@ -29,89 +49,101 @@ pub enum ProjectileMovement {
     Bomb {
         velocity: Vec3,
         acceleration: Vec3,
+        kind: BombKind,
     },
 }
 
 pub type ProjectileSpawnerViewMut<'a> = UniqueViewMut<'a, ProjectileSpawner>;
 pub type ProjectileSpawnerView<'a> = UniqueView<'a, ProjectileSpawner>;
 
-#[derive(Component, Unique)]
+// just the queue, so it's part of the sim (see ProjectileSprites for what they look like)
+#[derive(Component, Unique, Default)]
 pub struct ProjectileSpawner {
     pub to_spawn: Vec<ProjectileToSpawn>,
-    pub bad_rocket_width: f32,
-    pub bad_rocket_height: f32,
-    pub bad_rocket_texture_id: Id,
-    pub good_rocket_width: f32,
-    pub good_rocket_height: f32,
-    pub good_rocket_texture_id: Id,
-    pub bullet_width: f32,
-    pub bullet_height: f32,
-    pub bullet_texture_id: Id,
 }
 
 impl ProjectileSpawner {
-    pub fn new(renderer: &mut Renderer, media: &Media) -> Result<Self> {
-        let bad_rocket_texture_id = renderer.create_texture()?;
-
-        renderer.assign_simple_texture(
-            bad_rocket_texture_id,
-            TextureTarget::Texture2d,
-            &SimpleTextureOptions {
-                pixel_format: PixelFormat::Rgba,
-                wrap_s: Some(TextureWrapMode::ClampToEdge),
-                wrap_t: Some(TextureWrapMode::ClampToEdge),
-                ..SimpleTextureOptions::default()
-            },
-            &WebGlTextureSource::ImageElement(&media.objects.rocket_bad_img),
-        )?;
-
-        let bullet_texture_id = renderer.create_texture()?;
-
-        renderer.assign_simple_texture(
-            bullet_texture_id,
-            TextureTarget::Texture2d,
-            &SimpleTextureOptions {
-                pixel_format: PixelFormat::Rgba,
-                wrap_s: Some(TextureWrapMode::ClampToEdge),
-                wrap_t: Some(TextureWrapMode::ClampToEdge),
-                ..SimpleTextureOptions::default()
-            },
-            &WebGlTextureSource::ImageElement(&media.objects.bullet_img),
-        )?;
-
-        let good_rocket_texture_id = renderer.create_texture()?;
-
-        renderer.assign_simple_texture(
-            good_rocket_texture_id,
-            TextureTarget::Texture2d,
-            &SimpleTextureOptions {
-                pixel_format: PixelFormat::Rgba,
-                wrap_s: Some(TextureWrapMode::ClampToEdge),
-                wrap_t: Some(TextureWrapMode::ClampToEdge),
-                ..SimpleTextureOptions::default()
-            },
-            &WebGlTextureSource::ImageElement(&media.objects.rocket_good_img),
-        )?;
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+pub type ProjectileSpritesView<'a> = UniqueView<'a, ProjectileSprites>;
+
+#[derive(Component, Unique)]
+pub struct ProjectileSprites {
+    pub bad_rocket: ProjectileSprite,
+    pub good_rocket: ProjectileSprite,
+    pub bullet: ProjectileSprite,
+}
+
+impl ProjectileSprites {
+    pub fn new(atlas: &TextureAtlas, media: &Media) -> Result<Self> {
+        let objects = &media.objects;
 
         Ok(Self {
-            to_spawn: vec![],
-            bad_rocket_width: media.objects.rocket_bad_img.width() as f32,
-            bad_rocket_height: media.objects.rocket_bad_img.height() as f32,
-            bad_rocket_texture_id,
-            bullet_width: media.objects.bullet_img.width() as f32,
-            bullet_height: media.objects.bullet_img.height() as f32,
-            bullet_texture_id,
-            good_rocket_width: media.objects.rocket_good_img.width() as f32,
-            good_rocket_height: media.objects.rocket_good_img.height() as f32,
-            good_rocket_texture_id,
+            bad_rocket: ProjectileSprite::new(atlas, &objects.rocket_bad_img)?,
+            good_rocket: ProjectileSprite::new(atlas, &objects.rocket_good_img)?,
+            bullet: ProjectileSprite::new(atlas, &objects.bullet_img)?,
         })
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct ProjectileSprite {
+    pub width: f32,
+    pub height: f32,
+    pub page: usize,
+    pub uvs: [f32; 8],
+    pub mask: Arc<AlphaMask>,
+}
+
+impl ProjectileSprite {
+    pub fn new(atlas: &TextureAtlas, img: &HtmlImageElement) -> Result<Self> {
+        let region = atlas.image(img)?;
+
+        Ok(Self {
+            width: img.width() as f32,
+            height: img.height() as f32,
+            page: region.page,
+            uvs: region.uvs().data,
+            mask: Arc::new(AlphaMask::from_image(img)?),
+        })
+    }
+
+    // without the image, e.g. headless, it's just a solid box covering the whole texture
+    pub fn solid(width: u32, height: u32) -> Self {
+        Self {
+            width: width as f32,
+            height: height as f32,
+            page: 0,
+            uvs: Uvs::new(
+                width as f32,
+                height as f32,
+                &Bounds {
+                    x: 0.0,
+                    y: 0.0,
+                    width: width as f64,
+                    height: height as f64,
+                },
+            )
+            .data,
+            mask: Arc::new(AlphaMask::solid(width, height)),
+        }
+    }
+}
+
 pub enum ProjectileToSpawn {
-    BadRocketFromGround { side: LauncherSide },
+    BadRocketFromGround {
+        side: LauncherSide,
+    },
     BadRocketFromEnemy {},
     Bullet {},
 
-    Bomb {},
+    // the bomber (or level) decides where (0..1 across the screen) and how fast
+    Bomb {
+        x: f32,
+        acceleration: f32,
+        kind: BombKind,
+    },
 }
@@ -5,13 +5,17 @@ use serde::de::value::MapAccessDeserializer;
 use shipyard_scenegraph::traits::required::Vec3Ext;
 
 use super::data::{
-    Projectile, ProjectileMovement, ProjectileSpawner, ProjectileSpawnerViewMut, ProjectileToSpawn,
+    Projectile, ProjectileMovement, ProjectileSpawnerViewMut, ProjectileSprites,
+    ProjectileSpritesView, ProjectileToSpawn,
 };
 use crate::{
-    collision::data::Collider,
+    collision::{
+        data::Collider,
+        layer::{CollisionLayer, CollisionLayerKind, CollisionRulesView},
+    },
     delete::data::MarkForDeletion,
     enemy::{
-        data::Enemy,
+        data::{Enemy, EnemyKind},
         launcher::data::{EnemyLauncher, LauncherSide},
         physics::data::EnemyDirection,
     },
@@ -26,59 +30,69 @@ pub fn projectile_spawn_sys(
     mut projectiles: ViewMut<Projectile>,
     mut positions: ViewMut<LayoutPosition>,
     mut anchors: ViewMut<LayoutAnchor>,
-    mut colliders: ViewMut<Collider>,
+    // to allow more args
+    collision_group: (
+        ViewMut<Collider>,
+        ViewMut<CollisionLayer>,
+        CollisionRulesView,
+    ),
     mut sg_storages: SceneGraphStoragesMut,
-    mut rand: RandViewMut,
     enemies: View<Enemy>,
     launcher: View<EnemyLauncher>,
-    tick: UpdateTickView,
+    sprites: ProjectileSpritesView,
 ) {
-    let ProjectileSpawner {
-        good_rocket_height,
-        good_rocket_width,
-        good_rocket_texture_id,
-        bad_rocket_width,
-        bad_rocket_height,
-        bad_rocket_texture_id,
-        bullet_width,
-        bullet_height,
-        bullet_texture_id,
-        ..
-    } = *spawner;
+    let (mut colliders, mut collision_layers, collision_rules) = collision_group;
+    let ProjectileSprites {
+        bad_rocket,
+        good_rocket,
+        bullet,
+    } = &*sprites;
 
     for to_spawn in spawner.to_spawn.drain(..) {
         match to_spawn {
-            ProjectileToSpawn::Bomb {} => {
+            ProjectileToSpawn::Bomb {
+                x,
+                acceleration,
+                kind,
+            } => {
                 //let scale = rand::gen_range(0.3..1.0);
                 let entity = sg_storages.spawn_child_trs(None, None, None, None);
 
-                let pos = Vec3::new(rand.gen_range(0.0..1.0), 1.0, 0.0);
+                let pos = Vec3::new(x, 1.0, 0.0);
                 // TODO - get correct, try with pos 0.0 and 1.0
-                let anchor = Vec3::new(-good_rocket_width / 2.0, 0.0, 0.0);
+                let width = good_rocket.width * kind.scale();
+                let height = good_rocket.height * kind.scale();
+                let anchor = Vec3::new(-width / 2.0, 0.0, 0.0);
                 let velocity = Vec3::new(0.0, -0.0000001, 0.0);
-                let acceleration = Vec3::new(0.0, rand.gen_range(-0.0000001..-0.00000001), 0.0);
+                let acceleration = Vec3::new(0.0, acceleration, 0.0);
 
                 (
                     &mut projectiles,
                     &mut positions,
                     &mut anchors,
                     &mut colliders,
+                    &mut collision_layers,
                 )
                     .add_component_unchecked(
                         entity,
                         (
                             Projectile {
-                                width: good_rocket_width,
-                                height: good_rocket_height,
-                                texture_id: good_rocket_texture_id,
+                                width,
+                                height,
+                                page: good_rocket.page,
+                                uvs: good_rocket.uvs,
+                                mask: good_rocket.mask.clone(),
                                 movement: ProjectileMovement::Bomb {
                                     velocity,
                                     acceleration,
+                                    kind,
                                 },
+                                shooter: None,
                             },
                             LayoutPosition::new(pos),
                             LayoutAnchor::new(anchor),
                             Collider::default(),
+                            collision_rules.layer(CollisionLayerKind::Bomb),
                         ),
                     );
             }
@@ -94,7 +108,7 @@ pub fn projectile_spawn_sys(
                         LauncherSide::Right => -0.02,
                     };
 
-                    // this "just works" to keep it in the right origin place even as the page size shifts
+                    // from the launcher's corner to the mouth of its tube, in design units
                     let anchor = match side {
                         LauncherSide::Left => Vec3::new(215.0, 235.0, 0.0),
                         LauncherSide::Right => Vec3::new(-238.0, 220.0, 0.0),
@@ -121,14 +135,17 @@ pub fn projectile_spawn_sys(
                         &mut positions,
                         &mut anchors,
                         &mut colliders,
+                        &mut collision_layers,
                     )
                         .add_component_unchecked(
                             entity,
                             (
                                 Projectile {
-                                    width: bad_rocket_width,
-                                    height: bad_rocket_height,
-                                    texture_id: bad_rocket_texture_id,
+                                    width: bad_rocket.width,
+                                    height: bad_rocket.height,
+                                    page: bad_rocket.page,
+                                    uvs: bad_rocket.uvs,
+                                    mask: bad_rocket.mask.clone(),
                                     movement: ProjectileMovement::Rocket {
                                         rotation,
                                         // thrust_speed: 0.0,
@@ -136,10 +153,12 @@ pub fn projectile_spawn_sys(
                                         thrust_speed: 0.0005,
                                         rotation_speed,
                                     },
+                                    shooter: Some(EnemyKind::Four),
                                 },
                                 LayoutPosition::new(pos),
                                 LayoutAnchor::new(anchor),
                                 Collider::default(),
+                                collision_rules.layer(CollisionLayerKind::Rocket),
                             ),
                         );
                 }
@@ -155,6 +174,7 @@ pub fn projectile_spawn_sys(
                             let mut rot = 0.0;
                             let mut anchor = Vec3::new(0.0, 0.0, 0.0);
 
+                            // from the enemy's position out to the rpg, in design units
                             match controller.direction {
                                 EnemyDirection::Left => {
                                     anchor.x = -170.0;
@@ -188,14 +208,17 @@ pub fn projectile_spawn_sys(
                         &mut positions,
                         &mut anchors,
                         &mut colliders,
+                        &mut collision_layers,
                     )
                         .add_component_unchecked(
                             entity,
                             (
                                 Projectile {
-                                    width: bad_rocket_width,
-                                    height: bad_rocket_height,
-                                    texture_id: bad_rocket_texture_id,
+                                    width: bad_rocket.width,
+                                    height: bad_rocket.height,
+                                    page: bad_rocket.page,
+                                    uvs: bad_rocket.uvs,
+                                    mask: bad_rocket.mask.clone(),
                                     movement: ProjectileMovement::Rocket {
                                         rotation,
                                         // thrust_speed: 0.0,
@@ -203,10 +226,12 @@ pub fn projectile_spawn_sys(
                                         thrust_speed: 0.0005,
                                         rotation_speed: 0.0,
                                     },
+                                    shooter: Some(EnemyKind::Three),
                                 },
                                 LayoutPosition::new(pos),
                                 LayoutAnchor::new(anchor),
                                 Collider::default(),
+                                collision_rules.layer(CollisionLayerKind::Rocket),
                             ),
                         );
                 }
@@ -242,7 +267,7 @@ pub fn projectile_spawn_sys(
                 for (pos, rotation, velocity) in to_spawn.drain(..) {
                     let rot =
                         UnitQuaternion::from_axis_angle(&Vec3::z_axis(), rotation.to_radians());
-                    let origin = Vec3::new(bullet_width / 2.0, bullet_height / 2.0, 0.0);
+                    let origin = Vec3::new(bullet.width / 2.0, bullet.height / 2.0, 0.0);
                     let entity = sg_storages.spawn_child_trs_origin(
                         None,
                         None,
@@ -256,19 +281,23 @@ pub fn projectile_spawn_sys(
                         &mut positions,
                         &mut anchors,
                         &mut colliders,
+                        &mut collision_layers,
                     )
                         .add_component_unchecked(
                             entity,
                             (
                                 Projectile {
-                                    width: bullet_width,
-                                    height: bullet_height,
-                                    texture_id: bullet_texture_id,
+                                    width: bullet.width,
+                                    height: bullet.height,
+                                    page: bullet.page,
+                                    uvs: bullet.uvs,
+                                    mask: bullet.mask.clone(),
                                     movement: ProjectileMovement::Bullet {
                                         velocity,
                                         acceleration,
                                         rotation,
                                     },
+                                    shooter: Some(EnemyKind::Two),
                                 },
                                 LayoutPosition::new(pos),
                                 LayoutAnchor::new(Vec3::new(
@@ -277,6 +306,7 @@ pub fn projectile_spawn_sys(
                                     0.0,
                                 )),
                                 Collider::default(),
+                                collision_rules.layer(CollisionLayerKind::Bullet),
                             ),
                         );
                 }
@@ -344,6 +374,7 @@ pub fn projectile_physics_sys(
             ProjectileMovement::Bomb {
                 velocity,
                 acceleration,
+                ..
             } => {
                 pos.x += velocity.x * tick.delta as f32;
                 pos.y += velocity.y * tick.delta as f32;
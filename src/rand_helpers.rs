@@ -1,34 +1,51 @@
 use std::ops::{Deref, DerefMut};
 
 use crate::prelude::*;
-use rand::rngs::ThreadRng;
+use rand::{rngs::StdRng, SeedableRng};
 
 pub type RandView<'a> = NonSendSync<UniqueView<'a, Rand>>;
 pub type RandViewMut<'a> = NonSendSync<UniqueViewMut<'a, Rand>>;
 
+// All gameplay randomness goes through this, so that a given seed (plus identical inputs)
+// reproduces the exact same run
 #[derive(Component, Unique)]
 pub struct Rand {
-    inner: ThreadRng,
+    inner: StdRng,
+    seed: u64,
 }
 
 impl Rand {
-    pub fn new() -> Self {
+    pub fn new(seed: u64) -> Self {
         Self {
-            inner: rand::thread_rng(),
+            inner: StdRng::seed_from_u64(seed),
+            seed,
         }
     }
+
+    // a fresh seed for when one isn't supplied via config or url
+    pub fn random_seed() -> u64 {
+        rand::thread_rng().gen()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
 }
 
 impl Deref for Rand {
-    type Target = ThreadRng;
+    type Target = StdRng;
 
-    fn deref(&self) -> &ThreadRng {
+    fn deref(&self) -> &StdRng {
         &self.inner
     }
 }
 
 impl DerefMut for Rand {
-    fn deref_mut(&mut self) -> &mut ThreadRng {
+    fn deref_mut(&mut self) -> &mut StdRng {
         &mut self.inner
     }
 }
@@ -0,0 +1,146 @@
+// Sprites are collected here while rendering, and drawn with one instanced call per batch
+// a batch only grows while the texture stays the same, so things are drawn in exactly the order they were pushed
+// (everything is alpha blended without depth testing, so that order is what decides what's on top)
+use awsm_web::webgl::{BeginMode, BlendFactor, BufferData, BufferTarget, BufferUsage, GlToggle};
+
+use super::Renderer;
+use crate::prelude::*;
+
+pub struct SpriteBatcher {
+    batches: Vec<SpriteBatch>,
+    // FLOATS_PER_INSTANCE per sprite, kept around so it's not reallocated every frame
+    instances: Vec<f32>,
+}
+
+struct SpriteBatch {
+    texture_id: Id,
+    // in instances, not floats
+    start: usize,
+    count: usize,
+}
+
+pub struct SpriteInstance<'a> {
+    pub world_transform: &'a Mat4,
+    pub width: f32,
+    pub height: f32,
+    // same layout as Uvs::data
+    pub uvs: &'a [f32; 8],
+    pub tint: (f32, f32, f32, f32),
+}
+
+impl SpriteBatcher {
+    // model (16), quad scaler (2), uv rect (4), tint (4)
+    // the attribute layout in shaders.rs and sprite-instanced.vert has to match
+    pub const FLOATS_PER_INSTANCE: usize = 26;
+    pub const INSTANCE_STRIDE: usize = Self::FLOATS_PER_INSTANCE * 4;
+
+    pub fn new() -> Self {
+        Self {
+            batches: Vec::new(),
+            instances: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, texture_id: Id, sprite: SpriteInstance) {
+        match self.batches.last_mut() {
+            Some(batch) if batch.texture_id == texture_id => batch.count += 1,
+            _ => self.batches.push(SpriteBatch {
+                texture_id,
+                start: self.instances.len() / Self::FLOATS_PER_INSTANCE,
+                count: 1,
+            }),
+        }
+
+        let mut model: [f32; 16] = [0.0; 16];
+        sprite.world_transform.write_to_vf32(&mut model);
+        self.instances.extend_from_slice(&model);
+
+        self.instances
+            .extend_from_slice(&[sprite.width, sprite.height]);
+
+        // the quad is axis-aligned in texture space, so bottom-left and top-right are enough
+        let uvs = sprite.uvs;
+        self.instances
+            .extend_from_slice(&[uvs[2], uvs[3], uvs[4] - uvs[2], uvs[5] - uvs[3]]);
+
+        let (r, g, b, a) = sprite.tint;
+        self.instances.extend_from_slice(&[r, g, b, a]);
+    }
+
+    fn clear(&mut self) {
+        self.batches.clear();
+        self.instances.clear();
+    }
+}
+
+// Counted over a whole frame, see Renderer::draw_stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrawStats {
+    pub draw_calls: u32,
+    pub sprite_batches: u32,
+    pub sprites: u32,
+    // see ParticleSystem::render
+    pub particles: u32,
+}
+
+impl Renderer {
+    pub fn push_sprite(&mut self, texture_id: Id, sprite: SpriteInstance) {
+        self.sprite_batcher.push(texture_id, sprite);
+    }
+
+    // draws everything that was pushed since the last flush
+    pub fn flush_sprites(&mut self) -> Result<()> {
+        if self.sprite_batcher.batches.is_empty() {
+            return Ok(());
+        }
+
+        self.toggle(GlToggle::Blend, true);
+        self.set_blend_func(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha);
+        self.set_depth_func(awsm_web::webgl::CmpFunction::Less);
+        self.set_depth_mask(false);
+        self.toggle(GlToggle::DepthTest, false);
+
+        let program = self.shaders.programs.sprite_instanced.clone();
+        program.activate(self)?;
+
+        let batcher = std::mem::replace(&mut self.sprite_batcher, SpriteBatcher::new());
+
+        for batch in &batcher.batches {
+            let start = batch.start * SpriteBatcher::FLOATS_PER_INSTANCE;
+            let end = start + batch.count * SpriteBatcher::FLOATS_PER_INSTANCE;
+
+            self.upload_buffer(
+                self.buffers.sprite_instances,
+                BufferData::new(
+                    &batcher.instances[start..end],
+                    BufferTarget::ArrayBuffer,
+                    BufferUsage::DynamicDraw,
+                ),
+            )?;
+            self.activate_texture_sampler_name(batch.texture_id, "u_sampler")?;
+            self.gl.gl.draw_arrays_instanced(
+                BeginMode::TriangleStrip as u32,
+                0,
+                4,
+                batch.count as i32,
+            );
+
+            self.frame_draw_stats.draw_calls += 1;
+            self.frame_draw_stats.sprite_batches += 1;
+            self.frame_draw_stats.sprites += batch.count as u32;
+        }
+
+        // hand the allocations back
+        self.sprite_batcher = batcher;
+        self.sprite_batcher.clear();
+
+        Ok(())
+    }
+
+    // WebGl2Renderer::draw_arrays (via Deref), but counted in the DrawStats
+    // every non-instanced draw should go through this
+    pub fn draw_arrays_counted(&mut self, mode: BeginMode, first: u32, count: u32) {
+        self.gl.draw_arrays(mode, first, count);
+        self.frame_draw_stats.draw_calls += 1;
+    }
+}
@@ -8,6 +8,10 @@ pub struct Buffers {
     pub collision_geom: Id,
     pub quad_uvs: Id,
     pub collision_uvs: Id,
+    // per-instance data for the sprite batcher
+    pub sprite_instances: Id,
+    // per-instance data for the particles
+    pub particle_instances: Id,
 }
 
 impl Buffers {
@@ -31,12 +35,16 @@ impl Buffers {
         let quad_uvs = gl.create_buffer()?;
         let collision_geom = gl.create_buffer()?;
         let collision_uvs = gl.create_buffer()?;
+        let sprite_instances = gl.create_buffer()?;
+        let particle_instances = gl.create_buffer()?;
 
         Ok(Self {
             quad_geom,
             quad_uvs,
             collision_geom,
             collision_uvs,
+            sprite_instances,
+            particle_instances,
         })
     }
 }
@@ -9,6 +9,7 @@ use crate::{
 };
 
 use super::{
+    batch::{DrawStats, SpriteBatcher},
     buffers::Buffers,
     framebuffers::{self, FrameBuffers},
     shaders::Shaders,
@@ -23,17 +24,18 @@ pub struct Renderer {
     pub shaders: Shaders,
     pub buffers: Buffers,
     pub framebuffers: Option<FrameBuffers>,
+    pub sprite_batcher: SpriteBatcher,
+    // the last complete frame, e.g. for a debug overlay
+    pub draw_stats: DrawStats,
+    // the frame that's being drawn now
+    pub frame_draw_stats: DrawStats,
 }
 
 impl Renderer {
     pub const UBO_CAMERA: u32 = 0;
 
     pub fn new(dom: &DomState) -> Result<Self> {
-        let mut gl = WebGl2Renderer::new(dom.create_gl_context())?;
-
-        // these must be set right away, _before_ shaders are compiled etc.
-        gl.hardcoded_ubo_locations
-            .insert("ubo_camera".to_string(), Self::UBO_CAMERA);
+        let mut gl = Self::create_gl(dom.create_gl_context())?;
 
         dom.ui.phase.set(UiPhase::Buffers);
         let buffers = Buffers::new(&mut gl)?;
@@ -46,8 +48,45 @@ impl Renderer {
             shaders,
             buffers,
             framebuffers: None,
+            sprite_batcher: SpriteBatcher::new(),
+            draw_stats: DrawStats::default(),
+            frame_draw_stats: DrawStats::default(),
         })
     }
+
+    // After a lost context is restored, every buffer, shader etc. on the gpu is gone
+    // so this starts over on the same context, and whatever else holds an Id has to be rebuilt too (see context_loss)
+    pub fn rebuild(&mut self) -> Result<()> {
+        let mut gl = Self::create_gl(self.gl.gl.clone())?;
+        let buffers = Buffers::new(&mut gl)?;
+        let shaders = Shaders::compile(&mut gl, &buffers)?;
+
+        *self = Self {
+            gl,
+            shaders,
+            buffers,
+            // recreated on the next resize
+            framebuffers: None,
+            sprite_batcher: SpriteBatcher::new(),
+            draw_stats: DrawStats::default(),
+            frame_draw_stats: DrawStats::default(),
+        };
+        Ok(())
+    }
+
+    pub fn is_context_lost(&self) -> bool {
+        self.gl.gl.is_context_lost()
+    }
+
+    fn create_gl(context: WebGl2RenderingContext) -> Result<WebGl2Renderer> {
+        let mut gl = WebGl2Renderer::new(context)?;
+
+        // these must be set right away, _before_ shaders are compiled etc.
+        gl.hardcoded_ubo_locations
+            .insert("ubo_camera".to_string(), Self::UBO_CAMERA);
+
+        Ok(gl)
+    }
 }
 
 impl Deref for Renderer {
@@ -6,11 +6,12 @@ use awsm_web::webgl::{
     BufferUsage, DataType, DrawBuffer, FrameBufferAttachment, FrameBufferTarget,
     FrameBufferTextureTarget, GlToggle, Id, NameOrLoc, PartialWebGlFrameBuffer, PixelFormat,
     RenderBufferFormat, SimpleTextureOptions, TextureMagFilter, TextureMinFilter, TextureTarget,
-    VertexArray, WebGl2Renderer, WebGlTextureSource,
+    TextureWrapMode, VertexArray, WebGl2Renderer, WebGlTextureSource,
 };
 use shipyard::*;
 
 use super::Renderer;
+use crate::post::data::PostPassParams;
 
 //pub type DrawBuffersView<'a> = UniqueView<'a, Option<DrawBuffers>>;
 //pub type DrawBuffersViewMut<'a> = UniqueViewMut<'a, Option<DrawBuffers>>;
@@ -56,50 +57,99 @@ impl Renderer {
         }
     }
 
-    pub fn post_draw(&mut self) -> Result<bool> {
-        let (_, _, width, height) = self.get_viewport();
-        if let Some(framebuffers) = &self.framebuffers {
-            self.bind_framebuffer(framebuffers.fbo_draw.id, FrameBufferTarget::ReadFrameBuffer)?;
-
-            // multisampling
-            // i.e. to downsample from the msaa into single-sample fbo
-            // and that can't be done directly into the front buffer
-            if let Some(fbo_multisample) = &framebuffers.fbo_multisample {
-                self.bind_framebuffer(fbo_multisample.id, FrameBufferTarget::DrawFrameBuffer)?;
-                self.blit_framebuffer(
-                    0,
-                    0,
-                    framebuffers.width,
-                    framebuffers.height,
-                    0,
-                    0,
-                    framebuffers.width,
-                    framebuffers.height,
-                    BufferMask::ColorBufferBit,
-                    BlitFilter::Nearest,
-                );
-
-                self.bind_framebuffer(fbo_multisample.id, FrameBufferTarget::ReadFrameBuffer)?;
-            }
+    // passes run in order, the last one draws straight to the screen
+    pub fn post_draw(&mut self, passes: &[PostPassParams]) -> Result<bool> {
+        let framebuffers = match &self.framebuffers {
+            Some(framebuffers) => framebuffers,
+            None => return Ok(false),
+        };
 
-            self.release_framebuffer(FrameBufferTarget::DrawFrameBuffer);
-            self.blit_framebuffer(
+        let (width, height) = (framebuffers.width, framebuffers.height);
+        let fbo_draw = framebuffers.fbo_draw.id;
+        let fbo_multisample = framebuffers.fbo_multisample.as_ref().map(|fbo| fbo.id);
+        let fbo_post = [framebuffers.fbo_post[0].id, framebuffers.fbo_post[1].id];
+        let post_textures = [
+            framebuffers.fbo_post[0].color.unwrap_ext().id,
+            framebuffers.fbo_post[1].color.unwrap_ext().id,
+        ];
+
+        let blit = |renderer: &mut Self| {
+            renderer.blit_framebuffer(
                 0,
                 0,
-                framebuffers.width,
-                framebuffers.height,
+                width,
+                height,
                 0,
                 0,
-                framebuffers.width,
-                framebuffers.height,
+                width,
+                height,
                 BufferMask::ColorBufferBit,
                 BlitFilter::Nearest,
             );
+        };
 
-            Ok(true)
+        self.bind_framebuffer(fbo_draw, FrameBufferTarget::ReadFrameBuffer)?;
+
+        // multisampling
+        // i.e. to downsample from the msaa into single-sample fbo
+        // and that can't be done directly into the front buffer
+        if let Some(fbo_multisample) = fbo_multisample {
+            self.bind_framebuffer(fbo_multisample, FrameBufferTarget::DrawFrameBuffer)?;
+            blit(self);
+            self.bind_framebuffer(fbo_multisample, FrameBufferTarget::ReadFrameBuffer)?;
+        }
+
+        if passes.is_empty() {
+            self.release_framebuffer(FrameBufferTarget::DrawFrameBuffer);
+            blit(self);
         } else {
-            Ok(false)
+            // the passes need a texture to sample from, which the draw buffers aren't
+            self.bind_framebuffer(fbo_post[0], FrameBufferTarget::DrawFrameBuffer)?;
+            blit(self);
+            self.release_framebuffer(FrameBufferTarget::ReadFrameBuffer);
+
+            self.toggle(GlToggle::Blend, false);
+            self.toggle(GlToggle::DepthTest, false);
+
+            // ping-pong between the two, reading from one and drawing into the other
+            let mut source = 0;
+            for (index, params) in passes.iter().enumerate() {
+                if index == passes.len() - 1 {
+                    self.release_framebuffer(FrameBufferTarget::DrawFrameBuffer);
+                } else {
+                    self.bind_framebuffer(
+                        fbo_post[1 - source],
+                        FrameBufferTarget::DrawFrameBuffer,
+                    )?;
+                }
+
+                self.draw_post_pass(params, post_textures[source], width, height)?;
+                source = 1 - source;
+            }
         }
+
+        self.draw_stats = std::mem::take(&mut self.frame_draw_stats);
+
+        Ok(true)
+    }
+
+    fn draw_post_pass(
+        &mut self,
+        params: &PostPassParams,
+        texture_id: Id,
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        let program = self.shaders.programs.post.get(params.pass).clone();
+        program.activate(self)?;
+
+        let (radius, step) = params.quality.bloom_kernel();
+        self.upload_uniform_fvals_2_name("u_texel", (1.0 / width as f32, 1.0 / height as f32));
+        self.upload_uniform_fvals_4_name("u_params", (params.amount, radius, step, 0.0));
+        self.activate_texture_sampler_name(texture_id, "u_sampler")?;
+        self.draw_arrays_counted(BeginMode::TriangleStrip, 0, 4);
+
+        Ok(())
     }
 }
 
@@ -110,6 +160,8 @@ pub struct FrameBuffers {
     pub fbo_draw: FrameBuffer,
     pub fbo_multisample: Option<FrameBuffer>,
     pub fbo_collision: FrameBuffer,
+    // the post-processing chain reads from one and draws into the other
+    pub fbo_post: [FrameBuffer; 2],
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -156,6 +208,15 @@ impl FrameBuffers {
 
         fbo_collision.release(gl);
 
+        let mut post = || -> Result<FrameBuffer> {
+            let fbo = FrameBuffer::new(gl)?
+                .build_sampled_color(gl, width, height)?
+                .validate(gl)?;
+            fbo.release(gl);
+            Ok(fbo)
+        };
+        let fbo_post = [post()?, post()?];
+
         gl.release_framebuffer(FrameBufferTarget::DrawFrameBuffer);
         Ok(Self {
             width,
@@ -163,6 +224,7 @@ impl FrameBuffers {
             fbo_draw,
             fbo_multisample,
             fbo_collision,
+            fbo_post,
         })
     }
 
@@ -171,6 +233,9 @@ impl FrameBuffers {
         if let Some(mut fbo_multisample) = self.fbo_multisample.take() {
             fbo_multisample.destroy(&mut gl)?;
         }
+        for fbo in self.fbo_post.iter_mut() {
+            fbo.destroy(&mut gl)?;
+        }
         Ok(())
     }
 }
@@ -378,6 +443,46 @@ impl FrameBuffer {
         Ok(self)
     }
 
+    // a plain texture that can be sampled from, for the post-processing passes
+    // linear and clamped, since some passes read between pixels and past the edges
+    pub fn build_sampled_color(
+        mut self,
+        gl: &mut WebGl2Renderer,
+        width: u32,
+        height: u32,
+    ) -> Result<Self> {
+        let color_id = gl.create_texture()?;
+
+        gl.assign_simple_texture(
+            color_id,
+            TextureTarget::Texture2d,
+            &SimpleTextureOptions {
+                flip_y: Some(false),
+                filter_min: Some(TextureMinFilter::Linear),
+                filter_mag: Some(TextureMagFilter::Linear),
+                wrap_s: Some(TextureWrapMode::ClampToEdge),
+                wrap_t: Some(TextureWrapMode::ClampToEdge),
+                pixel_format: PixelFormat::Rgba,
+                ..SimpleTextureOptions::default()
+            },
+            &WebGlTextureSource::EmptyBufferView(width, height, 0),
+        )?;
+        gl.assign_framebuffer_texture_2d(
+            self.id,
+            color_id,
+            FrameBufferTarget::DrawFrameBuffer,
+            FrameBufferAttachment::Color0,
+            FrameBufferTextureTarget::Texture2d,
+        )?;
+
+        self.color = Some(FrameBufferId {
+            kind: FrameBufferIdKind::Texture,
+            id: color_id,
+        });
+
+        Ok(self)
+    }
+
     pub fn release(&self, gl: &mut WebGl2Renderer) {
         gl.release_texture_target(TextureTarget::Texture2d);
         gl.release_renderbuffer();
@@ -1,3 +1,4 @@
+pub mod batch;
 mod data;
 pub use data::*;
 pub(super) mod buffers;
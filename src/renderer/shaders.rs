@@ -1,12 +1,25 @@
 use awsm_web::webgl::{AttributeOptions, DataType, NameOrLoc, VertexArray, WebGl2Renderer};
 
-use super::{buffers::Buffers, Renderer};
-use crate::prelude::*;
+use super::{batch::SpriteBatcher, buffers::Buffers, Renderer};
+use crate::{particles::data::ParticleSystem, post::data::PostPass, prelude::*};
 
 const QUAD_UNIT_VERTEX: &'static str = include_str!("./shaders/vertex/quad-unit.vert");
 const QUAD_TEXTURE_FRAGMENT: &'static str = include_str!("./shaders/fragment/quad-texture.frag");
+const SPRITE_INSTANCED_VERTEX: &'static str =
+    include_str!("./shaders/vertex/sprite-instanced.vert");
+const SPRITE_INSTANCED_FRAGMENT: &'static str =
+    include_str!("./shaders/fragment/sprite-instanced.frag");
+const PARTICLE_VERTEX: &'static str = include_str!("./shaders/vertex/particle.vert");
 const COLLISION_VERTEX: &'static str = include_str!("./shaders/vertex/collision.vert");
 const COLLISION_FRAGMENT: &'static str = include_str!("./shaders/fragment/collision.frag");
+const FULLSCREEN_VERTEX: &'static str = include_str!("./shaders/vertex/fullscreen.vert");
+const POST_BLOOM_FRAGMENT: &'static str = include_str!("./shaders/fragment/post-bloom.frag");
+const POST_GRADE_FRAGMENT: &'static str = include_str!("./shaders/fragment/post-grade.frag");
+const POST_CHROMATIC_FRAGMENT: &'static str =
+    include_str!("./shaders/fragment/post-chromatic.frag");
+const POST_VIGNETTE_FRAGMENT: &'static str = include_str!("./shaders/fragment/post-vignette.frag");
+const POST_GRAYSCALE_FRAGMENT: &'static str =
+    include_str!("./shaders/fragment/post-grayscale.frag");
 
 #[derive(Clone, Debug)]
 pub struct Shaders {
@@ -18,19 +31,53 @@ pub struct Shaders {
 #[derive(Clone, Debug)]
 pub struct VertexShaders {
     pub quad_unit: Id,
+    pub sprite_instanced: Id,
+    pub particle: Id,
     pub collision: Id,
+    pub fullscreen: Id,
 }
 
 #[derive(Clone, Debug)]
 pub struct FragmentShaders {
     pub quad_texture: Id,
+    pub sprite_instanced: Id,
     pub collision: Id,
+    pub post_bloom: Id,
+    pub post_grade: Id,
+    pub post_chromatic: Id,
+    pub post_vignette: Id,
+    pub post_grayscale: Id,
 }
 
 #[derive(Clone, Debug)]
 pub struct ShaderPrograms {
     pub sprite: ShaderProgram,
+    pub sprite_instanced: ShaderProgram,
+    pub particle: ShaderProgram,
     pub collision: ShaderProgram,
+    pub post: PostPrograms,
+}
+
+// one per PostPass, all drawn as a full-screen quad
+#[derive(Clone, Debug)]
+pub struct PostPrograms {
+    pub bloom: ShaderProgram,
+    pub grade: ShaderProgram,
+    pub chromatic: ShaderProgram,
+    pub vignette: ShaderProgram,
+    pub grayscale: ShaderProgram,
+}
+
+impl PostPrograms {
+    pub fn get(&self, pass: PostPass) -> &ShaderProgram {
+        match pass {
+            PostPass::Bloom => &self.bloom,
+            PostPass::Grade => &self.grade,
+            PostPass::Chromatic => &self.chromatic,
+            PostPass::Vignette => &self.vignette,
+            PostPass::Grayscale => &self.grayscale,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -51,14 +98,39 @@ impl Shaders {
     pub fn compile(gl: &mut WebGl2Renderer, buffers: &Buffers) -> Result<Self> {
         let vertex = VertexShaders {
             quad_unit: gl.compile_shader(QUAD_UNIT_VERTEX, awsm_web::webgl::ShaderType::Vertex)?,
+            sprite_instanced: gl
+                .compile_shader(SPRITE_INSTANCED_VERTEX, awsm_web::webgl::ShaderType::Vertex)?,
+            particle: gl.compile_shader(PARTICLE_VERTEX, awsm_web::webgl::ShaderType::Vertex)?,
             collision: gl.compile_shader(COLLISION_VERTEX, awsm_web::webgl::ShaderType::Vertex)?,
+            fullscreen: gl
+                .compile_shader(FULLSCREEN_VERTEX, awsm_web::webgl::ShaderType::Vertex)?,
         };
 
         let fragment = FragmentShaders {
             quad_texture: gl
                 .compile_shader(QUAD_TEXTURE_FRAGMENT, awsm_web::webgl::ShaderType::Fragment)?,
+            sprite_instanced: gl.compile_shader(
+                SPRITE_INSTANCED_FRAGMENT,
+                awsm_web::webgl::ShaderType::Fragment,
+            )?,
             collision: gl
                 .compile_shader(COLLISION_FRAGMENT, awsm_web::webgl::ShaderType::Fragment)?,
+            post_bloom: gl
+                .compile_shader(POST_BLOOM_FRAGMENT, awsm_web::webgl::ShaderType::Fragment)?,
+            post_grade: gl
+                .compile_shader(POST_GRADE_FRAGMENT, awsm_web::webgl::ShaderType::Fragment)?,
+            post_chromatic: gl.compile_shader(
+                POST_CHROMATIC_FRAGMENT,
+                awsm_web::webgl::ShaderType::Fragment,
+            )?,
+            post_vignette: gl.compile_shader(
+                POST_VIGNETTE_FRAGMENT,
+                awsm_web::webgl::ShaderType::Fragment,
+            )?,
+            post_grayscale: gl.compile_shader(
+                POST_GRAYSCALE_FRAGMENT,
+                awsm_web::webgl::ShaderType::Fragment,
+            )?,
         };
 
         let sprite_shader = {
@@ -87,6 +159,98 @@ impl Shaders {
             ShaderProgram { program_id, vao_id }
         };
 
+        // the per-instance attributes are all in one interleaved buffer, see SpriteBatcher
+        let sprite_instanced_shader = {
+            let program_id =
+                gl.compile_program(&[vertex.sprite_instanced, fragment.sprite_instanced])?;
+            gl.init_uniform_buffer_name(program_id, "ubo_camera")?;
+
+            let vao_id = gl.create_vertex_array()?;
+
+            let instance_attribute = |loc: u32, size: u8, offset: usize| VertexArray {
+                attribute: NameOrLoc::Loc(loc),
+                buffer_id: buffers.sprite_instances,
+                opts: AttributeOptions {
+                    stride: SpriteBatcher::INSTANCE_STRIDE as u8,
+                    offset: (offset * 4) as u64,
+                    ..AttributeOptions::new(size, DataType::Float)
+                },
+            };
+
+            gl.assign_vertex_array(
+                vao_id,
+                None,
+                &[
+                    VertexArray {
+                        attribute: NameOrLoc::Name("a_geom_vertex"),
+                        buffer_id: buffers.quad_geom,
+                        opts: AttributeOptions::new(2, DataType::Float),
+                    },
+                    // a_model, a mat4 takes up 4 locations
+                    instance_attribute(2, 4, 0),
+                    instance_attribute(3, 4, 4),
+                    instance_attribute(4, 4, 8),
+                    instance_attribute(5, 4, 12),
+                    // a_quad_scaler
+                    instance_attribute(6, 2, 16),
+                    // a_uv_rect
+                    instance_attribute(7, 4, 18),
+                    // a_tint
+                    instance_attribute(8, 4, 22),
+                ],
+            )?;
+
+            // everything but the quad itself advances once per instance
+            gl.activate_vertex_array(vao_id)?;
+            for loc in 2..=8 {
+                gl.gl.vertex_attrib_divisor(loc, 1);
+            }
+
+            ShaderProgram { program_id, vao_id }
+        };
+
+        // same idea as the sprites, but much less per instance, see ParticleSystem::render
+        // the fragment shader is shared with the sprites, it just tints the texture
+        let particle_shader = {
+            let program_id = gl.compile_program(&[vertex.particle, fragment.sprite_instanced])?;
+            gl.init_uniform_buffer_name(program_id, "ubo_camera")?;
+
+            let vao_id = gl.create_vertex_array()?;
+
+            let instance_attribute = |loc: u32, offset: usize| VertexArray {
+                attribute: NameOrLoc::Loc(loc),
+                buffer_id: buffers.particle_instances,
+                opts: AttributeOptions {
+                    stride: ParticleSystem::INSTANCE_STRIDE as u8,
+                    offset: (offset * 4) as u64,
+                    ..AttributeOptions::new(4, DataType::Float)
+                },
+            };
+
+            gl.assign_vertex_array(
+                vao_id,
+                None,
+                &[
+                    VertexArray {
+                        attribute: NameOrLoc::Name("a_geom_vertex"),
+                        buffer_id: buffers.quad_geom,
+                        opts: AttributeOptions::new(2, DataType::Float),
+                    },
+                    // a_particle
+                    instance_attribute(1, 0),
+                    // a_color
+                    instance_attribute(2, 4),
+                ],
+            )?;
+
+            gl.activate_vertex_array(vao_id)?;
+            for loc in 1..=2 {
+                gl.gl.vertex_attrib_divisor(loc, 1);
+            }
+
+            ShaderProgram { program_id, vao_id }
+        };
+
         let collision_shader = {
             let program_id = gl.compile_program(&[vertex.collision, fragment.collision])?;
             gl.init_uniform_buffer_name(program_id, "ubo_camera")?;
@@ -113,9 +277,39 @@ impl Shaders {
             ShaderProgram { program_id, vao_id }
         };
 
+        // no camera, the quad goes straight to clip space
+        let mut post_shader = |fragment_id: Id| -> Result<ShaderProgram> {
+            let program_id = gl.compile_program(&[vertex.fullscreen, fragment_id])?;
+
+            let vao_id = gl.create_vertex_array()?;
+
+            gl.assign_vertex_array(
+                vao_id,
+                None,
+                &[VertexArray {
+                    attribute: NameOrLoc::Name("a_geom_vertex"),
+                    buffer_id: buffers.quad_geom,
+                    opts: AttributeOptions::new(2, DataType::Float),
+                }],
+            )?;
+
+            Ok(ShaderProgram { program_id, vao_id })
+        };
+
+        let post = PostPrograms {
+            bloom: post_shader(fragment.post_bloom)?,
+            grade: post_shader(fragment.post_grade)?,
+            chromatic: post_shader(fragment.post_chromatic)?,
+            vignette: post_shader(fragment.post_vignette)?,
+            grayscale: post_shader(fragment.post_grayscale)?,
+        };
+
         let programs = ShaderPrograms {
             sprite: sprite_shader,
+            sprite_instanced: sprite_instanced_shader,
+            particle: particle_shader,
             collision: collision_shader,
+            post,
         };
 
         Ok(Self {
@@ -0,0 +1,47 @@
+#version 300 es
+precision highp float;
+
+uniform sampler2D u_sampler;
+
+// 1 / framebuffer size
+uniform vec2 u_texel;
+// amount, radius (pixels), step (pixels), unused
+uniform vec4 u_params;
+
+in vec2 v_uv;
+
+out vec4 color;
+
+const float THRESHOLD = 0.7;
+
+vec3 bright(vec2 uv) {
+    vec3 c = texture(u_sampler, uv).rgb;
+    float luma = dot(c, vec3(0.2126, 0.7152, 0.0722));
+    return c * smoothstep(THRESHOLD, 1.0, luma);
+}
+
+void main() {
+    vec3 base = texture(u_sampler, v_uv).rgb;
+
+    float radius = u_params.y;
+    float step_size = u_params.z;
+
+    // a single blurred bright-pass, weighted towards the middle
+    // the loop bounds have to be constant, so anything past the radius is skipped instead
+    vec3 glow = vec3(0.0);
+    float total = 0.0;
+    for (int x = -4; x <= 4; x++) {
+        for (int y = -4; y <= 4; y++) {
+            vec2 offset = vec2(float(x), float(y)) * step_size;
+            if (abs(offset.x) > radius || abs(offset.y) > radius) {
+                continue;
+            }
+            float weight = 1.0 / (1.0 + dot(offset, offset) / (radius * radius));
+            glow += bright(v_uv + offset * u_texel) * weight;
+            total += weight;
+        }
+    }
+    glow /= max(total, 0.0001);
+
+    color = vec4(base + glow * u_params.x * 1.5, 1.0);
+}
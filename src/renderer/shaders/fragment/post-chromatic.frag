@@ -0,0 +1,31 @@
+#version 300 es
+precision highp float;
+
+uniform sampler2D u_sampler;
+
+// 1 / framebuffer size
+uniform vec2 u_texel;
+// amount, unused...
+uniform vec4 u_params;
+
+in vec2 v_uv;
+
+out vec4 color;
+
+// at full strength, in pixels at the edge of the screen
+const float MAX_SHIFT = 12.0;
+
+void main() {
+    float amount = u_params.x;
+
+    // red and blue pulled apart from the middle outwards
+    vec2 dir = (v_uv - 0.5) * 2.0;
+    vec2 shift = dir * MAX_SHIFT * amount * u_texel;
+
+    float r = texture(u_sampler, v_uv + shift).r;
+    float g = texture(u_sampler, v_uv).g;
+    float b = texture(u_sampler, v_uv - shift).b;
+
+    vec3 c = vec3(r, g, b);
+    color = vec4(mix(c, vec3(1.0), amount * 0.25), 1.0);
+}
@@ -0,0 +1,23 @@
+#version 300 es
+precision highp float;
+
+uniform sampler2D u_sampler;
+
+// amount, unused...
+uniform vec4 u_params;
+
+in vec2 v_uv;
+
+out vec4 color;
+
+void main() {
+    vec3 c = texture(u_sampler, v_uv).rgb;
+
+    // a touch more contrast and saturation, warmer highlights and cooler shadows
+    vec3 graded = (c - 0.5) * 1.08 + 0.5;
+    float luma = dot(graded, vec3(0.2126, 0.7152, 0.0722));
+    graded = mix(vec3(luma), graded, 1.12);
+    graded *= mix(vec3(0.96, 0.98, 1.04), vec3(1.04, 1.0, 0.95), luma);
+
+    color = vec4(mix(c, clamp(graded, 0.0, 1.0), u_params.x), 1.0);
+}
@@ -0,0 +1,19 @@
+#version 300 es
+precision highp float;
+
+uniform sampler2D u_sampler;
+
+// amount, unused...
+uniform vec4 u_params;
+
+in vec2 v_uv;
+
+out vec4 color;
+
+void main() {
+    vec3 c = texture(u_sampler, v_uv).rgb;
+    float luma = dot(c, vec3(0.2126, 0.7152, 0.0722));
+
+    // a little darker too, so the game over screen stands out
+    color = vec4(mix(c, vec3(luma * 0.8), u_params.x), 1.0);
+}
@@ -0,0 +1,20 @@
+#version 300 es
+precision highp float;
+
+uniform sampler2D u_sampler;
+
+// amount, unused...
+uniform vec4 u_params;
+
+in vec2 v_uv;
+
+out vec4 color;
+
+void main() {
+    vec3 c = texture(u_sampler, v_uv).rgb;
+
+    vec2 dir = v_uv - 0.5;
+    float vignette = smoothstep(0.8, 0.35, length(dir));
+
+    color = vec4(c * mix(1.0, mix(0.6, 1.0, vignette), u_params.x), 1.0);
+}
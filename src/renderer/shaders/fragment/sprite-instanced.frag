@@ -0,0 +1,15 @@
+#version 300 es
+precision highp float;
+
+uniform sampler2D u_sampler;
+
+in vec2 v_uv;
+in vec4 v_tint;
+
+out vec4 color;
+
+void main() {
+    vec4 tex_color = texture(u_sampler, v_uv);
+
+    color = tex_color * v_tint;
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+#version 300 es
+precision mediump float;
+
+// the unit quad, stretched over the whole viewport
+layout(location=0) in vec2 a_geom_vertex;
+
+out vec2 v_uv;
+
+void main() {
+    gl_Position = vec4(a_geom_vertex * 2.0 - 1.0, 0.0, 1.0);
+    v_uv = a_geom_vertex;
+}
@@ -0,0 +1,29 @@
+#version 300 es
+precision mediump float;
+
+layout(location=0) in vec2 a_geom_vertex;
+
+// per instance, see ParticleSystem::render
+// x, y, size, rotation
+layout(location=1) in vec4 a_particle;
+layout(location=2) in vec4 a_color;
+
+layout (std140) uniform ubo_camera {
+    uniform mat4 view;
+    uniform mat4 projection;
+} camera;
+
+out vec2 v_uv;
+out vec4 v_tint;
+
+void main() {
+    // the unit quad, centered on the particle and spun around that
+    vec2 corner = (a_geom_vertex - 0.5) * a_particle.z;
+    float s = sin(a_particle.w);
+    float c = cos(a_particle.w);
+    vec2 rotated = vec2((corner.x * c) - (corner.y * s), (corner.x * s) + (corner.y * c));
+
+    gl_Position = camera.projection * (camera.view * vec4(a_particle.xy + rotated, 0, 1));
+    v_uv = a_geom_vertex;
+    v_tint = a_color;
+}
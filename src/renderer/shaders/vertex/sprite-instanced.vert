@@ -0,0 +1,27 @@
+#version 300 es
+precision mediump float;
+
+layout(location=0) in vec2 a_geom_vertex;
+
+// per instance, see SpriteBatcher
+layout(location=2) in mat4 a_model;
+layout(location=6) in vec2 a_quad_scaler;
+// bottom-left corner and size, in texture space
+layout(location=7) in vec4 a_uv_rect;
+layout(location=8) in vec4 a_tint;
+
+layout (std140) uniform ubo_camera {
+    uniform mat4 view;
+    uniform mat4 projection;
+} camera;
+
+out vec2 v_uv;
+out vec4 v_tint;
+
+void main() {
+    mat4 mvp = (camera.projection * (camera.view * a_model));
+
+    gl_Position = mvp * vec4(a_geom_vertex * a_quad_scaler, 0, 1);
+    v_uv = a_uv_rect.xy + (a_geom_vertex * a_uv_rect.zw);
+    v_tint = a_tint;
+}
\ No newline at end of file
@@ -3,23 +3,29 @@ use nalgebra::geometry;
 
 use crate::{
     animation::data::Animation,
+    atlas::data::TextureAtlasView,
     background::data::BackgroundView,
+    camera::CameraView,
     collision::{
         data::{Collider, CollisionEventQueue, CollisionEventQueueViewMut},
         debug::CollisionDebugger,
     },
     config::CONFIG,
+    dom::DomView,
     enemy::{
         data::Enemy, effects::data::EnemyEffect, launcher::data::EnemyLauncher,
         physics::data::EnemyDirection,
     },
-    explosion::data::{Explosion, ExplosionSpawner, ExplosionSpawnerView},
+    explosion::data::{Explosion, ExplosionSpritesView},
     media::MediaView,
+    particles::data::{ParticleLayer, ParticleSystemViewMut},
+    post::data::{PostEffectsView, PostSettingsView},
     prelude::*,
     projectiles::data::Projectile,
+    tick::EndTickView,
 };
 
-use super::RendererViewMut;
+use super::{RendererView, RendererViewMut};
 
 pub fn render_sys(
     mut renderer: RendererViewMut,
@@ -38,52 +44,58 @@ pub fn render_sys(
         UniqueViewMut<CollisionDebugger>,
     ),
     animations: View<Animation>,
-    background: BackgroundView,
-    explosion_spawner: ExplosionSpawnerView,
+    scenery: (BackgroundView, CameraView, ParticleSystemViewMut),
+    sprites: (TextureAtlasView, ExplosionSpritesView),
+    post: (PostSettingsView, PostEffectsView),
 ) {
     let (enemies, enemy_launchers, projectiles, explosions, mut enemy_effects) = game_objects;
     let (colliders, mut collision_events, collision_debugger) = collision;
+    let (post_settings, post_effects) = post;
+    let (background, camera, mut particles) = scenery;
+    let (atlas, explosion_sprites) = sprites;
 
     if !(renderer.pre_draw().unwrap_ext()) {
         return;
     }
-    background.render(&mut renderer).unwrap_ext();
+    background.render(&mut renderer, &camera).unwrap_ext();
+    particles
+        .render(&mut renderer, ParticleLayer::Back)
+        .unwrap_ext();
 
+    // these only queue up sprites, they're drawn in batches by flush_sprites
     for (enemy, world_transform, animation, effect) in
         (&enemies, &world_transforms, &animations, &mut enemy_effects).iter()
     {
-        enemy
-            .render(&mut renderer, world_transform, animation, effect)
-            .unwrap_ext();
+        enemy.render(&mut renderer, &atlas, world_transform, animation, effect);
     }
 
     for (launcher, world_transform, animation) in
         (&enemy_launchers, &world_transforms, &animations).iter()
     {
-        launcher
-            .render(&mut renderer, world_transform, animation)
-            .unwrap_ext();
+        launcher.render(&mut renderer, &atlas, world_transform, animation);
     }
 
     for (projectile, world_transform) in (&projectiles, &world_transforms).iter() {
-        projectile
-            .render(&mut renderer, world_transform)
-            .unwrap_ext();
+        projectile.render(&mut renderer, &atlas, world_transform);
     }
 
     for (explosion, world_transform, animation) in
         (&explosions, &world_transforms, &animations).iter()
     {
-        explosion
-            .render(
-                &mut renderer,
-                world_transform,
-                &explosion_spawner,
-                animation,
-            )
-            .unwrap_ext();
+        explosion.render(
+            &mut renderer,
+            &atlas,
+            world_transform,
+            &explosion_sprites,
+            animation,
+        );
     }
 
+    renderer.flush_sprites().unwrap_ext();
+    particles
+        .render(&mut renderer, ParticleLayer::Front)
+        .unwrap_ext();
+
     if collision_debugger.draw {
         for (entity, collider) in colliders.iter().with_id() {
             let event = collision_events
@@ -96,5 +108,39 @@ pub fn render_sys(
         }
     }
 
-    renderer.post_draw().unwrap_ext();
+    renderer
+        .post_draw(&post_effects.passes(&post_settings))
+        .unwrap_ext();
+}
+
+// Once a second while the colliders are being drawn, i.e. while debugging
+// the counts are from the last complete frame (see Renderer::draw_stats)
+pub fn draw_stats_log_sys(
+    dom: DomView,
+    renderer: RendererView,
+    mut collision_debugger: UniqueViewMut<CollisionDebugger>,
+    end_tick: EndTickView,
+) {
+    if !collision_debugger.draw {
+        return;
+    }
+
+    let now = dom.window.performance().unwrap_ext().now();
+    if collision_debugger
+        .stats_logged_at
+        .map_or(false, |last| now - last < 1000.0)
+    {
+        return;
+    }
+    collision_debugger.stats_logged_at = Some(now);
+
+    let stats = renderer.draw_stats;
+    log::info!(
+        "fps: {:.0}, draw calls: {}, sprite batches: {}, sprites: {}, particles: {}",
+        end_tick.fps,
+        stats.draw_calls,
+        stats.sprite_batches,
+        stats.sprites,
+        stats.particles
+    );
 }
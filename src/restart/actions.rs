@@ -0,0 +1,126 @@
+use crate::{
+    background::data::BackgroundViewMut,
+    bomber::data::{Bomber, BomberViewMut},
+    camera::{CameraEffects, CameraEffectsViewMut},
+    collision::data::CollisionEventQueueViewMut,
+    config::CONFIG,
+    controller::recording::{InputRecorder, InputRecorderViewMut, InputReplayViewMut},
+    delete::{data::MarkForDeletion, systems::delete_sys},
+    dom::events::{UiEvent, UiEventQueue},
+    enemy::{data::Enemy, launcher::data::EnemyLauncher},
+    explosion::data::{Explosion, ExplosionSpawnerViewMut},
+    hit::data::HitEventQueueViewMut,
+    level::{
+        actions::spawn_level,
+        data::{LevelRunnerView, LevelRunnerViewMut},
+    },
+    particles::data::ParticleSystemViewMut,
+    post::data::{PostEffects, PostEffectsViewMut},
+    prelude::*,
+    projectiles::data::{Projectile, ProjectileSpawnerViewMut},
+    stats::data::{RunStats, RunStatsViewMut},
+    tick::{FrameCount, FrameCountViewMut, PauseTick, PauseTickViewMut},
+};
+
+use super::data::RestartRequestViewMut;
+
+// called right after the "controller" workload, which is where a restart gets requested
+pub fn restart_if_requested(world: &World) {
+    let requested =
+        world.run(|mut restart: RestartRequestViewMut| std::mem::take(&mut restart.requested));
+
+    if requested {
+        restart_game(world);
+    }
+}
+
+pub fn restart_game(world: &World) {
+    world.run(
+        |enemies: View<Enemy>,
+         projectiles: View<Projectile>,
+         explosions: View<Explosion>,
+         launchers: View<EnemyLauncher>,
+         mut deletions: ViewMut<MarkForDeletion>| {
+            let ids = (&enemies)
+                .iter()
+                .with_id()
+                .map(|(id, _)| id)
+                .chain((&projectiles).iter().with_id().map(|(id, _)| id))
+                .chain((&explosions).iter().with_id().map(|(id, _)| id))
+                .chain((&launchers).iter().with_id().map(|(id, _)| id))
+                .collect::<Vec<_>>();
+
+            for id in ids {
+                deletions.add_component_unchecked(id, MarkForDeletion {});
+            }
+        },
+    );
+
+    world.run(delete_sys);
+
+    // a fresh run is a fresh seed (unless it's pinned via config), and a fresh recording to go with it
+    // while replaying, it starts over from the top of the recording instead
+    let seed = world.run(
+        |mut rand: RandViewMut,
+         mut frame_count: FrameCountViewMut,
+         mut recorder: InputRecorderViewMut,
+         mut replay: InputReplayViewMut,
+         level_runner: LevelRunnerView| {
+            let seed = match &replay.recording {
+                Some(recording) => recording.seed,
+                None => CONFIG.session_seed(),
+            };
+
+            rand.reseed(seed);
+            *frame_count = FrameCount::default();
+            replay.cursor = 0;
+            if !replay.is_active() {
+                *recorder = InputRecorder::new(
+                    seed,
+                    level_runner.level.as_ref().map(|level| level.name.clone()),
+                );
+            }
+
+            seed
+        },
+    );
+
+    // whatever was still playing out from the last run
+    world.run(
+        |mut hit_events: HitEventQueueViewMut,
+         mut camera_effects: CameraEffectsViewMut,
+         mut post_effects: PostEffectsViewMut,
+         mut particles: ParticleSystemViewMut| {
+            hit_events.clear();
+            *camera_effects = CameraEffects::default();
+            *post_effects = PostEffects::default();
+            particles.clear();
+        },
+    );
+
+    world.run(
+        |mut explosion_spawner: ExplosionSpawnerViewMut,
+         mut projectile_spawner: ProjectileSpawnerViewMut,
+         mut collision_events: CollisionEventQueueViewMut,
+         mut bomber: BomberViewMut,
+         mut pause_tick: PauseTickViewMut,
+         mut ui_events: UniqueViewMut<UiEventQueue>,
+         mut stats: RunStatsViewMut,
+         mut level_runner: LevelRunnerViewMut,
+         mut background: BackgroundViewMut| {
+            explosion_spawner.to_spawn.clear();
+            explosion_spawner.spawned.clear();
+            projectile_spawner.to_spawn.clear();
+            collision_events.clear();
+            *bomber = Bomber::new();
+            level_runner.reset();
+            background.start_run(level_runner.level.as_ref());
+            *stats = RunStats::new();
+            *pause_tick = PauseTick::Running;
+            ui_events.push(UiEvent::Restarted(seed));
+            ui_events.push(UiEvent::RunStats(RunStats::new()));
+        },
+    );
+
+    spawn_level(world);
+}
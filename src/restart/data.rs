@@ -0,0 +1,11 @@
+use crate::prelude::*;
+
+pub type RestartRequestViewMut<'a> = UniqueViewMut<'a, RestartRequest>;
+pub type RestartRequestView<'a> = UniqueView<'a, RestartRequest>;
+
+// set by the controller when it sees Input::ResetButton
+// the actual restart needs the whole World, so it happens outside of the workloads
+#[derive(Component, Unique, Default)]
+pub struct RestartRequest {
+    pub requested: bool,
+}
@@ -0,0 +1,4 @@
+// Restarting a run in-place, without reloading the page
+// the spawners (and therefore the loaded media and textures) are kept, everything else is reset
+pub mod actions;
+pub mod data;
@@ -0,0 +1,122 @@
+// Everything the sim needs from the media folder, read straight off the disk
+// i.e. for a native host without a browser to fetch and decode things (see tests/headless.rs)
+//
+// sprite sheets come from their json info and single images from their png header
+// there's no image decoding though, so every mask is solid, and pixel-perfect collision is really just the quads
+use std::{fs, path::Path};
+
+use anyhow::Context;
+
+use crate::{
+    collision::layer::CollisionRules,
+    difficulty::data::DifficultyPresets,
+    enemy::{
+        attack::data::WeaponStats,
+        data::{
+            EnemySpriteSheets, EnemySpriteSheetsFour, EnemySpriteSheetsOne, EnemySpriteSheetsThree,
+            EnemySpriteSheetsTwo,
+        },
+        spawner::EnemySpawner,
+    },
+    explosion::data::ExplosionSprites,
+    level::data::Levels,
+    media::SpriteSheetMediaInfo,
+    prelude::*,
+    projectiles::data::{ProjectileSprite, ProjectileSprites},
+    spritesheet::SpriteSheet,
+};
+
+pub struct HeadlessMedia {
+    pub difficulty: DifficultyPresets,
+    pub levels: Levels,
+    pub collision: CollisionRules,
+    pub weapons: WeaponStats,
+    pub enemy_spawner: EnemySpawner,
+    pub projectile_sprites: ProjectileSprites,
+    pub explosion_sprites: ExplosionSprites,
+}
+
+impl HeadlessMedia {
+    // dir is the media folder, i.e. the one with data/ and image/ in it
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+
+        let text = |path: &str| {
+            fs::read_to_string(dir.join(path)).with_context(|| format!("could not read {path}"))
+        };
+
+        let sheet = |path: &str| -> Result<SpriteSheet> {
+            let info: SpriteSheetMediaInfo =
+                serde_json::from_str(&text(&format!("image/{path}.json"))?)
+                    .with_context(|| format!("{path}.json is not valid"))?;
+            SpriteSheet::from_info(&info)
+        };
+
+        let image = |path: &str| -> Result<ProjectileSprite> {
+            let bytes = fs::read(dir.join(format!("image/{path}.png")))
+                .with_context(|| format!("could not read {path}.png"))?;
+            let (width, height) =
+                png_size(&bytes).ok_or_else(|| anyhow!("{path}.png is not a png"))?;
+            Ok(ProjectileSprite::solid(width, height))
+        };
+
+        Ok(Self {
+            difficulty: DifficultyPresets::from_json(&text("data/difficulty.json")?)
+                .context("difficulty.json")?,
+            levels: Levels::from_json(&text("data/levels.json")?).context("levels.json")?,
+            collision: CollisionRules::from_json(&text("data/collision.json")?)
+                .context("collision.json")?,
+            weapons: WeaponStats::from_json(&text("data/weapons.json")?).context("weapons.json")?,
+            enemy_spawner: EnemySpawner {
+                sprite_sheets: EnemySpriteSheets {
+                    one: Some(EnemySpriteSheetsOne {
+                        blast: sheet("terrorists/1/blast")?,
+                        hurt: sheet("terrorists/1/hurt")?,
+                        idle: sheet("terrorists/1/idle")?,
+                        walk: sheet("terrorists/1/walk")?,
+                    }),
+                    two: Some(EnemySpriteSheetsTwo {
+                        hurt: sheet("terrorists/2/hurt")?,
+                        idle: sheet("terrorists/2/idle")?,
+                        shooting: sheet("terrorists/2/shooting")?,
+                        walk: sheet("terrorists/2/walk")?,
+                    }),
+                    three: Some(EnemySpriteSheetsThree {
+                        hurt: sheet("terrorists/3/hurt")?,
+                        idle: sheet("terrorists/3/idle")?,
+                        shoot: sheet("terrorists/3/shoot")?,
+                        walk: sheet("terrorists/3/walk")?,
+                    }),
+                    four: Some(EnemySpriteSheetsFour {
+                        hurt: sheet("terrorists/4/hurt")?,
+                        idle: sheet("terrorists/4/idle")?,
+                        shoot: sheet("terrorists/4/shoot")?,
+                    }),
+                },
+                launcher_sprite_sheet: sheet("object/launcher")?,
+            },
+            projectile_sprites: ProjectileSprites {
+                bad_rocket: image("object/rocket-bad")?,
+                good_rocket: image("object/rocket-good")?,
+                bullet: image("object/bullet")?,
+            },
+            explosion_sprites: ExplosionSprites {
+                spritesheet: sheet("object/explosion")?,
+            },
+        })
+    }
+}
+
+// the signature, then the IHDR chunk, which always comes first and starts with the width and height
+fn png_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    if bytes.get(0..8)? != SIGNATURE || bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+    let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+
+    Some((width, height))
+}
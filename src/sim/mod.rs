@@ -0,0 +1,8 @@
+// The "sim" layer is the subset of the game that runs without a browser or GPU
+// its systems only touch plain components and uniques, and anything web-facing
+// (ui phase changes, audio) is pushed onto an event queue for the host to drain
+//
+// In the browser, main wires it up alongside the renderer and dom
+// A headless host (e.g. native `cargo test`) can drive it against a plain World
+pub mod headless;
+pub mod workloads;
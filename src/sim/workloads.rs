@@ -0,0 +1,176 @@
+use shipyard_scenegraph::init::init_scenegraph;
+
+use crate::{
+    audio::{audio_hit_sys, AudioEventQueue},
+    bomber::{data::Bomber, systems::bomber_drop_sys},
+    collision::{
+        data::CollisionEventQueue,
+        layer::CollisionRules,
+        systems::{
+            detect_geometric_collision_sys, enemy_collision_layer_sys, mask_collision_check_sys,
+            update_collider_sys,
+        },
+    },
+    delete::systems::delete_sys,
+    difficulty::data::{Difficulty, DifficultyPresets},
+    dom::events::UiEventQueue,
+    enemy::{
+        animation::systems::enemy_animation_sys,
+        attack::{data::WeaponStats, systems::enemy_weapon_sys},
+        controller::systems::enemy_controller_physics_sys,
+        destroy::enemy_destroy_event_sys,
+        health::systems::{enemy_health_sys, enemy_hit_sys},
+        launcher::systems::launcher_animation_sys,
+        physics::systems::enemy_position_sys,
+        select::enemy_select_event_sys,
+    },
+    explosion::{
+        animation::explosion_animation_sys,
+        data::ExplosionSpawner,
+        systems::{explosion_hit_sys, explosion_spawn_sys},
+    },
+    game_over::systems::game_over_sys,
+    hit::{data::HitEventQueue, systems::hit_event_clear_sys},
+    layout::systems::flush_layout_sys,
+    level::{
+        actions::spawn_level,
+        data::{Level, LevelRunner},
+        systems::level_wave_sys,
+    },
+    prelude::*,
+    projectiles::{
+        data::ProjectileSpawner,
+        systems::{projectile_physics_sys, projectile_spawn_sys},
+    },
+    restart::data::RestartRequest,
+    sim::headless::HeadlessMedia,
+    stats::{
+        data::RunStats,
+        systems::{run_stats_hit_sys, run_stats_sys},
+    },
+    tick::{frame_count_sys, FrameCount, PauseTick, UpdateTick, UpdateTickViewMut},
+    winner::systems::winner_sys,
+};
+
+pub const SIM_UPDATE_WORKLOAD: &str = "sim_update";
+
+// the uniques that every sim system expects to find in the world
+// the browser host adds its own (renderer, dom, media, etc.) on top of these
+// difficulty presets, collision rules, weapon stats and the level are passed in since they're loaded as data
+// and so are the sprites (EnemySpawner, ProjectileSprites, ExplosionSprites), see sim/headless.rs
+pub fn init_sim_world(
+    world: &World,
+    seed: u64,
+    difficulty: DifficultyPresets,
+    collision: CollisionRules,
+    weapons: WeaponStats,
+    level: Option<Level>,
+) {
+    world.add_unique_non_send_sync(Rand::new(seed));
+    world.add_unique_non_send_sync(CollisionEventQueue::new());
+    world.add_unique(HitEventQueue::new());
+    world.add_unique(AudioEventQueue::new());
+    world.add_unique(UiEventQueue::new());
+    world.add_unique(ProjectileSpawner::new());
+    world.add_unique(ExplosionSpawner::new());
+    world.add_unique(Bomber::new());
+    world.add_unique(Difficulty::new(difficulty));
+    world.add_unique(collision);
+    world.add_unique(weapons);
+    world.add_unique(LevelRunner::new(level));
+    world.add_unique(UpdateTick::default());
+    world.add_unique(FrameCount::default());
+    world.add_unique(RestartRequest::default());
+    world.add_unique(RunStats::new());
+    world.add_unique(PauseTick::Running);
+}
+
+// The "update" workload without anything that needs the renderer
+// pixel-perfect collision still happens with the cpu backend (alpha masks)
+pub fn sim_update_workload() -> Workload {
+    update_workload(
+        SIM_UPDATE_WORKLOAD,
+        Workload::new("gpu_collision"),
+        Workload::new("hit_effects"),
+    )
+}
+
+// The fixed step, in order, for both the browser and headless
+// main passes in what needs the renderer, so the two can't drift apart:
+// gpu_collision reads back last frame's occlusion queries into hits (the gpu backend)
+// hit_effects reacts to this frame's hits, e.g. the camera/post effects
+pub fn update_workload(
+    name: &'static str,
+    mut gpu_collision: Workload,
+    mut hit_effects: Workload,
+) -> Workload {
+    Workload::new(name)
+        .with_system(enemy_select_event_sys)
+        .with_system(enemy_animation_sys)
+        .with_system(launcher_animation_sys)
+        .with_system(explosion_animation_sys)
+        .with_system(enemy_health_sys)
+        .with_system(enemy_weapon_sys)
+        .with_system(game_over_sys)
+        .with_system(winner_sys)
+        .with_system(enemy_destroy_event_sys)
+        .with_system(enemy_controller_physics_sys)
+        .with_system(enemy_position_sys)
+        .with_system(level_wave_sys)
+        .with_system(bomber_drop_sys)
+        .with_system(explosion_spawn_sys)
+        .with_system(projectile_spawn_sys)
+        .with_system(projectile_physics_sys)
+        .with_system(flush_layout_sys)
+        .with_system(local_transform_sys)
+        .with_system(world_transform_sys)
+        .with_system(update_collider_sys)
+        .with_system(enemy_collision_layer_sys)
+        .with_system(detect_geometric_collision_sys)
+        .with_system(hit_event_clear_sys)
+        .append(&mut gpu_collision)
+        .with_system(mask_collision_check_sys)
+        .with_system(explosion_hit_sys)
+        .with_system(enemy_hit_sys)
+        .with_system(run_stats_hit_sys)
+        .with_system(audio_hit_sys)
+        .append(&mut hit_effects)
+        .with_system(delete_sys)
+        .with_system(run_stats_sys)
+        .with_system(frame_count_sys)
+}
+
+// Sets up a world for headless use: the sim uniques, the sprites, the scenegraph, and the sim workload
+// then spawns whoever's on the field, like a run in the browser starts out
+pub fn init_headless(world: &World, seed: u64, media: HeadlessMedia, level: Option<Level>) {
+    let HeadlessMedia {
+        difficulty,
+        collision,
+        weapons,
+        enemy_spawner,
+        projectile_sprites,
+        explosion_sprites,
+        ..
+    } = media;
+
+    world.add_unique(enemy_spawner);
+    world.add_unique(projectile_sprites);
+    world.add_unique(explosion_sprites);
+    init_sim_world(world, seed, difficulty, collision, weapons, level);
+    sim_update_workload().add_to_world(world).unwrap_ext();
+    init_scenegraph::<Vec3, Quat, Mat4, f32>(world);
+
+    spawn_level(world);
+}
+
+// Runs a single physics step, the same way the browser's mainloop does for "update"
+// everything is laid out in design units, so there's no screen size to pass in
+pub fn sim_step(world: &World, delta: f64) {
+    if *world.borrow::<UniqueView<PauseTick>>().unwrap_ext() != PauseTick::Running {
+        return;
+    }
+
+    *world.borrow::<UpdateTickViewMut>().unwrap_ext() = UpdateTick { delta };
+
+    world.run_workload(SIM_UPDATE_WORKLOAD).unwrap_ext();
+}
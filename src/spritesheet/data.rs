@@ -1,63 +1,90 @@
-use awsm_web::webgl::{
-    PixelFormat, SimpleTextureOptions, TextureTarget, TextureWrapMode, WebGlTextureSource,
-};
 use web_sys::HtmlImageElement;
 
-use crate::{config::CONFIG, media::SpriteSheetMediaInfo, prelude::*, renderer::Renderer};
+use crate::{
+    atlas::data::{AtlasRegion, TextureAtlas},
+    collision::mask::{image_rgba, AlphaMask},
+    config::CONFIG,
+    media::SpriteSheetMediaInfo,
+    prelude::*,
+};
 
 #[derive(Clone, Debug)]
 pub struct SpriteSheet {
-    pub texture_id: Id,
-    pub cells: Vec<Bounds>,
-    pub atlas_width: f32,
-    pub atlas_height: f32,
+    // where each cell is in the texture atlas
+    pub cells: Vec<AtlasRegion>,
     pub anchor_x: f32,
     pub max_cell_width: f32,
     pub max_cell_height: f32,
     pub cell_duration: f64,
+    // one per cell, shared between all the clones
+    pub masks: Vec<Arc<AlphaMask>>,
 }
 
 impl SpriteSheet {
+    // the cells are drawn from the atlas, but the masks still come from the original image
     pub fn new(
-        renderer: &mut Renderer,
+        atlas: &TextureAtlas,
         img: &HtmlImageElement,
         info: &SpriteSheetMediaInfo,
     ) -> Result<Self> {
-        let texture_id = renderer.create_texture()?;
+        let source_cells = info.cells()?;
+        let cells = atlas.regions(img)?.to_vec();
+        if cells.len() != source_cells.len() {
+            return Err(anyhow!(
+                "{} has {} cells but {} in the atlas",
+                img.src(),
+                source_cells.len(),
+                cells.len()
+            ));
+        }
 
-        renderer.assign_simple_texture(
-            texture_id,
-            TextureTarget::Texture2d,
-            &SimpleTextureOptions {
-                pixel_format: PixelFormat::Rgba,
-                wrap_s: Some(TextureWrapMode::Repeat),
-                wrap_t: Some(TextureWrapMode::Repeat),
-                ..SimpleTextureOptions::default()
-            },
-            &WebGlTextureSource::ImageElement(&img),
-        )?;
+        let rgba = image_rgba(img)?;
+        let masks = source_cells
+            .iter()
+            .map(|cell| Arc::new(AlphaMask::from_rgba(&rgba, img.width(), cell)))
+            .collect();
+
+        Ok(Self::from_cells(cells, masks, info))
+    }
 
-        let atlas_width = img.width() as f32;
-        let atlas_height = img.height() as f32;
+    // Without a browser, i.e. the headless sim (see sim/headless.rs)
+    // only the info is needed, the sheet is its own "page" and every cell is fully solid
+    pub fn from_info(info: &SpriteSheetMediaInfo) -> Result<Self> {
+        let source_cells = info.cells()?;
+        let (width, height) = source_cells.iter().fold((0.0f32, 0.0f32), |(w, h), cell| {
+            (
+                w.max(cell.right() as f32),
+                h.max((cell.y + cell.height) as f32),
+            )
+        });
 
-        let cells = info
-            .sub_textures
+        let masks = source_cells
             .iter()
-            .map(|info| {
-                Ok(Bounds {
-                    x: info.x.parse()?,
-                    y: info.y.parse()?,
-                    width: info.width.parse()?,
-                    height: info.height.parse()?,
-                })
+            .map(|cell| Arc::new(AlphaMask::solid(cell.width as u32, cell.height as u32)))
+            .collect();
+
+        let cells = source_cells
+            .into_iter()
+            .map(|bounds| AtlasRegion {
+                page: 0,
+                bounds,
+                atlas_width: width,
+                atlas_height: height,
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect();
+
+        Ok(Self::from_cells(cells, masks, info))
+    }
 
+    fn from_cells(
+        cells: Vec<AtlasRegion>,
+        masks: Vec<Arc<AlphaMask>>,
+        info: &SpriteSheetMediaInfo,
+    ) -> Self {
         let anchor_x = match info.anchor_x {
             Some(anchor_x) => anchor_x,
             None => {
-                (cells.iter().fold(0.0, |acc, curr| acc + curr.width) as f32 / cells.len() as f32)
-                    / 2.0
+                (cells.iter().fold(0.0, |acc, curr| acc + curr.width()) / cells.len() as f32) / 2.0
             }
         };
 
@@ -70,21 +97,16 @@ impl SpriteSheet {
             cells
                 .iter()
                 .fold((0.0f32, 0.0f32), |(acc_width, acc_height), curr| {
-                    (
-                        acc_width.max(curr.width as f32),
-                        acc_height.max(curr.height as f32),
-                    )
+                    (acc_width.max(curr.width()), acc_height.max(curr.height()))
                 });
 
-        Ok(Self {
-            texture_id,
-            atlas_width,
-            atlas_height,
+        Self {
             cells,
             anchor_x,
             max_cell_width,
             max_cell_height,
             cell_duration,
-        })
+            masks,
+        }
     }
 }
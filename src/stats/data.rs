@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use crate::{enemy::data::EnemyKind, prelude::*};
+
+pub type RunStatsViewMut<'a> = UniqueViewMut<'a, RunStats>;
+pub type RunStatsView<'a> = UniqueView<'a, RunStats>;
+
+const SCORE_PER_INTERCEPT: u32 = 100;
+const SCORE_PER_SECOND: u32 = 1;
+
+#[derive(Component, Unique, Clone, Debug, Default, PartialEq)]
+pub struct RunStats {
+    // in milliseconds, only counts while running (i.e. not paused)
+    pub time_survived: f64,
+    // bombs taken out, by the kind of enemy that fired the projectile
+    pub intercepts: HashMap<EnemyKind, u32>,
+    pub shots_fired: HashMap<EnemyKind, u32>,
+    pub hides: u32,
+    pub jumps: u32,
+    // which enemy died, and at what point in the run (same units as time_survived)
+    pub deaths: Vec<(EnemyKind, f64)>,
+    // set whenever something changed that the ui cares about
+    // cleared when the ui is told about it
+    pub dirty: bool,
+}
+
+impl RunStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_shot(&mut self, kind: EnemyKind) {
+        *self.shots_fired.entry(kind).or_default() += 1;
+        self.dirty = true;
+    }
+
+    pub fn record_intercept(&mut self, kind: EnemyKind) {
+        *self.intercepts.entry(kind).or_default() += 1;
+        self.dirty = true;
+    }
+
+    pub fn record_hide(&mut self) {
+        self.hides += 1;
+        self.dirty = true;
+    }
+
+    pub fn record_jump(&mut self) {
+        self.jumps += 1;
+        self.dirty = true;
+    }
+
+    pub fn record_death(&mut self, kind: EnemyKind) {
+        self.deaths.push((kind, self.time_survived));
+        self.dirty = true;
+    }
+
+    pub fn advance_time(&mut self, delta: f64) {
+        let prev_seconds = self.seconds_survived();
+        self.time_survived += delta;
+        // the ui only shows whole seconds
+        if self.seconds_survived() != prev_seconds {
+            self.dirty = true;
+        }
+    }
+
+    pub fn seconds_survived(&self) -> u32 {
+        (self.time_survived / 1000.0) as u32
+    }
+
+    pub fn total_intercepts(&self) -> u32 {
+        self.intercepts.values().sum()
+    }
+
+    pub fn total_shots_fired(&self) -> u32 {
+        self.shots_fired.values().sum()
+    }
+
+    pub fn score(&self) -> u32 {
+        (self.total_intercepts() * SCORE_PER_INTERCEPT)
+            + (self.seconds_survived() * SCORE_PER_SECOND)
+    }
+}
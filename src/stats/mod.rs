@@ -0,0 +1,4 @@
+// Per-run statistics and the score derived from them
+// these are fed from the same places that push audio events and spawn explosions
+pub mod data;
+pub mod systems;
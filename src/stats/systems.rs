@@ -0,0 +1,31 @@
+use crate::{
+    dom::events::{UiEvent, UiEventQueue},
+    hit::data::HitEventQueueView,
+    prelude::*,
+    tick::UpdateTickView,
+};
+
+use super::data::RunStatsViewMut;
+
+// runs at the end of the "update" workload, after everything else has had a chance to record
+pub fn run_stats_sys(
+    mut stats: RunStatsViewMut,
+    mut ui_events: UniqueViewMut<UiEventQueue>,
+    tick: UpdateTickView,
+) {
+    stats.advance_time(tick.delta);
+
+    if stats.dirty {
+        stats.dirty = false;
+        ui_events.push(UiEvent::RunStats(stats.clone()));
+    }
+}
+
+// a bomb taken out by something that was fired, that's an intercept
+pub fn run_stats_hit_sys(hit_events: HitEventQueueView, mut stats: RunStatsViewMut) {
+    for hit in hit_events.iter().filter(|hit| hit.victim_kind.is_bomb()) {
+        if let Some(shooter) = hit.attacker_kind.shooter() {
+            stats.record_intercept(shooter);
+        }
+    }
+}
@@ -17,8 +17,6 @@ pub type UpdateTickView<'a> = UniqueView<'a, UpdateTick>;
 #[derive(Component, Unique, Default)]
 pub struct UpdateTick {
     pub delta: f64,
-    pub viewport_width: f64,
-    pub viewport_height: f64,
 }
 
 pub type DrawTickViewMut<'a> = UniqueViewMut<'a, DrawTick>;
@@ -56,5 +54,50 @@ pub enum PauseTick {
         timestamp: f64,
         previous: Box<PauseTick>,
     },
+    // the gl context is gone, until the browser gives it back (see context_loss)
+    LostContext {
+        previous: Box<PauseTick>,
+    },
     GameOver {},
+    Winner {},
+}
+
+impl PauseTick {
+    // visibility is tracked separately, so if the page is hidden this goes underneath that
+    pub fn context_lost(&mut self) {
+        match self {
+            PauseTick::LostContext { .. } => {}
+            PauseTick::LostVisibility { previous, .. } => previous.context_lost(),
+            _ => {
+                let previous = Box::new(self.clone());
+                *self = PauseTick::LostContext { previous };
+            }
+        }
+    }
+
+    // back to whatever it was before the context was lost
+    // and same as above, it may be underneath LostVisibility
+    pub fn context_restored(&mut self) {
+        match self {
+            PauseTick::LostContext { previous } => {
+                let previous = (**previous).clone();
+                *self = previous;
+            }
+            PauseTick::LostVisibility { previous, .. } => previous.context_restored(),
+            _ => {}
+        }
+    }
+}
+
+pub type FrameCountViewMut<'a> = UniqueViewMut<'a, FrameCount>;
+pub type FrameCountView<'a> = UniqueView<'a, FrameCount>;
+
+// The number of fixed "update" steps that have run so far
+// unlike the timestamps above, this is deterministic, so it's the timeline for recording and replay
+#[derive(Component, Unique, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameCount(pub u64);
+
+// runs at the very end of the "update" workload
+pub fn frame_count_sys(mut frame_count: FrameCountViewMut) {
+    frame_count.0 += 1;
 }
@@ -0,0 +1,61 @@
+use crate::{
+    bomber::data::BomberView,
+    config::CONFIG,
+    delete::data::MarkForDeletion,
+    dom::{
+        events::{UiEvent, UiEventQueue},
+        ui::game::GameUiPhase,
+    },
+    enemy::data::Enemy,
+    level::data::{LevelRunnerView, VictoryCondition},
+    prelude::*,
+    projectiles::data::{Projectile, ProjectileMovement},
+    stats::data::RunStatsView,
+    tick::PauseTick,
+};
+
+// You win by surviving all the bombs
+// i.e. the bomber has dropped its last one, and there are none left in the air
+// unless a level says otherwise (see VictoryCondition)
+// runs after game_over_sys, so losing your last enemy to the last bomb is still a loss
+pub fn winner_sys(
+    mut pause_tick: UniqueViewMut<PauseTick>,
+    bomber: BomberView,
+    enemies: View<Enemy>,
+    projectiles: View<Projectile>,
+    deletions: View<MarkForDeletion>,
+    mut ui_events: UniqueViewMut<UiEventQueue>,
+    stats: RunStatsView,
+    level_runner: LevelRunnerView,
+) {
+    if *pause_tick != PauseTick::Running {
+        return;
+    }
+
+    // whether the remaining bombs need to be survived too
+    let (reached, wait_for_bombs) = match level_runner.victory() {
+        None => (bomber.bombs_dropped >= CONFIG.bombs_to_win, true),
+        Some(VictoryCondition::SurviveAllWaves) => (level_runner.finished(), true),
+        Some(VictoryCondition::SurviveTime { seconds }) => {
+            (stats.time_survived >= seconds * 1000.0, false)
+        }
+        Some(VictoryCondition::Intercepts { count }) => (stats.total_intercepts() >= count, false),
+    };
+
+    if !reached {
+        return;
+    }
+
+    let bombs_in_flight = wait_for_bombs
+        && (&projectiles, !&deletions)
+            .iter()
+            .any(|(projectile, _)| matches!(projectile.movement, ProjectileMovement::Bomb { .. }));
+
+    let enemies_remaining = (&enemies, !&deletions).iter().next().is_some();
+
+    if !bombs_in_flight && enemies_remaining {
+        *pause_tick = PauseTick::Winner {};
+        ui_events.push(UiEvent::RunEnded(stats.clone()));
+        ui_events.push(UiEvent::GamePhase(Some(GameUiPhase::Winner)));
+    }
+}
@@ -0,0 +1,136 @@
+// The atlas packing algorithm, which has to place everything without overlaps and within the page size
+//
+// cargo test --test atlas_packer
+//
+// like benches/broadphase.rs, the module only depends on std so it's pulled in directly
+#[path = "../src/atlas/packer.rs"]
+#[allow(dead_code)]
+mod packer;
+
+use packer::{pack, PackError, Packing};
+
+// checks everything a packing has to guarantee, whatever the layout ends up being
+fn assert_valid(sizes: &[(u32, u32)], packing: &Packing, max_size: u32, padding: u32) {
+    assert_eq!(packing.placements.len(), sizes.len());
+
+    for (index, (placement, &(width, height))) in packing.placements.iter().zip(sizes).enumerate() {
+        let (page_width, page_height) = packing.pages[placement.page];
+        assert!(page_width <= max_size && page_height <= max_size);
+        assert!(
+            placement.x >= padding
+                && placement.y >= padding
+                && placement.x + width + padding <= page_width
+                && placement.y + height + padding <= page_height,
+            "rect {index} is outside of its page"
+        );
+    }
+
+    for a in 0..sizes.len() {
+        for b in (a + 1)..sizes.len() {
+            let (pa, pb) = (packing.placements[a], packing.placements[b]);
+            if pa.page != pb.page {
+                continue;
+            }
+            let (wa, ha) = sizes[a];
+            let (wb, hb) = sizes[b];
+            // gutters included, so neighbours are at least 2 * padding apart
+            let apart = pa.x + wa + padding * 2 <= pb.x
+                || pb.x + wb + padding * 2 <= pa.x
+                || pa.y + ha + padding * 2 <= pb.y
+                || pb.y + hb + padding * 2 <= pa.y;
+            assert!(apart, "rects {a} and {b} overlap");
+        }
+    }
+}
+
+#[test]
+fn empty() {
+    let packing = pack(&[], 1024, 2).unwrap();
+    assert!(packing.placements.is_empty());
+    assert!(packing.pages.is_empty());
+}
+
+#[test]
+fn single_rect_sits_inside_its_gutter() {
+    let sizes = [(100, 50)];
+    let packing = pack(&sizes, 1024, 2).unwrap();
+
+    assert_eq!(packing.pages, vec![(104, 54)]);
+    assert_eq!((packing.placements[0].x, packing.placements[0].y), (2, 2));
+    assert_valid(&sizes, &packing, 1024, 2);
+}
+
+#[test]
+fn same_height_rects_share_a_shelf() {
+    let sizes = [(10, 20), (30, 20), (40, 20)];
+    let packing = pack(&sizes, 1024, 0).unwrap();
+
+    assert_eq!(packing.pages, vec![(80, 20)]);
+    assert!(packing.placements.iter().all(|placement| placement.y == 0));
+    assert_valid(&sizes, &packing, 1024, 0);
+}
+
+#[test]
+fn shorter_rects_fill_earlier_shelves() {
+    // the tall ones open the first shelf, the short one still fits next to them
+    let sizes = [(8, 4), (16, 16), (16, 16)];
+    let packing = pack(&sizes, 64, 0).unwrap();
+
+    assert_eq!(packing.pages, vec![(40, 16)]);
+    assert_eq!(
+        (packing.placements[0].x, packing.placements[0].y),
+        (32, 0)
+    );
+    assert_valid(&sizes, &packing, 64, 0);
+}
+
+#[test]
+fn overflows_onto_more_pages() {
+    let sizes = vec![(60, 60); 5];
+    let packing = pack(&sizes, 128, 2).unwrap();
+
+    // 64x64 slots, so four to a page
+    assert_eq!(packing.pages.len(), 2);
+    assert_eq!(
+        packing
+            .placements
+            .iter()
+            .filter(|placement| placement.page == 1)
+            .count(),
+        1
+    );
+    assert_valid(&sizes, &packing, 128, 2);
+}
+
+#[test]
+fn too_large() {
+    let sizes = [(10, 10), (126, 10)];
+    assert_eq!(
+        pack(&sizes, 128, 2),
+        Err(PackError::TooLarge {
+            index: 1,
+            width: 126,
+            height: 10
+        })
+    );
+
+    // without the gutter it fits exactly
+    assert!(pack(&[(128, 128)], 128, 0).is_ok());
+}
+
+#[test]
+fn many_mixed_rects() {
+    // deterministic pseudo-random sizes, roughly like sprite cells and projectile images
+    let mut seed: u32 = 1234;
+    let mut next = |range: u32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (seed >> 16) % range + 1
+    };
+    let sizes: Vec<(u32, u32)> = (0..300).map(|_| (next(200), next(200))).collect();
+
+    let packing = pack(&sizes, 1024, 2).unwrap();
+    assert_valid(&sizes, &packing, 1024, 2);
+
+    // same input, same output
+    assert_eq!(pack(&sizes, 1024, 2).unwrap(), packing);
+}
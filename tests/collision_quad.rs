@@ -0,0 +1,262 @@
+// Checks that world points land on the right part of the sprite
+// in particular for enemies facing left, which are flipped via Scale.x = -1
+// and then that the cpu backend samples the right cell's AlphaMask there
+//
+// cargo test --test collision_quad
+//
+// the targets hang off the sprite sheets and projectiles, so like tests/headless.rs this goes through the lib
+
+use std::sync::Arc;
+
+use not_a_game::{
+    atlas::data::AtlasRegion,
+    collision::{
+        data::{Collider, CollisionEvent, CollisionEventTarget},
+        mask::AlphaMask,
+        quad::QuadBasis,
+    },
+    spritesheet::SpriteSheet,
+    utils::bounds::Bounds,
+};
+use shipyard::EntityId;
+
+const WIDTH: f32 = 100.0;
+const HEIGHT: f32 = 50.0;
+const EPSILON: f32 = 0.0001;
+
+// same layout as Collider::update, for a transform that's just a translation and a horizontal scale
+// i.e. what the enemies get from their LayoutPosition and Scale
+fn vertices(x: f32, y: f32, scale_x: f32) -> [f32; 8] {
+    let point = |local_x: f32, local_y: f32| (x + local_x * scale_x, y + local_y);
+    let lt = point(0.0, HEIGHT);
+    let lb = point(0.0, 0.0);
+    let rt = point(WIDTH, HEIGHT);
+    let rb = point(WIDTH, 0.0);
+    [lt.0, lt.1, lb.0, lb.1, rt.0, rt.1, rb.0, rb.1]
+}
+
+// stands in for an AlphaMask where only the left quarter of the cell is solid
+// e.g. a gun barrel that's drawn pointing left
+fn barrel_is_solid(basis: &QuadBasis, x: f32, y: f32) -> bool {
+    let (s, t) = basis.quad_coords(x, y);
+    (0.0..0.25).contains(&s) && (0.0..1.0).contains(&t)
+}
+
+fn assert_close(actual: (f32, f32), expected: (f32, f32)) {
+    assert!(
+        (actual.0 - expected.0).abs() < EPSILON && (actual.1 - expected.1).abs() < EPSILON,
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+#[test]
+fn facing_right_is_not_mirrored() {
+    let basis = QuadBasis::from_vertices(&vertices(200.0, 100.0, 1.0)).unwrap();
+
+    assert!(!basis.is_mirrored());
+    assert_close(basis.quad_coords(200.0, 100.0), (0.0, 0.0));
+    assert_close(basis.quad_coords(225.0, 125.0), (0.25, 0.5));
+    assert_close(basis.quad_coords(300.0, 150.0), (1.0, 1.0));
+}
+
+#[test]
+fn facing_left_is_mirrored() {
+    // with Scale.x = -1 the sprite extends to the left of its position
+    let basis = QuadBasis::from_vertices(&vertices(300.0, 100.0, -1.0)).unwrap();
+
+    assert!(basis.is_mirrored());
+    // the sprite's left edge is now on the right in world space
+    assert_close(basis.quad_coords(300.0, 100.0), (0.0, 0.0));
+    assert_close(basis.quad_coords(275.0, 125.0), (0.25, 0.5));
+    assert_close(basis.quad_coords(200.0, 150.0), (1.0, 1.0));
+}
+
+#[test]
+fn facing_left_hits_the_mirrored_side() {
+    let right = QuadBasis::from_vertices(&vertices(200.0, 100.0, 1.0)).unwrap();
+    let left = QuadBasis::from_vertices(&vertices(300.0, 100.0, -1.0)).unwrap();
+
+    // both cover x 200..300 in world space, the barrel is on opposite ends
+    assert!(barrel_is_solid(&right, 210.0, 125.0));
+    assert!(!barrel_is_solid(&right, 290.0, 125.0));
+
+    assert!(!barrel_is_solid(&left, 210.0, 125.0));
+    assert!(barrel_is_solid(&left, 290.0, 125.0));
+}
+
+#[test]
+fn outside_the_quad_is_out_of_range() {
+    let basis = QuadBasis::from_vertices(&vertices(300.0, 100.0, -1.0)).unwrap();
+
+    // just past the right edge in world space, i.e. behind the (flipped) left edge of the sprite
+    let (s, _) = basis.quad_coords(301.0, 125.0);
+    assert!(s < 0.0);
+    assert!(!barrel_is_solid(&basis, 301.0, 125.0));
+
+    let (_, t) = basis.quad_coords(250.0, 151.0);
+    assert!(t > 1.0);
+}
+
+#[test]
+fn collapsed_quad_has_no_basis() {
+    assert!(QuadBasis::from_vertices(&vertices(300.0, 100.0, 0.0)).is_none());
+}
+
+// two cells side by side, 0 has the barrel on the left quarter and 1 on the right quarter
+// everything else is transparent
+fn spritesheet() -> SpriteSheet {
+    let (width, height) = (WIDTH as u32 * 2, HEIGHT as u32);
+    let mut rgba = vec![0u8; (width * height * 4) as usize];
+    for row in 0..height {
+        for col in (0..25).chain(175..200) {
+            rgba[((row * width + col) * 4 + 3) as usize] = 255;
+        }
+    }
+
+    let bounds = |index: usize| Bounds {
+        x: index as f64 * WIDTH as f64,
+        y: 0.0,
+        width: WIDTH as f64,
+        height: HEIGHT as f64,
+    };
+
+    SpriteSheet {
+        cells: (0..2)
+            .map(|index| AtlasRegion {
+                page: 0,
+                bounds: bounds(index),
+                atlas_width: width as f32,
+                atlas_height: height as f32,
+            })
+            .collect(),
+        anchor_x: 0.0,
+        max_cell_width: WIDTH,
+        max_cell_height: HEIGHT,
+        cell_duration: 100.0,
+        masks: (0..2)
+            .map(|index| Arc::new(AlphaMask::from_rgba(&rgba, width, &bounds(index))))
+            .collect(),
+    }
+}
+
+fn collider(vertices: [f32; 8]) -> Collider {
+    Collider {
+        vertices,
+        width: WIDTH,
+        height: HEIGHT,
+    }
+}
+
+// a small fully solid square centered on x, y, like a bullet
+fn probe(x: f32, y: f32) -> CollisionEventTarget {
+    let point = |dx: f32, dy: f32| (x + dx, y + dy);
+    let lt = point(-2.0, 2.0);
+    let lb = point(-2.0, -2.0);
+    let rt = point(2.0, 2.0);
+    let rb = point(2.0, -2.0);
+
+    CollisionEventTarget {
+        entity: EntityId::dead(),
+        vertices: [lt.0, lt.1, lb.0, lb.1, rt.0, rt.1, rb.0, rb.1],
+        uvs: [0.0; 8],
+        page: 0,
+        mask: Arc::new(AlphaMask::solid(4, 4)),
+    }
+}
+
+fn solid_at(target: &CollisionEventTarget, x: f32, y: f32) -> bool {
+    target.is_solid_at(&target.basis().unwrap(), x, y)
+}
+
+// checked with the enemy as either side of the event, it's the same answer
+fn hits(enemy: &CollisionEventTarget, x: f32, y: f32) -> bool {
+    let enemy_first = CollisionEvent {
+        a: enemy.clone(),
+        b: probe(x, y),
+        occlusion_query: None,
+    }
+    .check_alpha_masks();
+
+    let probe_first = CollisionEvent {
+        a: probe(x, y),
+        b: enemy.clone(),
+        occlusion_query: None,
+    }
+    .check_alpha_masks();
+
+    assert_eq!(enemy_first, probe_first);
+    enemy_first
+}
+
+#[test]
+fn masks_are_per_cell() {
+    let sheet = spritesheet();
+
+    assert!(sheet.masks[0].get(0, 0) && !sheet.masks[0].get(99, 0));
+    assert!(!sheet.masks[1].get(0, 0) && sheet.masks[1].get(99, 0));
+}
+
+#[test]
+fn targets_use_the_current_cell() {
+    let sheet = spritesheet();
+    let collider = collider(vertices(200.0, 100.0, 1.0));
+
+    let first = CollisionEventTarget::from_cell(EntityId::dead(), &collider, &sheet, 0);
+    let second = CollisionEventTarget::from_cell(EntityId::dead(), &collider, &sheet, 1);
+
+    assert!(Arc::ptr_eq(&first.mask, &sheet.masks[0]));
+    assert!(Arc::ptr_eq(&second.mask, &sheet.masks[1]));
+    assert_eq!(second.uvs, sheet.cells[1].uvs().data);
+
+    // the left end only has a barrel in the first cell, the right end only in the second
+    assert!(solid_at(&first, 210.0, 125.0));
+    assert!(!solid_at(&first, 290.0, 125.0));
+    assert!(!solid_at(&second, 210.0, 125.0));
+    assert!(solid_at(&second, 290.0, 125.0));
+
+    assert!(hits(&first, 210.0, 125.0));
+    assert!(!hits(&first, 250.0, 125.0));
+    assert!(!hits(&second, 210.0, 125.0));
+    assert!(hits(&second, 290.0, 125.0));
+}
+
+#[test]
+fn facing_left_samples_the_mask_mirrored() {
+    let sheet = spritesheet();
+    // same world space as above, but flipped
+    let collider = collider(vertices(300.0, 100.0, -1.0));
+
+    let first = CollisionEventTarget::from_cell(EntityId::dead(), &collider, &sheet, 0);
+    let second = CollisionEventTarget::from_cell(EntityId::dead(), &collider, &sheet, 1);
+
+    assert!(!solid_at(&first, 210.0, 125.0));
+    assert!(solid_at(&first, 290.0, 125.0));
+    assert!(solid_at(&second, 210.0, 125.0));
+    assert!(!solid_at(&second, 290.0, 125.0));
+
+    assert!(!hits(&first, 210.0, 125.0));
+    assert!(hits(&first, 290.0, 125.0));
+    assert!(hits(&second, 210.0, 125.0));
+    assert!(!hits(&second, 290.0, 125.0));
+}
+
+#[test]
+fn nothing_hits_outside_the_quad() {
+    let sheet = spritesheet();
+    let collider = collider(vertices(300.0, 100.0, -1.0));
+    let target = CollisionEventTarget::from_cell(EntityId::dead(), &collider, &sheet, 0);
+
+    assert!(!solid_at(&target, 301.0, 125.0));
+    assert!(!solid_at(&target, 290.0, 151.0));
+    assert!(!hits(&target, 310.0, 125.0));
+}
+
+#[test]
+fn collapsed_targets_never_hit() {
+    let sheet = spritesheet();
+    let collider = collider(vertices(200.0, 100.0, 0.0));
+    let target = CollisionEventTarget::from_cell(EntityId::dead(), &collider, &sheet, 0);
+
+    assert!(target.basis().is_none());
+    assert!(!hits(&target, 200.0, 125.0));
+}
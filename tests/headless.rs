@@ -0,0 +1,235 @@
+// A whole run without a browser: the sim world from media/, stepped frame by frame
+//
+// cargo test --test headless
+//
+// unlike the std-only tests this needs most of the game, which it gets from the lib (src/lib.rs)
+
+use nalgebra_glm::Vec3;
+use not_a_game::{
+    bomber::data::{Bomber, BomberViewMut},
+    collision::{
+        data::Collider,
+        layer::{CollisionLayer, CollisionLayerKind, CollisionRulesView},
+    },
+    difficulty::data::{Difficulty, DifficultyRamp},
+    enemy::data::EnemyKind,
+    layout::data::{LayoutAnchor, LayoutPosition},
+    level::data::BombKind,
+    projectiles::data::{
+        Projectile, ProjectileMovement, ProjectileSpawnerViewMut, ProjectileSpritesView,
+        ProjectileToSpawn,
+    },
+    sim::{
+        headless::HeadlessMedia,
+        workloads::{init_headless, sim_step},
+    },
+    stats::data::RunStats,
+    tick::{FrameCount, PauseTick},
+};
+use shipyard::{AddComponent, UniqueView, UniqueViewMut, ViewMut, World};
+use shipyard_scenegraph::prelude::SceneGraphStoragesMut;
+
+const MEDIA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/media");
+// the mainloop's fixed step
+const DELTA: f64 = 1000.0 / 60.0;
+// ten minutes, way more than it takes to get through every bomb
+const MAX_FRAMES: u64 = 60 * 60 * 10;
+
+fn world(seed: u64) -> World {
+    let world = World::new();
+    init_headless(&world, seed, HeadlessMedia::load(MEDIA_DIR).unwrap(), None);
+    world
+}
+
+fn running(world: &World) -> bool {
+    *world.borrow::<UniqueView<PauseTick>>().unwrap() == PauseTick::Running
+}
+
+// until the run is over one way or the other, returns how many steps that took
+fn run_to_end(world: &World) -> u64 {
+    let mut frames = 0;
+    while running(world) && frames < MAX_FRAMES {
+        sim_step(world, DELTA);
+        frames += 1;
+    }
+    frames
+}
+
+#[test]
+fn steps_advance_the_frame_count_and_time() {
+    let world = world(1);
+
+    for _ in 0..30 {
+        sim_step(&world, DELTA);
+    }
+
+    assert!(running(&world));
+    assert_eq!(
+        *world.borrow::<UniqueView<FrameCount>>().unwrap(),
+        FrameCount(30)
+    );
+    let stats = world.borrow::<UniqueView<RunStats>>().unwrap();
+    assert!((stats.time_survived - 30.0 * DELTA).abs() < 0.001);
+}
+
+#[test]
+fn bombs_drop_and_the_run_ends() {
+    let world = world(1);
+
+    let frames = run_to_end(&world);
+
+    assert!(!running(&world), "still running after {frames} frames");
+    assert!(matches!(
+        *world.borrow::<UniqueView<PauseTick>>().unwrap(),
+        PauseTick::GameOver {} | PauseTick::Winner {}
+    ));
+    assert!(world.borrow::<UniqueView<Bomber>>().unwrap().bombs_dropped > 0);
+    assert_eq!(world.borrow::<UniqueView<FrameCount>>().unwrap().0, frames);
+}
+
+#[test]
+fn the_same_seed_plays_out_the_same() {
+    let a = world(42);
+    let b = world(42);
+
+    assert_eq!(run_to_end(&a), run_to_end(&b));
+    assert_eq!(
+        *a.borrow::<UniqueView<RunStats>>().unwrap(),
+        *b.borrow::<UniqueView<RunStats>>().unwrap()
+    );
+    assert_eq!(
+        *a.borrow::<UniqueView<PauseTick>>().unwrap(),
+        *b.borrow::<UniqueView<PauseTick>>().unwrap()
+    );
+}
+
+// the bomber stops after CONFIG.bombs_to_win, so the time ramps have to get going before then
+#[test]
+fn time_ramps_reach_their_last_stage() {
+    let presets = HeadlessMedia::load(MEDIA_DIR).unwrap().difficulty.presets;
+
+    for preset in presets
+        .iter()
+        .filter(|preset| preset.ramp == DifficultyRamp::Time)
+    {
+        let last_stage = preset.stages.last().unwrap().at;
+
+        for seed in 1..=3 {
+            let world = world(seed);
+            assert!(world
+                .borrow::<UniqueViewMut<Difficulty>>()
+                .unwrap()
+                .select(&preset.name));
+
+            let mut frames = 0;
+            while running(&world)
+                && world
+                    .borrow::<UniqueView<Bomber>>()
+                    .unwrap()
+                    .drop_countdown
+                    .is_some()
+                && frames < MAX_FRAMES
+            {
+                sim_step(&world, DELTA);
+                frames += 1;
+            }
+
+            assert!(
+                world
+                    .borrow::<UniqueView<Bomber>>()
+                    .unwrap()
+                    .drop_countdown
+                    .is_none(),
+                "{} (seed {seed}) ended before the last bomb",
+                preset.name
+            );
+            // the last drop was in the last stage
+            let seconds = world
+                .borrow::<UniqueView<RunStats>>()
+                .unwrap()
+                .time_survived
+                / 1000.0;
+            assert!(
+                seconds >= last_stage,
+                "{} (seed {seed}) dropped every bomb by {seconds:.1}s, the last stage is at {last_stage}s",
+                preset.name
+            );
+        }
+    }
+}
+
+// a single bomb at the top of the screen, with a rocket parked right on it that never moves
+// nothing else drops, so whatever gets recorded is down to that one pair
+fn rocket_on_a_bomb(world: &World) {
+    world.run(
+        |mut bomber: BomberViewMut, mut spawner: ProjectileSpawnerViewMut| {
+            bomber.drop_countdown = None;
+            spawner.to_spawn.push(ProjectileToSpawn::Bomb {
+                x: 0.5,
+                acceleration: 0.0,
+                kind: BombKind::Standard,
+            });
+        },
+    );
+
+    world.run(
+        |mut sg_storages: SceneGraphStoragesMut,
+         mut projectiles: ViewMut<Projectile>,
+         mut positions: ViewMut<LayoutPosition>,
+         mut anchors: ViewMut<LayoutAnchor>,
+         mut colliders: ViewMut<Collider>,
+         mut layers: ViewMut<CollisionLayer>,
+         rules: CollisionRulesView,
+         sprites: ProjectileSpritesView| {
+            let rocket = &sprites.bad_rocket;
+            let entity = sg_storages.spawn_child_trs(None, None, None, None);
+
+            (
+                &mut projectiles,
+                &mut positions,
+                &mut anchors,
+                &mut colliders,
+                &mut layers,
+            )
+                .add_component_unchecked(
+                    entity,
+                    (
+                        Projectile {
+                            width: rocket.width,
+                            height: rocket.height,
+                            page: rocket.page,
+                            uvs: rocket.uvs,
+                            mask: rocket.mask.clone(),
+                            movement: ProjectileMovement::Rocket {
+                                rotation: 0.0,
+                                thrust_speed: 0.0,
+                                rotation_speed: 0.0,
+                            },
+                            shooter: Some(EnemyKind::Four),
+                        },
+                        LayoutPosition::new(Vec3::new(0.5, 1.0, 0.0)),
+                        LayoutAnchor::new(Vec3::new(-rocket.width / 2.0, 0.0, 0.0)),
+                        Collider::default(),
+                        rules.layer(CollisionLayerKind::Rocket),
+                    ),
+                );
+        },
+    );
+}
+
+#[test]
+fn a_bomb_is_only_intercepted_once() {
+    let world = world(1);
+    rocket_on_a_bomb(&world);
+
+    // plenty of ticks with the two of them overlapping before the explosion takes them away
+    for _ in 0..60 {
+        sim_step(&world, DELTA);
+    }
+
+    let stats = world.borrow::<UniqueView<RunStats>>().unwrap();
+    assert_eq!(stats.total_intercepts(), 1);
+    // and it's recorded against the one that shot it
+    assert_eq!(stats.intercepts.len(), 1);
+    assert_eq!(stats.intercepts.get(&EnemyKind::Four), Some(&1));
+}
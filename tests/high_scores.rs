@@ -0,0 +1,138 @@
+// Ranking and persistence of the best runs, against in-memory storage instead of the browser's
+//
+// cargo test --test high_scores
+//
+// the records hang off RunStats, so like tests/headless.rs this goes through the lib
+
+use std::{cell::RefCell, rc::Rc};
+
+use not_a_game::{
+    high_scores::{
+        data::{HighScores, RunRecord},
+        storage::{MemoryStorage, ScoreStorage},
+    },
+    prelude::Result,
+};
+
+fn record(score: u32, timestamp: u64) -> RunRecord {
+    RunRecord {
+        timestamp,
+        duration: 1000.0,
+        score,
+        seed: timestamp,
+        mode: "normal".to_string(),
+    }
+}
+
+fn scores(records: &[RunRecord]) -> Vec<u32> {
+    records.iter().map(|record| record.score).collect()
+}
+
+// MemoryStorage, but the test keeps a handle on what was written
+#[derive(Clone, Default)]
+struct SharedStorage(Rc<RefCell<Option<String>>>);
+
+impl ScoreStorage for SharedStorage {
+    fn read(&self) -> Result<Option<String>> {
+        Ok(self.0.borrow().clone())
+    }
+
+    fn write(&mut self, data: &str) -> Result<()> {
+        *self.0.borrow_mut() = Some(data.to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn best_is_ranked_highest_first() {
+    let mut high_scores = HighScores::load(Box::new(MemoryStorage::new()));
+
+    assert_eq!(high_scores.add(record(200, 1)).unwrap(), Some(0));
+    assert_eq!(high_scores.add(record(500, 2)).unwrap(), Some(0));
+    assert_eq!(high_scores.add(record(300, 3)).unwrap(), Some(1));
+    assert_eq!(high_scores.add(record(100, 4)).unwrap(), Some(3));
+
+    assert_eq!(scores(&high_scores.best), [500, 300, 200, 100]);
+    // history is just the order they came in, latest first
+    assert_eq!(scores(&high_scores.history), [100, 300, 500, 200]);
+}
+
+#[test]
+fn ties_go_to_the_earlier_run() {
+    let mut high_scores = HighScores::load(Box::new(MemoryStorage::new()));
+
+    high_scores.add(record(300, 1)).unwrap();
+    assert_eq!(high_scores.add(record(300, 2)).unwrap(), Some(1));
+
+    let timestamps: Vec<u64> = high_scores.best.iter().map(|r| r.timestamp).collect();
+    assert_eq!(timestamps, [1, 2]);
+}
+
+#[test]
+fn best_and_history_are_truncated() {
+    let mut high_scores = HighScores::load(Box::new(MemoryStorage::new()));
+
+    for i in 0..HighScores::MAX_HISTORY as u64 + 5 {
+        high_scores.add(record(100 + i as u32, i)).unwrap();
+    }
+
+    assert_eq!(high_scores.best.len(), HighScores::MAX_BEST);
+    assert_eq!(high_scores.history.len(), HighScores::MAX_HISTORY);
+    assert_eq!(high_scores.best[0].score, 124);
+    assert_eq!(high_scores.best[HighScores::MAX_BEST - 1].score, 115);
+    assert_eq!(high_scores.history[0].timestamp, 24);
+
+    // lower than everything on a full list doesn't make it in, but is still history
+    assert_eq!(high_scores.add(record(1, 100)).unwrap(), None);
+    assert_eq!(high_scores.best.len(), HighScores::MAX_BEST);
+    assert_eq!(high_scores.history[0].timestamp, 100);
+}
+
+#[test]
+fn scores_survive_a_reload() {
+    let storage = SharedStorage::default();
+
+    let mut high_scores = HighScores::load(Box::new(storage.clone()));
+    high_scores.add(record(200, 1)).unwrap();
+    high_scores.add(record(400, 2)).unwrap();
+
+    let reloaded = HighScores::load(Box::new(storage));
+    assert_eq!(reloaded.best, high_scores.best);
+    assert_eq!(reloaded.history, high_scores.history);
+}
+
+#[test]
+fn other_versions_are_dropped() {
+    let mut storage = MemoryStorage::new();
+    storage
+        .write(&format!(
+            r#"{{"version":{},"best":[{{"timestamp":1,"duration":1000.0,"score":200,"seed":1,"mode":"normal"}}],"history":[]}}"#,
+            HighScores::VERSION + 1
+        ))
+        .unwrap();
+
+    let high_scores = HighScores::load(Box::new(storage));
+    assert!(high_scores.best.is_empty());
+    assert!(high_scores.history.is_empty());
+}
+
+#[test]
+fn unreadable_data_is_dropped() {
+    let mut storage = MemoryStorage::new();
+    storage.write("not json").unwrap();
+
+    let high_scores = HighScores::load(Box::new(storage));
+    assert!(high_scores.best.is_empty());
+}
+
+#[test]
+fn the_current_version_loads() {
+    let storage = SharedStorage::default();
+    HighScores::load(Box::new(storage.clone()))
+        .add(record(200, 1))
+        .unwrap();
+
+    let stored = storage.0.borrow().clone().unwrap();
+    assert!(stored.contains(&format!(r#""version":{}"#, HighScores::VERSION)));
+    assert_eq!(scores(&HighScores::load(Box::new(storage)).best), [200]);
+}
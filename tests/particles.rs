@@ -0,0 +1,187 @@
+// The particle simulation: curves, emission counts and how particles move and die
+//
+// cargo test --test particles
+//
+// like tests/atlas_packer.rs, the module only depends on std so it's pulled in directly
+#[path = "../src/particles/sim.rs"]
+#[allow(dead_code)]
+mod sim;
+
+use sim::{step, Curve, CurveError, EmitterDef, EmitterState, Particle, ParticleRng};
+
+fn def() -> EmitterDef {
+    EmitterDef {
+        rate: 10.0,
+        burst: 0,
+        duration: None,
+        lifetime: (1.0, 1.0),
+        speed: (100.0, 100.0),
+        direction: 90.0,
+        spread: 0.0,
+        gravity: 0.0,
+        drag: 0.0,
+        spin: (0.0, 0.0),
+        size: Curve::constant(4.0),
+        color: Curve::constant([1.0, 1.0, 1.0, 1.0]),
+    }
+}
+
+fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() < 0.001
+}
+
+#[test]
+fn curve_interpolates_between_keys() {
+    let curve = Curve::new(vec![(0.0, 0.0), (0.5, 10.0), (1.0, 0.0)]).unwrap();
+
+    assert!(approx(curve.sample(0.0), 0.0));
+    assert!(approx(curve.sample(0.25), 5.0));
+    assert!(approx(curve.sample(0.5), 10.0));
+    assert!(approx(curve.sample(0.75), 5.0));
+    assert!(approx(curve.sample(1.0), 0.0));
+
+    let color = Curve::new(vec![
+        (0.0, [1.0, 1.0, 1.0, 1.0]),
+        (1.0, [0.0, 0.5, 1.0, 0.0]),
+    ])
+    .unwrap();
+    let mid = color.sample(0.5);
+    assert!(
+        approx(mid[0], 0.5) && approx(mid[1], 0.75) && approx(mid[2], 1.0) && approx(mid[3], 0.5)
+    );
+}
+
+#[test]
+fn curve_is_flat_outside_its_keys() {
+    let curve = Curve::new(vec![(0.2, 1.0), (0.8, 3.0)]).unwrap();
+
+    assert!(approx(curve.sample(0.0), 1.0));
+    assert!(approx(curve.sample(1.0), 3.0));
+    assert!(approx(Curve::constant(7.0).sample(0.6), 7.0));
+}
+
+#[test]
+fn bad_curves_are_rejected() {
+    assert_eq!(Curve::<f32>::new(vec![]), Err(CurveError::Empty));
+    assert_eq!(
+        Curve::new(vec![(0.0, 1.0), (1.5, 1.0)]),
+        Err(CurveError::OutOfRange)
+    );
+    assert_eq!(
+        Curve::new(vec![(0.6, 1.0), (0.4, 1.0)]),
+        Err(CurveError::Unsorted)
+    );
+}
+
+#[test]
+fn rate_carries_over_between_steps() {
+    let def = def();
+    let mut state = EmitterState::default();
+
+    // 10 per second at 60fps is one every 6 frames, give or take rounding
+    let total: u32 = (0..60).map(|_| state.advance(&def, 1.0 / 60.0)).sum();
+    assert!((9..=10).contains(&total), "spawned {total}");
+    assert!(!state.is_finished(&def));
+}
+
+#[test]
+fn burst_then_stop_after_duration() {
+    let def = EmitterDef {
+        rate: 0.0,
+        burst: 12,
+        duration: Some(0.5),
+        ..def()
+    };
+    let mut state = EmitterState::default();
+
+    assert_eq!(state.advance(&def, 0.1), 12);
+    assert_eq!(state.advance(&def, 0.1), 0);
+    assert!(!state.is_finished(&def));
+
+    for _ in 0..5 {
+        state.advance(&def, 0.1);
+    }
+    assert!(state.is_finished(&def));
+}
+
+#[test]
+fn rate_stops_partway_through_a_step() {
+    let def = EmitterDef {
+        rate: 100.0,
+        duration: Some(0.25),
+        ..def()
+    };
+    let mut state = EmitterState::default();
+
+    // only a quarter of a second of emission, even though the step is a whole second
+    assert_eq!(state.advance(&def, 1.0), 25);
+    assert_eq!(state.advance(&def, 1.0), 0);
+}
+
+#[test]
+fn spawn_goes_in_the_cone_relative_to_the_rotation() {
+    let def = EmitterDef {
+        direction: 0.0,
+        spread: 90.0,
+        ..def()
+    };
+    let mut rng = ParticleRng::new(42);
+    // attached to something facing straight up
+    let rotation = std::f32::consts::FRAC_PI_2;
+
+    for _ in 0..100 {
+        let particle = def.spawn(&mut rng, 5.0, 6.0, rotation);
+        let angle = particle.vy.atan2(particle.vx).to_degrees();
+        assert!(
+            (45.0 - 0.01..=135.0 + 0.01).contains(&angle),
+            "angle {angle}"
+        );
+        assert!(approx(particle.vx.hypot(particle.vy), 100.0));
+        assert_eq!((particle.x, particle.y), (5.0, 6.0));
+    }
+}
+
+#[test]
+fn step_applies_gravity_and_drops_dead_particles() {
+    let def = EmitterDef {
+        gravity: -100.0,
+        ..def()
+    };
+    let particle = Particle {
+        x: 0.0,
+        y: 0.0,
+        vx: 10.0,
+        vy: 0.0,
+        rotation: 0.0,
+        spin: 0.0,
+        age: 0.0,
+        lifetime: 1.0,
+    };
+    let mut particles = vec![
+        particle,
+        Particle {
+            age: 0.95,
+            ..particle
+        },
+    ];
+
+    step(&mut particles, &def, 0.1);
+
+    assert_eq!(particles.len(), 1);
+    let particle = particles[0];
+    assert!(approx(particle.x, 1.0));
+    assert!(approx(particle.vy, -10.0));
+    assert!(particle.y < 0.0);
+    assert!(approx(particle.life(), 0.1));
+}
+
+#[test]
+fn rng_stays_in_range() {
+    let mut rng = ParticleRng::new(0);
+    for _ in 0..1000 {
+        let value = rng.next_f32();
+        assert!((0.0..1.0).contains(&value));
+        let ranged = rng.range((2.0, 3.0));
+        assert!((2.0..3.0).contains(&ranged));
+    }
+}
@@ -0,0 +1,150 @@
+// Fitting the design resolution into different windows
+//
+// cargo test --test scaling
+//
+// like tests/atlas_packer.rs, the module only depends on std so it's pulled in directly
+#[path = "../src/layout/scaling.rs"]
+#[allow(dead_code)]
+mod scaling;
+
+use scaling::{ScaleMode, ViewportFit, DESIGN_HEIGHT, DESIGN_WIDTH, FOOTER_HEIGHT};
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 0.001
+}
+
+#[test]
+fn design_sized_window_is_the_same_in_every_mode() {
+    for mode in ScaleMode::ALL {
+        let fit = ViewportFit::new(mode, DESIGN_WIDTH, DESIGN_HEIGHT);
+        assert_eq!(
+            fit,
+            ViewportFit {
+                canvas_x: 0.0,
+                canvas_y: 0.0,
+                canvas_width: DESIGN_WIDTH,
+                canvas_height: DESIGN_HEIGHT,
+                view_width: DESIGN_WIDTH,
+                view_height: DESIGN_HEIGHT,
+            },
+            "{}",
+            mode.name()
+        );
+    }
+}
+
+#[test]
+fn letterbox_adds_bars_on_the_long_side() {
+    // wider than 16:9, so bars left and right
+    let fit = ViewportFit::new(ScaleMode::Letterbox, 2000.0, 900.0);
+    assert!(approx(fit.canvas_height, 900.0));
+    assert!(approx(fit.canvas_width, 1600.0));
+    assert!(approx(fit.canvas_x, 200.0));
+    assert!(approx(fit.canvas_y, 0.0));
+    assert_eq!(
+        (fit.view_width, fit.view_height),
+        (DESIGN_WIDTH, DESIGN_HEIGHT)
+    );
+
+    // taller, so top and bottom
+    let fit = ViewportFit::new(ScaleMode::Letterbox, 960.0, 1000.0);
+    assert!(approx(fit.canvas_width, 960.0));
+    assert!(approx(fit.canvas_height, 540.0));
+    assert!(approx(fit.canvas_x, 0.0));
+    assert!(approx(fit.canvas_y, 230.0));
+}
+
+#[test]
+fn crop_fills_the_window_and_shows_less_of_the_world() {
+    let fit = ViewportFit::new(ScaleMode::Crop, 2000.0, 900.0);
+    assert_eq!((fit.canvas_width, fit.canvas_height), (2000.0, 900.0));
+    // scaled to the width, so the top and bottom are cut off
+    assert!(approx(fit.view_width, DESIGN_WIDTH));
+    assert!(approx(fit.view_height, DESIGN_WIDTH * 900.0 / 2000.0));
+    assert!(fit.view_height < DESIGN_HEIGHT);
+}
+
+#[test]
+fn stretch_fills_the_window_with_the_whole_world() {
+    let fit = ViewportFit::new(ScaleMode::Stretch, 800.0, 800.0);
+    assert_eq!((fit.canvas_width, fit.canvas_height), (800.0, 800.0));
+    assert_eq!(
+        (fit.view_width, fit.view_height),
+        (DESIGN_WIDTH, DESIGN_HEIGHT)
+    );
+}
+
+#[test]
+fn backing_store_follows_the_pixel_ratio() {
+    let fit = ViewportFit::new(ScaleMode::Letterbox, 1280.0, 720.0);
+    assert_eq!(fit.backing_size(1.0), (1280, 720));
+    assert_eq!(fit.backing_size(2.0), (2560, 1440));
+    assert_eq!(fit.backing_size(1.5), (1920, 1080));
+    // nonsense ratios fall back to 1
+    assert_eq!(fit.backing_size(0.0), (1280, 720));
+}
+
+#[test]
+fn empty_window_still_fits() {
+    let fit = ViewportFit::new(ScaleMode::Letterbox, 0.0, 0.0);
+    assert!(fit.canvas_width > 0.0 && fit.canvas_height > 0.0);
+    assert_eq!(fit.backing_size(1.0), (1, 1));
+}
+
+#[test]
+fn canvas_points_map_to_design_units() {
+    // half size, so one css pixel is two design units whatever the pixel ratio
+    let fit = ViewportFit::new(ScaleMode::Letterbox, 960.0, 540.0);
+
+    // the dom goes down, the world goes up
+    let (x, y) = fit.canvas_to_design(0.0, 0.0);
+    assert!(approx(x, 0.0) && approx(y, DESIGN_HEIGHT));
+    let (x, y) = fit.canvas_to_design(1.0, 1.0);
+    assert!(approx(x, DESIGN_WIDTH) && approx(y, 0.0));
+    let (x, y) = fit.canvas_to_design(0.25, 0.5);
+    assert!(approx(x, 480.0) && approx(y, 540.0));
+}
+
+#[test]
+fn cropped_canvas_points_are_offset_into_the_design_area() {
+    let fit = ViewportFit::new(ScaleMode::Crop, 2000.0, 900.0);
+    let cut = (DESIGN_HEIGHT - fit.view_height) / 2.0;
+
+    // the bottom of the canvas isn't the bottom of the design area anymore
+    let (_, y) = fit.canvas_to_design(0.0, 1.0);
+    assert!(approx(y, cut));
+    let (_, y) = fit.canvas_to_design(0.0, 0.0);
+    assert!(approx(y, DESIGN_HEIGHT - cut));
+}
+
+#[test]
+fn design_to_window_is_the_inverse() {
+    for mode in ScaleMode::ALL {
+        let fit = ViewportFit::new(mode, 1300.0, 1000.0);
+        let (x, y) = fit.design_to_window(500.0, 300.0);
+        let (u, v) = (
+            (x - fit.canvas_x) / fit.canvas_width,
+            (y - fit.canvas_y) / fit.canvas_height,
+        );
+        let (x, y) = fit.canvas_to_design(u, v);
+        assert!(approx(x, 500.0) && approx(y, 300.0), "{}", mode.name());
+    }
+}
+
+#[test]
+fn footer_covers_the_footer_height_at_any_scale() {
+    // letterboxed with bars on top and bottom, the footer sits on the canvas, not the window
+    let fit = ViewportFit::new(ScaleMode::Letterbox, 960.0, 1000.0);
+    let (left, top, width, height) = fit.footer();
+    assert!(approx(left, 0.0) && approx(width, 960.0));
+    assert!(approx(height, FOOTER_HEIGHT / 2.0));
+    assert!(approx(top + height, fit.canvas_y + fit.canvas_height));
+
+    let fit = ViewportFit::new(
+        ScaleMode::Letterbox,
+        DESIGN_WIDTH * 2.0,
+        DESIGN_HEIGHT * 2.0,
+    );
+    let (_, _, _, height) = fit.footer();
+    assert!(approx(height, FOOTER_HEIGHT * 2.0));
+}
@@ -0,0 +1,147 @@
+// The rules every enemy's weapon follows: cooldown, running dry and reloading, and charged shots
+//
+// cargo test --test weapon
+//
+// the stats come from media/data/weapons.json
+
+use not_a_game::enemy::{
+    attack::data::{AttackStats, Weapon, WeaponStats},
+    data::EnemyKind,
+};
+
+const WEAPONS_JSON: &str = include_str!("../media/data/weapons.json");
+
+fn stats(magazine: u32, charge: Option<f64>) -> AttackStats {
+    AttackStats {
+        cooldown: 100.0,
+        magazine,
+        reload: 1000.0,
+        charge,
+    }
+}
+
+#[test]
+fn the_shipped_stats_load() {
+    let weapons = WeaponStats::from_json(WEAPONS_JSON).unwrap();
+
+    assert_eq!(weapons.get(EnemyKind::Two).magazine, 6);
+    assert!(weapons.get(EnemyKind::Four).charge.is_some());
+    assert!(weapons.get(EnemyKind::One).charge.is_none());
+}
+
+#[test]
+fn bad_stats_are_rejected() {
+    let with = |four: &str| {
+        let stats = r#"{"cooldown": 100, "magazine": 1, "reload": 100}"#;
+        format!(r#"{{"one": {stats}, "two": {stats}, "three": {stats}, "four": {four}}}"#)
+    };
+
+    assert!(
+        WeaponStats::from_json(&with(r#"{"cooldown": 100, "magazine": 1, "reload": 100}"#)).is_ok()
+    );
+    assert!(
+        WeaponStats::from_json(&with(r#"{"cooldown": 100, "magazine": 0, "reload": 100}"#))
+            .is_err()
+    );
+    assert!(
+        WeaponStats::from_json(&with(r#"{"cooldown": -1, "magazine": 1, "reload": 100}"#)).is_err()
+    );
+    assert!(WeaponStats::from_json(&with(
+        r#"{"cooldown": 100, "magazine": 1, "reload": 100, "ammo": 3}"#
+    ))
+    .is_err());
+    // missing one of the kinds
+    assert!(
+        WeaponStats::from_json(r#"{"one": {"cooldown": 100, "magazine": 1, "reload": 100}}"#)
+            .is_err()
+    );
+}
+
+#[test]
+fn nothing_fires_while_cooling_down() {
+    let mut weapon = Weapon::new(stats(3, None));
+
+    assert!(weapon.trigger_pressed());
+    assert_eq!(weapon.ammo, 2);
+
+    assert!(!weapon.trigger_pressed());
+    weapon.update(99.0);
+    assert!(!weapon.trigger_pressed());
+    assert_eq!(weapon.ammo, 2);
+
+    weapon.update(1.0);
+    assert!(weapon.trigger_pressed());
+    assert_eq!(weapon.ammo, 1);
+}
+
+#[test]
+fn running_dry_reloads() {
+    let mut weapon = Weapon::new(stats(2, None));
+
+    assert!(weapon.trigger_pressed());
+    weapon.update(100.0);
+    assert!(weapon.trigger_pressed());
+    assert_eq!(weapon.ammo, 0);
+    assert!(weapon.is_reloading());
+    assert!(weapon.status().reloading);
+
+    // the cooldown is long over, but the reload isn't
+    weapon.update(500.0);
+    assert!(!weapon.trigger_pressed());
+
+    weapon.update(500.0);
+    assert!(!weapon.is_reloading());
+    assert_eq!(weapon.ammo, 2);
+    assert!(weapon.trigger_pressed());
+}
+
+#[test]
+fn releasing_early_doesnt_fire() {
+    let mut weapon = Weapon::new(stats(1, Some(500.0)));
+
+    // charged weapons fire on release, not on press
+    assert!(!weapon.trigger_pressed());
+    assert_eq!(weapon.status().charged, Some(false));
+
+    weapon.update(499.0);
+    assert!(!weapon.trigger_released());
+    assert_eq!(weapon.ammo, 1);
+    assert_eq!(weapon.status().charged, None);
+
+    // and the next press starts over
+    assert!(!weapon.trigger_pressed());
+    weapon.update(499.0);
+    assert!(!weapon.trigger_released());
+    assert_eq!(weapon.ammo, 1);
+}
+
+#[test]
+fn releasing_when_charged_fires() {
+    let mut weapon = Weapon::new(stats(1, Some(500.0)));
+
+    assert!(!weapon.trigger_pressed());
+    weapon.update(250.0);
+    // key repeat while holding doesn't restart the charge
+    assert!(!weapon.trigger_pressed());
+    weapon.update(250.0);
+    assert_eq!(weapon.status().charged, Some(true));
+
+    assert!(weapon.trigger_released());
+    assert_eq!(weapon.ammo, 0);
+    assert!(weapon.is_reloading());
+
+    // nothing to release without a press
+    assert!(!weapon.trigger_released());
+}
+
+#[test]
+fn a_cancelled_charge_doesnt_fire() {
+    let mut weapon = Weapon::new(stats(1, Some(500.0)));
+
+    weapon.trigger_pressed();
+    weapon.update(600.0);
+    weapon.cancel_charge();
+
+    assert!(!weapon.trigger_released());
+    assert_eq!(weapon.ammo, 1);
+}